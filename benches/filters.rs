@@ -0,0 +1,99 @@
+// game-translator/benches/filters.rs
+
+// ============================================================================
+// BENCHMARK - Filtros de pré-processamento (src/filters.rs)
+// ============================================================================
+//
+// Mede erosão/dilatação e Sobel no caso que importa: uma região de
+// legenda de 1200x100 com upscale 3x (3600x300).
+//
+// Uso:
+//   cargo bench --bench filters
+//   cargo bench --bench filters --features parallel
+//
+// Cada grupo compara a versão rápida ("rapida") com a implementação
+// antiga ("ingenua", src/filters/naive.rs) na mesma imagem, então o
+// relatório do criterion já mostra o ganho lado a lado.
+//
+// ============================================================================
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/filters.rs"]
+mod filters;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use filters::{naive, Morphology};
+
+/// Legenda de 1200x100 com upscale 3x
+const WIDTH: usize = 3600;
+const HEIGHT: usize = 300;
+
+/// A versão ingênua leva dezenas de ms por iteração no raio 4
+const SAMPLE_SIZE: usize = 10;
+
+/// "Texto" claro em faixas sobre fundo com ruído (determinístico)
+fn subtitle_frame() -> Vec<u8> {
+    let mut state = 0x2545_f491u32;
+    let mut pixels = Vec::with_capacity(WIDTH * HEIGHT);
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let noise = (state >> 27) as u8;
+            let text = (90..210).contains(&y) && (x / 9) % 5 < 3;
+            pixels.push(if text { 230 + noise } else { 40 + noise * 2 });
+        }
+    }
+
+    pixels
+}
+
+fn bench_morphology(c: &mut Criterion) {
+    let frame = subtitle_frame();
+
+    for (name, op) in [("erode", Morphology::Erode), ("dilate", Morphology::Dilate)] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(SAMPLE_SIZE);
+        group.throughput(Throughput::Elements((WIDTH * HEIGHT) as u64));
+
+        for radius in [1, 2, 4] {
+            group.bench_with_input(BenchmarkId::new("rapida", radius), &radius, |b, &radius| {
+                b.iter(|| filters::morphology(black_box(&frame), WIDTH, HEIGHT, radius, op))
+            });
+            group.bench_with_input(
+                BenchmarkId::new("ingenua", radius),
+                &radius,
+                |b, &radius| {
+                    b.iter(|| naive::morphology(black_box(&frame), WIDTH, HEIGHT, radius, op))
+                },
+            );
+        }
+
+        group.finish();
+    }
+}
+
+fn bench_sobel(c: &mut Criterion) {
+    let frame = subtitle_frame();
+    let threshold = 60;
+
+    let mut group = c.benchmark_group("sobel");
+    group.sample_size(SAMPLE_SIZE);
+    group.throughput(Throughput::Elements((WIDTH * HEIGHT) as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("rapida", threshold),
+        &threshold,
+        |b, &t| b.iter(|| filters::sobel_threshold(black_box(&frame), WIDTH, HEIGHT, t)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("ingenua", threshold),
+        &threshold,
+        |b, &t| b.iter(|| naive::sobel_threshold(black_box(&frame), WIDTH, HEIGHT, t)),
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_morphology, bench_sobel);
+criterion_main!(benches);
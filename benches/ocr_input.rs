@@ -0,0 +1,84 @@
+// game-translator/benches/ocr_input.rs
+
+// ============================================================================
+// BENCHMARK - Entrada do Windows OCR (src/ocr/windows_backend.rs)
+// ============================================================================
+//
+// Antes, cada captura era codificada em PNG, escrita num stream e
+// decodificada pelo BitmapDecoder antes do OCR. Agora os pixels vão
+// direto para um SoftwareBitmap BGRA8.
+//
+// Este benchmark mede só a parte que roda em qualquer plataforma:
+// "png" = codificar o PNG (o passo 1 antigo), "bgra" = converter para
+// BGRA8 (o passo 1 novo, igual a image_to_software_bitmap). O stream e o
+// BitmapDecoder que também saíram só existem no Windows e não entram na
+// conta, então o ganho real é maior que o medido aqui.
+//
+// Uso:
+//   cargo bench --bench ocr_input
+//
+// ============================================================================
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use image::{DynamicImage, RgbaImage};
+use std::io::Cursor;
+
+/// Tamanhos típicos: legenda 1200x100 com upscale 3x, região de diálogo,
+/// tela cheia 1080p
+const SIZES: &[(&str, u32, u32)] = &[
+    ("legenda", 3600, 300),
+    ("regiao", 1200, 400),
+    ("tela_cheia", 1920, 1080),
+];
+
+/// Imagem de captura com ruído e "texto" (determinística)
+fn capture(width: u32, height: u32) -> DynamicImage {
+    let mut state = 0x2545_f491u32;
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        let noise = (state >> 27) as u8;
+        let text = (y / 12) % 3 == 1 && (x / 9) % 5 < 3;
+        let value = if text { 230 + noise } else { 40 + noise * 2 };
+        image::Rgba([value, value.wrapping_add(noise), 255 - value, 255])
+    }))
+}
+
+/// Passo 1 antigo: PNG em memória
+fn encode_png(image: &DynamicImage) -> Vec<u8> {
+    let mut png_bytes: Vec<u8> = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+        .unwrap();
+    png_bytes
+}
+
+/// Passo 1 novo: RGBA → BGRA (troca vermelho e azul)
+fn to_bgra8(image: &DynamicImage) -> Vec<u8> {
+    let mut pixels = image.to_rgba8().into_raw();
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    pixels
+}
+
+fn bench_ocr_input(c: &mut Criterion) {
+    let mut group = c.benchmark_group("entrada_ocr");
+    group.sample_size(20);
+
+    for &(name, width, height) in SIZES {
+        let image = capture(width, height);
+        group.throughput(Throughput::Elements(width as u64 * height as u64));
+
+        group.bench_with_input(BenchmarkId::new("png", name), &image, |b, image| {
+            b.iter(|| encode_png(black_box(image)))
+        });
+        group.bench_with_input(BenchmarkId::new("bgra", name), &image, |b, image| {
+            b.iter(|| to_bgra8(black_box(image)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_ocr_input);
+criterion_main!(benches);
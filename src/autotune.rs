@@ -0,0 +1,544 @@
+// game-translator/src/autotune.rs
+
+// ============================================================================
+// MÓDULO AUTOTUNE - Busca automática dos parâmetros de pré-processamento
+// ============================================================================
+//
+// Usa um corpus de capturas com texto esperado (ver corpus.rs) para achar
+// o melhor PreprocessConfig para um jogo, sem tentativa e erro manual.
+//
+// Busca por descida de coordenadas:
+// 1. Começa da configuração atual (com o pré-processamento ligado)
+// 2. Para cada parâmetro, testa todos os valores candidatos mantendo os
+//    outros fixos e fica com o de menor CER
+// 3. Repete as passadas até nenhum parâmetro melhorar (ou MAX_PASSES)
+//
+// Parâmetros que não valem no modo atual são pulados: com edge detection
+// não há binarização, `threshold` só vale no modo fixo, `sauvola_k` só
+// no Sauvola e assim por diante. O modo em si também é pesquisado.
+//
+// Isso testa dezenas de combinações em vez das milhares de uma grade
+// completa. Combinações repetidas não são avaliadas de novo (cache).
+//
+// No fim, o melhor resultado é gravado em `display.preprocess` ou em
+// `subtitle.preprocess` e é impresso um relatório por parâmetro, mostrando
+// quanto o CER varia com cada um (os que mais variam são os que importam).
+//
+// Uso:
+//   game-translator --autotune corpus/                       → display
+//   game-translator --autotune corpus/ --autotune-target subtitle
+//
+// A busca mexe nos campos simples, que não representam um pipeline de
+// `steps` montado à mão. Se o alvo tem steps, o resultado só é impresso
+// (como steps) e o config.json fica intacto; com --autotune-replace-steps
+// o pipeline é trocado pelo resultado (gravado como steps).
+//
+// ============================================================================
+
+use crate::config::{AppConfig, PreprocessConfig, ThresholdMode};
+use crate::corpus::{self, CorpusSample};
+use crate::ocr::{self, OcrBackend};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Número máximo de passadas da descida de coordenadas
+const MAX_PASSES: usize = 4;
+
+/// Melhora mínima de CER para trocar um parâmetro (evita trocar por ruído)
+const MIN_IMPROVEMENT: f64 = 1e-6;
+
+/// Qual pré-processamento do config recebe o resultado
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutotuneTarget {
+    Display,
+    Subtitle,
+}
+
+/// Opções do auto-ajuste (vindas da linha de comando)
+#[derive(Debug, Clone)]
+pub struct AutotuneOptions {
+    /// Pasta do corpus
+    pub corpus_dir: PathBuf,
+    /// Onde gravar o resultado
+    pub target: AutotuneTarget,
+    /// Força um backend de OCR ("windows" ou "tesseract")
+    pub backend: Option<String>,
+    /// Substitui um pipeline de steps do alvo (senão o resultado só é impresso)
+    pub replace_steps: bool,
+}
+
+impl AutotuneOptions {
+    /// Cria opções com valores padrão para a pasta informada
+    pub fn new(corpus_dir: PathBuf) -> Self {
+        AutotuneOptions {
+            corpus_dir,
+            target: AutotuneTarget::Display,
+            backend: None,
+            replace_steps: false,
+        }
+    }
+}
+
+/// Um parâmetro ajustável e seus valores candidatos
+struct Knob {
+    name: &'static str,
+    values: &'static [f32],
+    /// Nomes dos valores (parâmetros que são um enum); vazio = numérico
+    labels: &'static [&'static str],
+    get: fn(&PreprocessConfig) -> f32,
+    set: fn(&mut PreprocessConfig, f32),
+    /// O parâmetro muda o pipeline nesta configuração?
+    active: fn(&PreprocessConfig) -> bool,
+}
+
+impl Knob {
+    /// Valor para o relatório ("sauvola" em vez de "2")
+    fn describe(&self, value: f32) -> String {
+        self.labels
+            .get(value as usize)
+            .map_or_else(|| value.to_string(), |label| label.to_string())
+    }
+}
+
+/// Modos de binarização, na ordem dos valores do parâmetro `threshold_mode`
+const THRESHOLD_MODES: [ThresholdMode; 4] = [
+    ThresholdMode::Fixed,
+    ThresholdMode::Otsu,
+    ThresholdMode::Sauvola,
+    ThresholdMode::LocalMean,
+];
+
+/// Nos campos simples, edge detection desliga a binarização
+fn binarizes(config: &PreprocessConfig) -> bool {
+    config.edge_detection == 0
+}
+
+fn adaptive(config: &PreprocessConfig) -> bool {
+    binarizes(config)
+        && matches!(
+            config.threshold_mode,
+            ThresholdMode::Sauvola | ThresholdMode::LocalMean
+        )
+}
+
+/// Parâmetros pesquisados, na ordem da descida
+const KNOBS: &[Knob] = &[
+    Knob {
+        name: "invert",
+        values: &[0.0, 1.0],
+        labels: &[],
+        get: |c| if c.invert { 1.0 } else { 0.0 },
+        set: |c, v| c.invert = v > 0.5,
+        active: |_| true,
+    },
+    Knob {
+        name: "upscale",
+        values: &[1.0, 1.5, 2.0, 3.0],
+        labels: &[],
+        get: |c| c.upscale,
+        set: |c, v| c.upscale = v,
+        active: |_| true,
+    },
+    Knob {
+        name: "contrast",
+        values: &[1.0, 1.25, 1.5, 2.0, 2.5, 3.0],
+        labels: &[],
+        get: |c| c.contrast,
+        set: |c, v| c.contrast = v,
+        active: |_| true,
+    },
+    Knob {
+        name: "edge_detection",
+        values: &[0.0, 30.0, 50.0, 80.0],
+        labels: &[],
+        get: |c| c.edge_detection as f32,
+        set: |c, v| c.edge_detection = v as u8,
+        active: |_| true,
+    },
+    Knob {
+        name: "threshold_mode",
+        values: &[0.0, 1.0, 2.0, 3.0],
+        labels: &["fixed", "otsu", "sauvola", "local_mean"],
+        get: |c| {
+            THRESHOLD_MODES
+                .iter()
+                .position(|&mode| mode == c.threshold_mode)
+                .unwrap_or(0) as f32
+        },
+        set: |c, v| c.threshold_mode = THRESHOLD_MODES[v as usize],
+        active: binarizes,
+    },
+    Knob {
+        name: "threshold",
+        values: &[0.0, 80.0, 110.0, 128.0, 150.0, 180.0, 210.0],
+        labels: &[],
+        get: |c| c.threshold as f32,
+        set: |c, v| c.threshold = v as u8,
+        active: |c| binarizes(c) && c.threshold_mode == ThresholdMode::Fixed,
+    },
+    Knob {
+        name: "adaptive_window",
+        values: &[15.0, 25.0, 41.0, 61.0],
+        labels: &[],
+        get: |c| c.adaptive_window as f32,
+        set: |c, v| c.adaptive_window = v as u32,
+        active: adaptive,
+    },
+    Knob {
+        name: "sauvola_k",
+        values: &[0.2, 0.3, 0.34, 0.4, 0.5],
+        labels: &[],
+        get: |c| c.sauvola_k,
+        set: |c, v| c.sauvola_k = v,
+        active: |c| adaptive(c) && c.threshold_mode == ThresholdMode::Sauvola,
+    },
+    Knob {
+        name: "local_mean_offset",
+        values: &[0.0, 5.0, 10.0, 15.0, 20.0],
+        labels: &[],
+        get: |c| c.local_mean_offset,
+        set: |c, v| c.local_mean_offset = v,
+        active: |c| adaptive(c) && c.threshold_mode == ThresholdMode::LocalMean,
+    },
+    Knob {
+        name: "blur",
+        values: &[0.0, 0.5, 1.0, 1.5, 2.0],
+        labels: &[],
+        get: |c| c.blur,
+        set: |c, v| c.blur = v,
+        active: |_| true,
+    },
+    Knob {
+        name: "dilate",
+        values: &[0.0, 1.0, 2.0],
+        labels: &[],
+        get: |c| c.dilate as f32,
+        set: |c, v| c.dilate = v as u8,
+        active: |_| true,
+    },
+    Knob {
+        name: "erode",
+        values: &[0.0, 1.0, 2.0],
+        labels: &[],
+        get: |c| c.erode as f32,
+        set: |c, v| c.erode = v as u8,
+        active: |_| true,
+    },
+];
+
+/// Avalia configurações sobre o corpus, lembrando as já avaliadas
+struct Evaluator<'a> {
+    backend: &'a dyn OcrBackend,
+    samples: &'a [CorpusSample],
+    cache: HashMap<String, f64>,
+}
+
+impl Evaluator<'_> {
+    /// CER médio da configuração (do cache, se já foi avaliada)
+    fn cer(&mut self, config: &PreprocessConfig) -> f64 {
+        let key = config_key(config);
+        if let Some(&cer) = self.cache.get(&key) {
+            return cer;
+        }
+
+        let label = config_label(config);
+        let score = corpus::score_config(self.backend, self.samples, &label, config);
+        info!(
+            "🎛️  {} → CER {:.4} ({}ms)",
+            label, score.mean_cer, score.elapsed_ms
+        );
+        self.cache.insert(key, score.mean_cer);
+        score.mean_cer
+    }
+}
+
+/// Chave do cache: o pipeline efetivo inteiro (steps, threshold_mode,
+/// color_key...), não só os parâmetros pesquisados. Senão um config com
+/// `steps` e o mesmo config sem eles dividiriam o mesmo CER.
+/// As opções de debug não mudam o OCR e ficam de fora.
+fn config_key(config: &PreprocessConfig) -> String {
+    let mut key = config.clone();
+    key.save_debug_image = false;
+    key.save_debug_stages = false;
+    serde_json::to_string(&key).unwrap_or_default()
+}
+
+/// Nome legível de uma configuração (só os parâmetros pesquisados que
+/// valem nela)
+fn config_label(config: &PreprocessConfig) -> String {
+    KNOBS
+        .iter()
+        .filter(|knob| (knob.active)(config))
+        .map(|knob| format!("{}={}", knob.name, knob.describe((knob.get)(config))))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Roda o auto-ajuste, grava o resultado no config.json e imprime o relatório
+pub fn run(options: &AutotuneOptions, app_config: &mut AppConfig) -> Result<()> {
+    let samples = corpus::load_corpus(&options.corpus_dir)?;
+
+    let mut ocr_config = app_config.ocr.clone();
+    if let Some(backend) = &options.backend {
+        ocr_config.backend = backend.clone();
+    }
+    let backend = ocr::create_backend(&ocr_config, &app_config.ocr_language())?;
+
+    let (target_name, current) = match options.target {
+        AutotuneTarget::Display => ("display.preprocess", &app_config.display.preprocess),
+        AutotuneTarget::Subtitle => ("subtitle.preprocess", &app_config.subtitle.preprocess),
+    };
+
+    println!(
+        "Auto-ajuste de {} | OCR: {} | corpus: {} ({} capturas)",
+        target_name,
+        backend.name(),
+        options.corpus_dir.display(),
+        samples.len()
+    );
+
+    let mut evaluator = Evaluator {
+        backend: backend.as_ref(),
+        samples: &samples,
+        cache: HashMap::new(),
+    };
+
+    // Ponto de partida: a configuração atual e "sem pré-processamento"
+    let original_cer = if current.enabled {
+        evaluator.cer(current)
+    } else {
+        corpus::score_config(backend.as_ref(), &samples, "desligado", current).mean_cer
+    };
+
+    // A busca mexe nos campos simples, que não representam um pipeline de steps
+    let has_steps = current.steps.is_some();
+    if has_steps {
+        println!(
+            "Aviso: {} usa um pipeline de steps, a busca parte dos campos simples{}",
+            target_name,
+            if options.replace_steps {
+                " e o pipeline será substituído"
+            } else {
+                " (o resultado só é impresso)"
+            }
+        );
+    }
+
+    let mut best = current.clone();
+    best.enabled = true;
+    best.save_debug_image = false;
+    best.save_debug_stages = false;
+    best.steps = None;
+    let mut best_cer = evaluator.cer(&best);
+
+    // Descida de coordenadas
+    for pass in 1..=MAX_PASSES {
+        let mut improved = false;
+
+        for knob in KNOBS {
+            // Não muda nada no modo atual (ex: threshold com Otsu)
+            if !(knob.active)(&best) {
+                continue;
+            }
+
+            for &value in knob.values {
+                let mut candidate = best.clone();
+                (knob.set)(&mut candidate, value);
+
+                let cer = evaluator.cer(&candidate);
+                if cer + MIN_IMPROVEMENT < best_cer {
+                    best = candidate;
+                    best_cer = cer;
+                    improved = true;
+                }
+            }
+        }
+
+        info!("🎛️  Passada {}: melhor CER {:.4}", pass, best_cer);
+        if !improved {
+            break;
+        }
+    }
+
+    // Relatório: variação do CER por parâmetro, com os outros no melhor valor
+    print_report(&mut evaluator, &best, original_cer, best_cer);
+
+    if best_cer + MIN_IMPROVEMENT >= original_cer {
+        println!(
+            "A configuração atual já é a melhor encontrada, {} não foi alterado",
+            target_name
+        );
+        return Ok(());
+    }
+
+    // Não apaga um pipeline montado à mão sem pedido explícito
+    if has_steps && !options.replace_steps {
+        println!(
+            "{} usa um pipeline de steps e não foi alterado. Melhor pipeline encontrado:",
+            target_name
+        );
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&best.pipeline()).unwrap_or_default()
+        );
+        println!("Para gravar por cima do pipeline atual: --autotune-replace-steps");
+        return Ok(());
+    }
+
+    let target = match options.target {
+        AutotuneTarget::Display => &mut app_config.display.preprocess,
+        AutotuneTarget::Subtitle => &mut app_config.subtitle.preprocess,
+    };
+    best.save_debug_image = target.save_debug_image;
+    best.save_debug_stages = target.save_debug_stages;
+    // Quem usava steps continua com steps (mesmo pipeline, em ordem explícita)
+    if has_steps {
+        best.steps = Some(best.pipeline());
+    }
+    *target = best;
+    app_config.save()?;
+
+    println!(
+        "Melhor configuração gravada em {} (config.json)",
+        target_name
+    );
+
+    Ok(())
+}
+
+/// Imprime o valor escolhido e o impacto de cada parâmetro
+fn print_report(
+    evaluator: &mut Evaluator,
+    best: &PreprocessConfig,
+    original_cer: f64,
+    best_cer: f64,
+) {
+    let mut rows: Vec<_> = KNOBS
+        .iter()
+        .filter(|knob| (knob.active)(best))
+        .map(|knob| {
+            let results: Vec<(f32, f64)> = knob
+                .values
+                .iter()
+                .map(|&value| {
+                    let mut candidate = best.clone();
+                    (knob.set)(&mut candidate, value);
+                    (value, evaluator.cer(&candidate))
+                })
+                .collect();
+            (knob, (knob.get)(best), results)
+        })
+        .collect();
+
+    // Impacto = diferença entre o pior e o melhor valor do parâmetro
+    let impact = |results: &[(f32, f64)]| {
+        let max = results.iter().map(|(_, c)| *c).fold(f64::MIN, f64::max);
+        let min = results.iter().map(|(_, c)| *c).fold(f64::MAX, f64::min);
+        max - min
+    };
+    rows.sort_by(|a, b| impact(&b.2).total_cmp(&impact(&a.2)));
+
+    println!();
+    println!(
+        "CER: {:.4} (atual) → {:.4} (melhor), {} combinações avaliadas",
+        original_cer,
+        best_cer,
+        evaluator.cache.len()
+    );
+    println!();
+    println!(
+        "{:<18} {:>10} {:>8}  CER por valor",
+        "parâmetro", "melhor", "impacto"
+    );
+
+    for (knob, value, results) in &rows {
+        let values = results
+            .iter()
+            .map(|(v, cer)| format!("{}={:.3}", knob.describe(*v), cer))
+            .collect::<Vec<String>>()
+            .join("  ");
+        println!(
+            "{:<18} {:>10} {:>8.4}  {}",
+            knob.name,
+            knob.describe(*value),
+            impact(results),
+            values
+        );
+    }
+    println!();
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PreprocessStep;
+
+    #[test]
+    fn cache_key_covers_the_whole_pipeline() {
+        let plain = PreprocessConfig::default();
+        let mut with_steps = plain.clone();
+        with_steps.steps = Some(vec![PreprocessStep::Grayscale, PreprocessStep::Otsu]);
+
+        // Mesmos parâmetros pesquisados, pipelines diferentes
+        assert_eq!(config_label(&plain), config_label(&with_steps));
+        assert_ne!(config_key(&plain), config_key(&with_steps));
+
+        let mut otsu = plain.clone();
+        otsu.threshold_mode = ThresholdMode::Otsu;
+        assert_ne!(config_key(&plain), config_key(&otsu));
+
+        let mut keyed = plain.clone();
+        keyed.color_key = vec![[255, 255, 255]];
+        assert_ne!(config_key(&plain), config_key(&keyed));
+
+        // Debug não muda o resultado do OCR
+        let mut debug = plain.clone();
+        debug.save_debug_image = true;
+        debug.save_debug_stages = true;
+        assert_eq!(config_key(&plain), config_key(&debug));
+    }
+
+    #[test]
+    fn knobs_follow_the_threshold_mode() {
+        let active = |config: &PreprocessConfig| -> Vec<&str> {
+            KNOBS
+                .iter()
+                .filter(|knob| (knob.active)(config))
+                .map(|knob| knob.name)
+                .collect()
+        };
+
+        let mut config = PreprocessConfig::default();
+        assert!(active(&config).contains(&"threshold"));
+        assert!(!active(&config).contains(&"sauvola_k"));
+
+        // O parâmetro do modo vai e volta pelo valor do knob
+        let mode = KNOBS.iter().find(|k| k.name == "threshold_mode").unwrap();
+        (mode.set)(&mut config, 2.0);
+        assert_eq!(config.threshold_mode, ThresholdMode::Sauvola);
+        assert_eq!((mode.get)(&config), 2.0);
+        assert_eq!(mode.describe(2.0), "sauvola");
+
+        let sauvola = active(&config);
+        assert!(sauvola.contains(&"adaptive_window") && sauvola.contains(&"sauvola_k"));
+        assert!(!sauvola.contains(&"threshold") && !sauvola.contains(&"local_mean_offset"));
+        assert!(config_label(&config).contains("threshold_mode=sauvola"));
+
+        // Edge detection desliga toda a binarização
+        config.edge_detection = 50;
+        let edges = active(&config);
+        for name in [
+            "threshold_mode",
+            "threshold",
+            "adaptive_window",
+            "sauvola_k",
+        ] {
+            assert!(!edges.contains(&name), "{}", name);
+        }
+    }
+}
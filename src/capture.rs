@@ -0,0 +1,446 @@
+// game-translator/src/capture.rs
+
+// ============================================================================
+// MÓDULO CAPTURE - De onde vêm as imagens (tela ao vivo ou gravação)
+// ============================================================================
+//
+// O pipeline (hotkeys e legendas) pede imagens a uma `CaptureSource`
+// em vez de chamar o crate screenshots direto. Assim dá para rodar o
+// modo legenda e as traduções por hotkey sobre gameplay gravado, numa
+// máquina Linux sem monitor (CI, depuração de um bug reportado).
+//
+// Fontes disponíveis:
+// - ScreenSource:   a tela ao vivo (padrão)
+// - FolderSource:   uma pasta de PNGs, um frame por captura, em ordem
+//                   alfabética. Um vídeo vira pasta com:
+//                     ffmpeg -i gameplay.mp4 -vf fps=2 frames/%05d.png
+// - SequenceSource: frames com timestamps (manifesto JSON); a captura
+//                   devolve o frame do instante atual, como um vídeo
+//                   tocando em tempo real
+//
+// Manifesto da SequenceSource (caminhos relativos ao manifesto):
+//   { "frames": [ { "time_ms": 0,    "file": "00001.png" },
+//                 { "time_ms": 1500, "file": "00002.png" } ] }
+//
+// Cada frame gravado é o monitor inteiro: as regiões do config (x/y
+// relativos ao monitor) são recortadas dele. O índice do monitor é
+// ignorado nas gravações.
+//
+// ============================================================================
+
+use crate::screenshot;
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Fonte de imagens para o pipeline de OCR
+pub trait CaptureSource: Send {
+    /// Nome para os logs ("tela", "pasta frames/ (12 frames)", ...)
+    fn describe(&self) -> String;
+
+    /// Captura o monitor inteiro
+    fn capture_screen(&mut self, monitor: Option<u32>) -> Result<DynamicImage>;
+
+    /// Captura uma região (x/y relativos ao monitor)
+    fn capture_region(
+        &mut self,
+        monitor: Option<u32>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<DynamicImage>;
+
+    /// A gravação chegou ao fim? (a tela ao vivo nunca acaba)
+    ///
+    /// Depois do fim, as capturas continuam devolvendo o último frame.
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+// ============================================================================
+// TELA AO VIVO
+// ============================================================================
+
+/// Captura a tela de verdade (crate screenshots)
+pub struct ScreenSource;
+
+impl CaptureSource for ScreenSource {
+    fn describe(&self) -> String {
+        "tela".to_string()
+    }
+
+    fn capture_screen(&mut self, monitor: Option<u32>) -> Result<DynamicImage> {
+        screenshot::capture_screen_to_memory(monitor)
+    }
+
+    fn capture_region(
+        &mut self,
+        monitor: Option<u32>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<DynamicImage> {
+        screenshot::capture_region_to_memory(monitor, x, y, width, height)
+    }
+}
+
+// ============================================================================
+// PASTA DE FRAMES
+// ============================================================================
+
+/// Pasta de PNGs: cada captura avança um frame
+pub struct FolderSource {
+    folder: PathBuf,
+    frames: Vec<PathBuf>,
+    next: usize,
+}
+
+impl FolderSource {
+    /// Lista os PNGs da pasta (ordem alfabética)
+    pub fn open(folder: &Path) -> Result<Self> {
+        let mut frames: Vec<PathBuf> = fs::read_dir(folder)
+            .with_context(|| format!("Falha ao ler pasta de frames {}", folder.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+            })
+            .collect();
+        frames.sort();
+
+        if frames.is_empty() {
+            anyhow::bail!("Nenhum PNG encontrado em {}", folder.display());
+        }
+
+        Ok(FolderSource {
+            folder: folder.to_path_buf(),
+            frames,
+            next: 0,
+        })
+    }
+
+    /// Próximo frame (o último se repete depois do fim)
+    fn next_frame(&mut self) -> Result<DynamicImage> {
+        let index = self.next.min(self.frames.len() - 1);
+        self.next = (self.next + 1).min(self.frames.len());
+        trace!("🎞️  Frame {}/{}", index + 1, self.frames.len());
+        load_frame(&self.frames[index])
+    }
+}
+
+impl CaptureSource for FolderSource {
+    fn describe(&self) -> String {
+        format!(
+            "pasta {} ({} frames)",
+            self.folder.display(),
+            self.frames.len()
+        )
+    }
+
+    fn capture_screen(&mut self, _monitor: Option<u32>) -> Result<DynamicImage> {
+        self.next_frame()
+    }
+
+    fn capture_region(
+        &mut self,
+        _monitor: Option<u32>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<DynamicImage> {
+        crop_frame(self.next_frame()?, x, y, width, height)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next >= self.frames.len()
+    }
+}
+
+// ============================================================================
+// SEQUÊNCIA COM TIMESTAMPS
+// ============================================================================
+
+/// Manifesto de uma gravação com timestamps
+#[derive(Debug, Deserialize)]
+struct SequenceManifest {
+    frames: Vec<SequenceFrame>,
+}
+
+/// Um frame do manifesto
+#[derive(Debug, Deserialize)]
+struct SequenceFrame {
+    /// Instante do frame, desde o começo da gravação
+    time_ms: u64,
+    /// PNG do frame (relativo ao manifesto)
+    file: PathBuf,
+}
+
+/// Gravação com timestamps, tocada em tempo real
+///
+/// O relógio começa na primeira captura; cada captura devolve o último
+/// frame cujo instante já passou (frames podem ser pulados ou repetidos,
+/// como numa captura de verdade).
+pub struct SequenceSource {
+    manifest: PathBuf,
+    frames: Vec<SequenceFrame>,
+    started: Option<Instant>,
+    /// Frame carregado por último (índice, imagem): evita reler o PNG
+    current: Option<(usize, DynamicImage)>,
+}
+
+impl SequenceSource {
+    /// Lê o manifesto JSON
+    pub fn open(manifest: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(manifest)
+            .with_context(|| format!("Falha ao ler manifesto {}", manifest.display()))?;
+        let parsed: SequenceManifest = serde_json::from_str(&contents)
+            .with_context(|| format!("Falha ao parsear manifesto {}", manifest.display()))?;
+
+        let base = manifest.parent().unwrap_or(Path::new("."));
+        let mut frames: Vec<SequenceFrame> = parsed
+            .frames
+            .into_iter()
+            .map(|frame| SequenceFrame {
+                time_ms: frame.time_ms,
+                file: base.join(frame.file),
+            })
+            .collect();
+        frames.sort_by_key(|frame| frame.time_ms);
+
+        if frames.is_empty() {
+            anyhow::bail!("Manifesto {} não tem frames", manifest.display());
+        }
+
+        Ok(SequenceSource {
+            manifest: manifest.to_path_buf(),
+            frames,
+            started: None,
+            current: None,
+        })
+    }
+
+    /// Milissegundos desde a primeira captura
+    fn elapsed_ms(&self) -> u64 {
+        self.started
+            .map(|start| start.elapsed().as_millis() as u64)
+            .unwrap_or(0)
+    }
+
+    /// Frame do instante atual
+    fn frame_now(&mut self) -> Result<DynamicImage> {
+        let elapsed = {
+            let start = *self.started.get_or_insert_with(Instant::now);
+            start.elapsed().as_millis() as u64
+        };
+
+        // Último frame que já começou (o primeiro, se nenhum começou)
+        let index = self
+            .frames
+            .partition_point(|frame| frame.time_ms <= elapsed)
+            .saturating_sub(1);
+
+        if let Some((current, image)) = &self.current {
+            if *current == index {
+                return Ok(image.clone());
+            }
+        }
+
+        trace!(
+            "🎞️  Frame {}/{} ({} ms)",
+            index + 1,
+            self.frames.len(),
+            elapsed
+        );
+        let image = load_frame(&self.frames[index].file)?;
+        self.current = Some((index, image.clone()));
+        Ok(image)
+    }
+}
+
+impl CaptureSource for SequenceSource {
+    fn describe(&self) -> String {
+        let duration = self.frames.last().map(|frame| frame.time_ms).unwrap_or(0);
+        format!(
+            "gravação {} ({} frames, {:.1}s)",
+            self.manifest.display(),
+            self.frames.len(),
+            duration as f64 / 1000.0
+        )
+    }
+
+    fn capture_screen(&mut self, _monitor: Option<u32>) -> Result<DynamicImage> {
+        self.frame_now()
+    }
+
+    fn capture_region(
+        &mut self,
+        _monitor: Option<u32>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<DynamicImage> {
+        crop_frame(self.frame_now()?, x, y, width, height)
+    }
+
+    fn is_finished(&self) -> bool {
+        let last = self.frames.last().map(|frame| frame.time_ms).unwrap_or(0);
+        self.started.is_some() && self.elapsed_ms() >= last
+    }
+}
+
+// ============================================================================
+// REPLAY (linha de comando)
+// ============================================================================
+
+/// O que rodar sobre a gravação
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayMode {
+    /// Modo legenda (captura contínua da região de legendas)
+    Subtitle,
+    /// Hotkey "região customizada" a cada frame
+    Region,
+    /// Hotkey "tela inteira" a cada frame
+    FullScreen,
+}
+
+/// Opções do replay (vindas da linha de comando)
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Pasta de PNGs ou manifesto JSON
+    pub path: PathBuf,
+    /// Pipeline a exercitar
+    pub mode: ReplayMode,
+}
+
+impl ReplayOptions {
+    /// Cria opções com valores padrão (modo legenda) para a gravação
+    pub fn new(path: PathBuf) -> Self {
+        ReplayOptions {
+            path,
+            mode: ReplayMode::Subtitle,
+        }
+    }
+}
+
+/// Abre uma gravação: pasta = FolderSource, arquivo = SequenceSource
+pub fn open_recording(path: &Path) -> Result<Box<dyn CaptureSource>> {
+    let source: Box<dyn CaptureSource> = if path.is_dir() {
+        Box::new(FolderSource::open(path)?)
+    } else {
+        Box::new(SequenceSource::open(path)?)
+    };
+
+    info!("🎞️  Fonte de captura: {}", source.describe());
+    Ok(source)
+}
+
+// ============================================================================
+// FUNÇÕES AUXILIARES
+// ============================================================================
+
+/// Lê um frame gravado
+fn load_frame(path: &Path) -> Result<DynamicImage> {
+    image::open(path).with_context(|| format!("Falha ao abrir frame {}", path.display()))
+}
+
+/// Recorta a região do frame (mesma validação da captura ao vivo)
+fn crop_frame(
+    frame: DynamicImage,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<DynamicImage> {
+    if x + width > frame.width() || y + height > frame.height() {
+        anyhow::bail!(
+            "Região ({},{} {}x{}) está fora do frame gravado ({}x{})",
+            x,
+            y,
+            width,
+            height,
+            frame.width(),
+            frame.height()
+        );
+    }
+
+    Ok(frame.crop_imm(x, y, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    /// Pasta temporária com PNGs de 8x4, cada um preenchido com um tom
+    fn write_frames(name: &str, shades: &[u8]) -> PathBuf {
+        let folder =
+            std::env::temp_dir().join(format!("game-translator-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+
+        for (index, &shade) in shades.iter().enumerate() {
+            RgbImage::from_pixel(8, 4, Rgb([shade, shade, shade]))
+                .save(folder.join(format!("{:03}.png", index)))
+                .unwrap();
+        }
+
+        folder
+    }
+
+    fn shade(image: &DynamicImage) -> u8 {
+        image.to_rgb8().get_pixel(0, 0)[0]
+    }
+
+    #[test]
+    fn folder_source_plays_frames_in_order_then_holds_last() {
+        let folder = write_frames("folder", &[10, 20, 30]);
+        let mut source = FolderSource::open(&folder).unwrap();
+
+        let mut seen = Vec::new();
+        while !source.is_finished() {
+            seen.push(shade(&source.capture_screen(None).unwrap()));
+        }
+        assert_eq!(seen, vec![10, 20, 30]);
+
+        // Depois do fim repete o último frame
+        let region = source.capture_region(None, 2, 1, 4, 2).unwrap();
+        assert_eq!((region.width(), region.height()), (4, 2));
+        assert_eq!(shade(&region), 30);
+
+        // Região fora do frame é erro, como na captura ao vivo
+        assert!(source.capture_region(None, 6, 0, 4, 2).is_err());
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn sequence_source_follows_timestamps() {
+        let folder = write_frames("sequence", &[10, 20]);
+        let manifest = folder.join("manifest.json");
+        fs::write(
+            &manifest,
+            r#"{ "frames": [ { "time_ms": 60000, "file": "001.png" },
+                             { "time_ms": 0, "file": "000.png" } ] }"#,
+        )
+        .unwrap();
+
+        let mut source = SequenceSource::open(&manifest).unwrap();
+        assert!(!source.is_finished());
+
+        // No começo só o frame do instante 0 está valendo
+        assert_eq!(shade(&source.capture_screen(None).unwrap()), 10);
+        assert_eq!(shade(&source.capture_screen(None).unwrap()), 10);
+        assert!(!source.is_finished());
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
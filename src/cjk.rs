@@ -0,0 +1,173 @@
+// game-translator/src/cjk.rs
+
+// ============================================================================
+// MÓDULO CJK - Texto em chinês, japonês e coreano
+// ============================================================================
+//
+// Chinês e japonês não usam espaço entre palavras, mas o OCR costuma
+// inserir espaços entre os caracteres ("こ ん に ち は"). Este módulo
+// centraliza as regras para:
+// - Detectar caracteres CJK (ideogramas, kana, hangul, pontuação)
+// - Remover espaços falsos entre caracteres que não usam espaço
+// - Juntar linhas/colunas sem inserir espaço no meio de uma frase
+//
+// Coreano (hangul) usa espaço entre palavras, então é tratado como CJK
+// para detecção, mas os espaços dele são preservados.
+//
+// ============================================================================
+
+/// Verifica se o caractere é CJK (ideograma, kana, hangul ou pontuação CJK)
+pub fn is_cjk(c: char) -> bool {
+    is_unspaced(c) || is_hangul(c)
+}
+
+/// Verifica se o texto contém algum caractere CJK
+pub fn contains_cjk(text: &str) -> bool {
+    text.chars().any(is_cjk)
+}
+
+/// Verifica se é pontuação CJK (、。「」！？ etc.)
+pub fn is_cjk_punctuation(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{303F}' // Símbolos e pontuação CJK
+        | '\u{FF01}'..='\u{FF0F}' // ！＂＃...／ (largura total)
+        | '\u{FF1A}'..='\u{FF20}' // ：；＜＝＞？＠
+        | '\u{FF3B}'..='\u{FF40}' // ［＼］＾＿｀
+        | '\u{FF5B}'..='\u{FF65}' // ｛｜｝～ e pontuação de meia largura
+        | '\u{30FB}' // ・
+    )
+}
+
+/// Caracteres de escritas que não usam espaço entre palavras
+/// (ideogramas chineses/japoneses, hiragana, katakana e pontuação CJK)
+fn is_unspaced(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}' // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{31F0}'..='\u{31FF}' // Katakana (extensão fonética)
+        | '\u{3400}'..='\u{4DBF}' // Ideogramas (extensão A)
+        | '\u{4E00}'..='\u{9FFF}' // Ideogramas unificados
+        | '\u{F900}'..='\u{FAFF}' // Ideogramas de compatibilidade
+        | '\u{FF66}'..='\u{FF9F}' // Katakana de meia largura
+    ) || is_cjk_punctuation(c)
+}
+
+/// Hangul (coreano)
+fn is_hangul(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' // Jamo
+        | '\u{3130}'..='\u{318F}' // Jamo de compatibilidade
+        | '\u{AC00}'..='\u{D7AF}' // Sílabas
+    )
+}
+
+/// Remove espaços entre caracteres que não usam espaço
+///
+/// "こ ん に ち は 、 世界" → "こんにちは、世界"
+/// Espaços entre palavras latinas (e coreanas) são mantidos.
+pub fn remove_cjk_spaces(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            let previous = chars[..i].iter().rev().find(|c| !c.is_whitespace());
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+
+            if let (Some(&previous), Some(&next)) = (previous, next) {
+                if is_unspaced(previous) && is_unspaced(next) {
+                    continue;
+                }
+            }
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+/// Verifica se duas partes de texto devem ser juntadas SEM espaço
+///
+/// Verdadeiro quando o fim de `previous` ou o início de `next` é
+/// de uma escrita sem espaço (chinês/japonês).
+pub fn joins_without_space(previous: &str, next: &str) -> bool {
+    let last = previous.trim_end().chars().last();
+    let first = next.trim_start().chars().next();
+
+    last.is_some_and(is_unspaced) || first.is_some_and(is_unspaced)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::{DetectedText, OcrResultWithPositions};
+
+    #[test]
+    fn detects_cjk_and_removes_false_spaces() {
+        assert!(contains_cjk("Press A ボタン"));
+        assert!(contains_cjk("안녕하세요"));
+        assert!(contains_cjk("你好"));
+        assert!(!contains_cjk("Café, naïve"));
+        assert!(is_cjk_punctuation('。') && is_cjk_punctuation('！'));
+
+        // Japonês e chinês: espaços do OCR somem
+        assert_eq!(
+            remove_cjk_spaces("こ ん に ち は 、 世界"),
+            "こんにちは、世界"
+        );
+        assert_eq!(remove_cjk_spaces("你 好 ， 世 界 。"), "你好，世界。");
+        // Latim no meio: o espaço antes da escrita sem espaço fica
+        assert_eq!(
+            remove_cjk_spaces("Press A ボ タ ン を 押 す"),
+            "Press A ボタンを押す"
+        );
+        // Coreano usa espaço entre palavras
+        assert_eq!(remove_cjk_spaces("안녕 하세요 세계"), "안녕 하세요 세계");
+    }
+
+    #[test]
+    fn joins_lines_without_space_only_for_unspaced_scripts() {
+        assert!(joins_without_space("こんにちは", "世界"));
+        assert!(joins_without_space("我们走吧", "，快点"));
+        assert!(joins_without_space("HPが", "減った"));
+        assert!(joins_without_space("Aボタン ", " を押す"));
+        assert!(!joins_without_space("Hello", "world"));
+        assert!(!joins_without_space("안녕", "하세요"));
+    }
+
+    #[test]
+    fn vertical_columns_read_right_to_left_after_horizontal_lines() {
+        let text =
+            |text: &str, x: f64, y: f64, width: f64, height: f64, vertical: bool| DetectedText {
+                text: text.to_string(),
+                x,
+                y,
+                width,
+                height,
+                words: Vec::new(),
+                confidence: None,
+                vertical,
+            };
+
+        // Colunas fora de ordem, título horizontal por último
+        let mut result = OcrResultWithPositions {
+            full_text: String::new(),
+            lines: vec![
+                text("です。", 200.0, 100.0, 30.0, 90.0, true),
+                text("こんにちは", 300.0, 100.0, 30.0, 150.0, true),
+                text("世界", 250.0, 100.0, 30.0, 60.0, true),
+                text("第一章", 180.0, 20.0, 120.0, 30.0, false),
+            ],
+        };
+
+        result.apply_orientation("auto");
+
+        let order: Vec<&str> = result.lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(order, ["第一章", "こんにちは", "世界", "です。"]);
+        assert_eq!(result.full_text, "第一章こんにちは世界です。");
+    }
+}
@@ -0,0 +1,178 @@
+// game-translator/src/cli.rs
+
+// ============================================================================
+// MÓDULO CLI - Argumentos de linha de comando
+// ============================================================================
+//
+// Uso:
+//   game-translator                          → modo normal (overlay + hotkeys)
+//   game-translator --prewarm dialogos.json  → modo normal + pré-aquece o cache
+//
+// Opções do pré-aquecimento:
+//   --prewarm-batch <n>     linhas por requisição (padrão: 20)
+//   --prewarm-delay <ms>    pausa entre lotes (padrão: 1000)
+//   --prewarm-column <n>    coluna do CSV a traduzir (padrão: todas)
+//   --prewarm-field <nome>  campo do JSON a traduzir (padrão: todos)
+//
+// Avaliação do OCR (sem abrir o overlay, ver corpus.rs):
+//   --ocr-score <pasta>     avalia o pré-processamento sobre um corpus
+//   --score-config <json>   PreprocessConfig extra a avaliar (repetível)
+//   --max-cer <n>           falha (código 1) se o CER passar disso
+//   --ocr-backend <nome>    força o backend ("windows" ou "tesseract")
+//
+// Auto-ajuste do pré-processamento (sem abrir o overlay, ver autotune.rs):
+//   --autotune <pasta>      procura o melhor pré-processamento para o corpus
+//   --autotune-target <t>   onde gravar: "display" (padrão) ou "subtitle"
+//   --autotune-replace-steps  grava mesmo se o alvo tem pipeline de steps
+//
+// Medição da captura de tela (sem abrir o overlay):
+//   --capture-bench <n>     mede n capturas das regiões (antes/depois)
+//
+// Replay de gameplay gravado (sem abrir o overlay, ver capture.rs):
+//   --replay <caminho>      pasta de PNGs ou manifesto JSON com timestamps
+//   --replay-mode <m>       "subtitle" (padrão), "region" ou "fullscreen"
+//
+// ============================================================================
+
+use crate::autotune::{AutotuneOptions, AutotuneTarget};
+use crate::capture::{ReplayMode, ReplayOptions};
+use crate::corpus::ScoreOptions;
+use crate::prewarm::PrewarmOptions;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Argumentos já interpretados
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    /// Pré-aquecimento do cache (se `--prewarm` foi passado)
+    pub prewarm: Option<PrewarmOptions>,
+    /// Avaliação do OCR sobre um corpus (se `--ocr-score` foi passado)
+    pub ocr_score: Option<ScoreOptions>,
+    /// Auto-ajuste do pré-processamento (se `--autotune` foi passado)
+    pub autotune: Option<AutotuneOptions>,
+    /// Medição da captura: número de iterações (se `--capture-bench` foi passado)
+    pub capture_bench: Option<usize>,
+    /// Replay de uma gravação (se `--replay` foi passado)
+    pub replay: Option<ReplayOptions>,
+}
+
+/// Lê os argumentos do processo
+pub fn parse_args() -> Result<CliArgs> {
+    let mut cli = CliArgs::default();
+    let mut prewarm_batch: Option<usize> = None;
+    let mut prewarm_delay: Option<u64> = None;
+    let mut prewarm_column: Option<usize> = None;
+    let mut prewarm_field: Option<String> = None;
+    let mut score_configs: Vec<PathBuf> = Vec::new();
+    let mut max_cer: Option<f64> = None;
+    let mut ocr_backend: Option<String> = None;
+    let mut autotune_target = AutotuneTarget::Display;
+    let mut autotune_replace_steps = false;
+    let mut replay_mode = ReplayMode::Subtitle;
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--prewarm" => {
+                let path = next_value(&mut iter, &arg)?;
+                cli.prewarm = Some(PrewarmOptions::new(PathBuf::from(path)));
+            }
+            "--prewarm-batch" => {
+                prewarm_batch = Some(parse_number(&next_value(&mut iter, &arg)?, &arg)?);
+            }
+            "--prewarm-delay" => {
+                prewarm_delay = Some(parse_number(&next_value(&mut iter, &arg)?, &arg)?);
+            }
+            "--prewarm-column" => {
+                prewarm_column = Some(parse_number(&next_value(&mut iter, &arg)?, &arg)?);
+            }
+            "--prewarm-field" => {
+                prewarm_field = Some(next_value(&mut iter, &arg)?);
+            }
+            "--ocr-score" => {
+                let path = next_value(&mut iter, &arg)?;
+                cli.ocr_score = Some(ScoreOptions::new(PathBuf::from(path)));
+            }
+            "--score-config" => {
+                score_configs.push(PathBuf::from(next_value(&mut iter, &arg)?));
+            }
+            "--max-cer" => {
+                max_cer = Some(parse_number(&next_value(&mut iter, &arg)?, &arg)?);
+            }
+            "--ocr-backend" => {
+                ocr_backend = Some(next_value(&mut iter, &arg)?);
+            }
+            "--autotune" => {
+                let path = next_value(&mut iter, &arg)?;
+                cli.autotune = Some(AutotuneOptions::new(PathBuf::from(path)));
+            }
+            "--autotune-target" => {
+                autotune_target = match next_value(&mut iter, &arg)?.as_str() {
+                    "display" => AutotuneTarget::Display,
+                    "subtitle" => AutotuneTarget::Subtitle,
+                    other => anyhow::bail!("Valor inválido para {}: {}", arg, other),
+                };
+            }
+            "--autotune-replace-steps" => autotune_replace_steps = true,
+            "--capture-bench" => {
+                cli.capture_bench = Some(parse_number(&next_value(&mut iter, &arg)?, &arg)?);
+            }
+            "--replay" => {
+                let path = next_value(&mut iter, &arg)?;
+                cli.replay = Some(ReplayOptions::new(PathBuf::from(path)));
+            }
+            "--replay-mode" => {
+                replay_mode = match next_value(&mut iter, &arg)?.as_str() {
+                    "subtitle" => ReplayMode::Subtitle,
+                    "region" => ReplayMode::Region,
+                    "fullscreen" => ReplayMode::FullScreen,
+                    other => anyhow::bail!("Valor inválido para {}: {}", arg, other),
+                };
+            }
+            other => anyhow::bail!("Argumento desconhecido: {}", other),
+        }
+    }
+
+    if let Some(ref mut prewarm) = cli.prewarm {
+        if let Some(batch) = prewarm_batch {
+            prewarm.batch_size = batch;
+        }
+        if let Some(delay) = prewarm_delay {
+            prewarm.delay_ms = delay;
+        }
+        prewarm.csv_column = prewarm_column;
+        prewarm.json_field = prewarm_field;
+    }
+
+    if let Some(ref mut score) = cli.ocr_score {
+        score.extra_configs = score_configs;
+        score.max_cer = max_cer;
+        score.backend = ocr_backend.clone();
+    }
+
+    if let Some(ref mut autotune) = cli.autotune {
+        autotune.target = autotune_target;
+        autotune.backend = ocr_backend;
+        autotune.replace_steps = autotune_replace_steps;
+    }
+
+    if let Some(ref mut replay) = cli.replay {
+        replay.mode = replay_mode;
+    }
+
+    Ok(cli)
+}
+
+/// Pega o valor que vem depois de uma opção
+fn next_value(iter: &mut impl Iterator<Item = String>, option: &str) -> Result<String> {
+    iter.next()
+        .with_context(|| format!("Faltou o valor de {}", option))
+}
+
+/// Converte o valor de uma opção para número
+fn parse_number<T: std::str::FromStr>(value: &str, option: &str) -> Result<T> {
+    value
+        .parse()
+        .ok()
+        .with_context(|| format!("Valor inválido para {}: {}", option, value))
+}
@@ -6,17 +6,30 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
 
 /// Estrutura de configuração da região de captura
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// x/y são relativos ao canto do monitor da região (ver screenshot.rs),
+/// ou à área cliente da janela do jogo quando `game_window` está ligado
+/// (ver game_window.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RegionConfig {
     pub x: u32,
     pub y: u32,
     pub width: u32,
     pub height: u32,
+    /// Índice do monitor (0 = principal). Ausente = monitor principal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<u32>,
+    /// Tamanho da área cliente da janela quando a região foi selecionada
+    /// (regiões presas à janela: se o jogo mudar de resolução, a região
+    /// é escalada junto)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_size: Option<[u32; 2]>,
 }
 
 impl Default for RegionConfig {
@@ -26,6 +39,8 @@ impl Default for RegionConfig {
             y: 0,
             width: 1920,
             height: 1080,
+            monitor: None,
+            window_size: None,
         }
     }
 }
@@ -68,6 +83,13 @@ pub struct HotkeyConfig {
     pub select_subtitle_region: String,
     pub toggle_subtitle_mode: String,
     pub hide_translation: String,
+    /// Grava as últimas capturas numa pasta (relatório de bug)
+    #[serde(default = "default_dump_history_hotkey")]
+    pub dump_history: String,
+}
+
+fn default_dump_history_hotkey() -> String {
+    "F9".to_string()
 }
 
 impl Default for HotkeyConfig {
@@ -79,6 +101,7 @@ impl Default for HotkeyConfig {
             select_subtitle_region: "NumpadDivide".to_string(),
             toggle_subtitle_mode: "Numpad0".to_string(),
             hide_translation: "NumpadDecimal".to_string(),
+            dump_history: default_dump_history_hotkey(),
         }
     }
 }
@@ -159,6 +182,33 @@ pub struct DisplayConfig {
     pub tts_enabled: bool,
     /// Pré-processamento de imagem para OCR
     pub preprocess: PreprocessConfig,
+    /// Agrupamento das linhas do OCR em blocos (parágrafos/caixas de diálogo)
+    #[serde(default)]
+    pub layout: LayoutConfig,
+}
+
+/// Configuração da análise de layout (agrupa linhas em blocos)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Agrupar linhas em blocos antes de traduzir
+    /// (false = cada linha é traduzida separadamente, como antes)
+    pub enabled: bool,
+    /// Espaço vertical máximo entre duas linhas do mesmo bloco,
+    /// em múltiplos da altura da linha (ex: 1.0 = uma linha em branco)
+    pub max_line_gap: f32,
+    /// Tolerância de alinhamento (esquerda, centro ou direita),
+    /// em múltiplos da altura da linha
+    pub align_tolerance: f32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            enabled: true,
+            max_line_gap: 1.0,
+            align_tolerance: 1.5,
+        }
+    }
 }
 
 /// Estrutura de configuração de tradução
@@ -204,7 +254,7 @@ impl Default for TranslationConfig {
 }
 
 /// Configuração de pré-processamento de imagem para OCR
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PreprocessConfig {
     /// Habilita pré-processamento
     pub enabled: bool,
@@ -219,6 +269,10 @@ pub struct PreprocessConfig {
     pub threshold: u8,
     /// Salva imagem processada para debug
     pub save_debug_image: bool,
+    /// Salva cada etapa do pipeline (com as caixas do OCR) numa pasta
+    /// por captura, dentro de `debug_dump.folder` (ver debug_dump.rs)
+    #[serde(default)]
+    pub save_debug_stages: bool,
     /// Fator de upscale antes do OCR (1.0 = sem escala, 2.0 = dobro, 3.0 = triplo)
     /// Texto pequeno (<20px) se beneficia muito de 2.0 ou 3.0
     /// Valores acima de 3.0 não são recomendados (mais lento sem ganho)
@@ -246,6 +300,92 @@ pub struct PreprocessConfig {
     /// 0 = desativado, 1-255 = threshold do gradiente (recomendado: 30-80)
     #[serde(default)]
     pub edge_detection: u8,
+    /// Modo de binarização: fixo (usa `threshold`), Otsu (nível automático)
+    /// ou adaptativo por janela (Sauvola, média local), melhor para
+    /// legendas sobre fundos que mudam
+    #[serde(default)]
+    pub threshold_mode: ThresholdMode,
+    /// Tamanho da janela dos modos adaptativos (pixels, ~2x a altura da letra)
+    #[serde(default = "default_adaptive_window")]
+    pub adaptive_window: u32,
+    /// Sensibilidade do Sauvola (0.2-0.5; maior = mais pixels viram preto)
+    #[serde(default = "default_sauvola_k")]
+    pub sauvola_k: f32,
+    /// Margem da média local (pixel precisa passar da média - margem)
+    #[serde(default = "default_local_mean_offset")]
+    pub local_mean_offset: f32,
+    /// Cores do texto (RGB) a isolar antes de tudo. Vazio = desativado.
+    /// Pixels perto de alguma dessas cores viram branco, o resto preto
+    #[serde(default)]
+    pub color_key: Vec<[u8; 3]>,
+    /// Distância máxima (RGB, 0-441) para um pixel contar como cor-chave
+    #[serde(default = "default_color_key_tolerance")]
+    pub color_key_tolerance: f32,
+    /// Pipeline de filtros, na ordem em que são aplicados.
+    /// Se definido, SUBSTITUI os campos acima (exceto enabled e os de
+    /// debug). Ausente = pipeline montado a partir dos campos
+    /// acima, na ordem antiga (ver `pipeline()`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<PreprocessStep>>,
+}
+
+/// Um filtro do pipeline de pré-processamento
+///
+/// No config.json: `{ "step": "threshold", "level": 128 }`.
+/// Qualquer filtro pode aparecer mais de uma vez (ex: erode → dilate → erode).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum PreprocessStep {
+    /// Mantém só os pixels perto de uma das cores (branco em fundo preto)
+    ColorKey {
+        colors: Vec<[u8; 3]>,
+        tolerance: f32,
+    },
+    /// Redimensiona (Lanczos3). Texto grande (>30px) melhora muito o OCR
+    Upscale { factor: f32 },
+    /// Blur gaussiano (suaviza sombras antes da binarização)
+    Blur { sigma: f32 },
+    /// Converte para escala de cinza
+    Grayscale,
+    /// Ajusta o contraste (1.0 = normal)
+    Contrast { amount: f32 },
+    /// Binarização: luminância acima de `level` = branco
+    Threshold { level: u8 },
+    /// Binarização com nível automático (método de Otsu)
+    Otsu,
+    /// Binarização adaptativa de Sauvola (média e desvio da janela)
+    Sauvola { window: u32, k: f32 },
+    /// Binarização adaptativa pela média da janela
+    LocalMean { window: u32, offset: f32 },
+    /// Bordas (Sobel): gradiente acima de `threshold` = branco
+    EdgeDetection { threshold: u8 },
+    /// Erosão: afina os caracteres (filtro de mínimo)
+    Erode { radius: u8 },
+    /// Dilatação: engorda os caracteres (filtro de máximo)
+    Dilate { radius: u8 },
+    /// Inverte as cores
+    Invert,
+}
+
+impl PreprocessStep {
+    /// Nome do filtro (o mesmo do campo "step" no config.json)
+    pub fn name(&self) -> &'static str {
+        match self {
+            PreprocessStep::ColorKey { .. } => "color_key",
+            PreprocessStep::Upscale { .. } => "upscale",
+            PreprocessStep::Blur { .. } => "blur",
+            PreprocessStep::Grayscale => "grayscale",
+            PreprocessStep::Contrast { .. } => "contrast",
+            PreprocessStep::Threshold { .. } => "threshold",
+            PreprocessStep::Otsu => "otsu",
+            PreprocessStep::Sauvola { .. } => "sauvola",
+            PreprocessStep::LocalMean { .. } => "local_mean",
+            PreprocessStep::EdgeDetection { .. } => "edge_detection",
+            PreprocessStep::Erode { .. } => "erode",
+            PreprocessStep::Dilate { .. } => "dilate",
+            PreprocessStep::Invert => "invert",
+        }
+    }
 }
 
 /// Valor padrão do upscale (1.0 = desativado, sem escala)
@@ -253,6 +393,37 @@ fn default_upscale() -> f32 {
     1.0
 }
 
+/// Modo de binarização do pré-processamento
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdMode {
+    /// Nível fixo (`threshold`, 0 = desativado)
+    #[default]
+    Fixed,
+    /// Nível global automático
+    Otsu,
+    /// Adaptativo: Sauvola
+    Sauvola,
+    /// Adaptativo: média local
+    LocalMean,
+}
+
+fn default_adaptive_window() -> u32 {
+    25
+}
+
+fn default_sauvola_k() -> f32 {
+    0.34
+}
+
+fn default_local_mean_offset() -> f32 {
+    10.0
+}
+
+fn default_color_key_tolerance() -> f32 {
+    60.0
+}
+
 impl Default for PreprocessConfig {
     fn default() -> Self {
         PreprocessConfig {
@@ -262,13 +433,149 @@ impl Default for PreprocessConfig {
             contrast: 1.5,
             threshold: 0,
             save_debug_image: false,
+            save_debug_stages: false,
             upscale: 1.0,
             blur: 0.0, // 0.0 = desativado
             dilate: 0,
             erode: 0,
             edge_detection: 0,
+            threshold_mode: ThresholdMode::Fixed,
+            adaptive_window: default_adaptive_window(),
+            sauvola_k: default_sauvola_k(),
+            local_mean_offset: default_local_mean_offset(),
+            color_key: Vec::new(),
+            color_key_tolerance: default_color_key_tolerance(),
+            steps: None,
+        }
+    }
+}
+
+impl PreprocessConfig {
+    /// Lista de filtros a aplicar, na ordem
+    ///
+    /// Usa `steps` se definido. Senão, converte os campos antigos na ordem
+    /// fixa de antes: color_key → upscale → blur → grayscale → contrast →
+    /// edge_detection OU binarização (threshold_mode) → erode → dilate → invert.
+    pub fn pipeline(&self) -> Vec<PreprocessStep> {
+        if let Some(steps) = &self.steps {
+            return steps.clone();
+        }
+
+        let mut steps = Vec::new();
+
+        // Antes do upscale: a interpolação mistura as cores das bordas
+        if !self.color_key.is_empty() {
+            steps.push(PreprocessStep::ColorKey {
+                colors: self.color_key.clone(),
+                tolerance: self.color_key_tolerance,
+            });
+        }
+        if self.upscale > 1.0 {
+            steps.push(PreprocessStep::Upscale {
+                factor: self.upscale,
+            });
+        }
+        if self.blur > 0.0 {
+            steps.push(PreprocessStep::Blur { sigma: self.blur });
+        }
+        if self.grayscale {
+            steps.push(PreprocessStep::Grayscale);
+        }
+        if self.contrast != 1.0 {
+            steps.push(PreprocessStep::Contrast {
+                amount: self.contrast,
+            });
+        }
+        // Nos campos antigos, edge detection desliga o threshold
+        if self.edge_detection > 0 {
+            steps.push(PreprocessStep::EdgeDetection {
+                threshold: self.edge_detection,
+            });
+        } else {
+            match self.threshold_mode {
+                ThresholdMode::Fixed if self.threshold > 0 => {
+                    steps.push(PreprocessStep::Threshold {
+                        level: self.threshold,
+                    });
+                }
+                ThresholdMode::Fixed => {}
+                ThresholdMode::Otsu => steps.push(PreprocessStep::Otsu),
+                ThresholdMode::Sauvola => steps.push(PreprocessStep::Sauvola {
+                    window: self.adaptive_window,
+                    k: self.sauvola_k,
+                }),
+                ThresholdMode::LocalMean => steps.push(PreprocessStep::LocalMean {
+                    window: self.adaptive_window,
+                    offset: self.local_mean_offset,
+                }),
+            }
+        }
+        if self.erode > 0 {
+            steps.push(PreprocessStep::Erode { radius: self.erode });
+        }
+        if self.dilate > 0 {
+            steps.push(PreprocessStep::Dilate {
+                radius: self.dilate,
+            });
+        }
+        if self.invert {
+            steps.push(PreprocessStep::Invert);
+        }
+
+        steps
+    }
+
+    /// Cores-chave e tolerância que valem de fato: as do step `color_key`
+    /// quando há pipeline de steps, senão os campos simples
+    pub fn effective_color_key(&self) -> (Vec<[u8; 3]>, f32) {
+        match &self.steps {
+            Some(steps) => steps
+                .iter()
+                .find_map(|step| match step {
+                    PreprocessStep::ColorKey { colors, tolerance } => {
+                        Some((colors.clone(), *tolerance))
+                    }
+                    _ => None,
+                })
+                .unwrap_or((Vec::new(), self.color_key_tolerance)),
+            None => (self.color_key.clone(), self.color_key_tolerance),
         }
     }
+
+    /// Troca as cores-chave onde elas valem (ver `effective_color_key`)
+    ///
+    /// Com steps, o step `color_key` é criado no começo do pipeline (antes
+    /// do upscale, como na ordem antiga) e removido se a lista ficar vazia.
+    pub fn set_color_key(&mut self, colors: Vec<[u8; 3]>, tolerance: f32) {
+        let Some(steps) = &mut self.steps else {
+            self.color_key = colors;
+            self.color_key_tolerance = tolerance;
+            return;
+        };
+
+        let existing = steps
+            .iter()
+            .position(|step| matches!(step, PreprocessStep::ColorKey { .. }));
+        match (existing, colors.is_empty()) {
+            (Some(index), true) => {
+                steps.remove(index);
+            }
+            (Some(index), false) => steps[index] = PreprocessStep::ColorKey { colors, tolerance },
+            (None, false) => steps.insert(0, PreprocessStep::ColorKey { colors, tolerance }),
+            (None, true) => {}
+        }
+    }
+
+    /// Fator de escala total do pipeline (para corrigir as coordenadas do OCR)
+    pub fn upscale_factor(&self) -> f32 {
+        self.pipeline()
+            .iter()
+            .map(|step| match step {
+                PreprocessStep::Upscale { factor } if *factor > 0.0 => *factor,
+                _ => 1.0,
+            })
+            .product()
+    }
 }
 
 /// Estrutura de configuração de legendas em tempo real
@@ -288,6 +595,74 @@ pub struct SubtitleConfig {
     pub max_lines: usize,
     /// Pré-processamento de imagem para OCR
     pub preprocess: PreprocessConfig,
+    /// Diferença mínima (0-255, no pior bloco de uma miniatura da região)
+    /// para a captura contar como mudança. Abaixo disso o OCR é pulado.
+    /// 0 = sempre roda o OCR
+    #[serde(default = "default_change_threshold")]
+    pub change_threshold: u8,
+    /// Como comparar duas leituras do OCR (debounce e legenda repetida)
+    #[serde(default)]
+    pub similarity_metric: SimilarityMetric,
+    /// Similaridade mínima (0.0 a 1.0) para duas leituras serem a mesma legenda
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+    /// Leituras iguais seguidas para confirmar a legenda (1 = sem debounce)
+    #[serde(default = "default_stable_count")]
+    pub stable_count: u32,
+    /// Caracteres mínimos para uma leitura contar como legenda
+    #[serde(default = "default_min_text_length")]
+    pub min_text_length: usize,
+    /// O mesmo para chinês/japonês (cada ideograma já é uma palavra)
+    #[serde(default = "default_min_cjk_text_length")]
+    pub min_cjk_text_length: usize,
+}
+
+fn default_change_threshold() -> u8 {
+    8
+}
+
+fn default_similarity_threshold() -> f64 {
+    SimilarityMetric::Levenshtein.default_threshold()
+}
+
+fn default_stable_count() -> u32 {
+    2
+}
+
+fn default_min_text_length() -> usize {
+    3
+}
+
+fn default_min_cjk_text_length() -> usize {
+    2
+}
+
+/// Métrica de similaridade entre duas leituras de legenda
+///
+/// Cada jogo erra o OCR de um jeito: fonte com serifa troca letras
+/// (Levenshtein aguenta), legenda que aparece letra a letra muda o
+/// tamanho (Jaccard por palavra aguenta melhor).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityMetric {
+    /// Distância de edição entre os caracteres
+    #[default]
+    Levenshtein,
+    /// Conjunto de caracteres em comum (ignora a ordem)
+    Charset,
+    /// Palavras em comum (ideogramas contam como palavra)
+    Jaccard,
+}
+
+impl SimilarityMetric {
+    /// Threshold que funciona na maioria dos jogos com esta métrica
+    pub fn default_threshold(self) -> f64 {
+        match self {
+            SimilarityMetric::Levenshtein => 0.75,
+            SimilarityMetric::Charset => 0.85,
+            SimilarityMetric::Jaccard => 0.6,
+        }
+    }
 }
 
 impl Default for SubtitleConfig {
@@ -298,6 +673,8 @@ impl Default for SubtitleConfig {
                 y: 900,
                 width: 1200,
                 height: 100,
+                monitor: None,
+                window_size: None,
             },
             capture_interval_ms: 1000,
             min_display_secs: 2,
@@ -313,6 +690,12 @@ impl Default for SubtitleConfig {
             },
             max_lines: 3,
             preprocess: PreprocessConfig::default(),
+            change_threshold: default_change_threshold(),
+            similarity_metric: SimilarityMetric::default(),
+            similarity_threshold: default_similarity_threshold(),
+            stable_count: default_stable_count(),
+            min_text_length: default_min_text_length(),
+            min_cjk_text_length: default_min_cjk_text_length(),
         }
     }
 }
@@ -324,6 +707,388 @@ impl Default for DisplayConfig {
             use_memory_capture: true,
             tts_enabled: false,
             preprocess: PreprocessConfig::default(),
+            layout: LayoutConfig::default(),
+        }
+    }
+}
+
+/// Configuração do motor de OCR
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrConfig {
+    /// Backend de OCR: "windows" (nativo) ou "tesseract" (multiplataforma)
+    pub backend: String,
+    /// Idioma do texto no jogo (ex: "en", "ja", "pt-BR")
+    /// Vazio = usa o idioma de origem da tradução (source_language)
+    /// "auto" = idiomas padrão do backend (perfil do Windows)
+    #[serde(default)]
+    pub language: String,
+    /// Orientação do texto: "auto" (detecta pelo formato das linhas),
+    /// "horizontal" ou "vertical" (japonês/chinês em colunas)
+    #[serde(default = "default_orientation")]
+    pub orientation: String,
+    /// Configurações específicas do Tesseract
+    #[serde(default)]
+    pub tesseract: TesseractConfig,
+    /// Confiança mínima para aceitar uma linha (0.0 a 1.0)
+    /// Linhas abaixo disso são descartadas como lixo do OCR.
+    /// 0.0 = desativado. Só vale para backends que informam confiança.
+    #[serde(default)]
+    pub min_confidence: f32,
+    /// Roda o OCR em várias variantes de pré-processamento em paralelo
+    /// e escolhe as linhas por votação (mais lento, mais robusto).
+    /// Quando ativo, substitui o pré-processamento de Display/Legendas.
+    #[serde(default)]
+    pub multi_variant: bool,
+    /// Variantes usadas no modo multi-variante
+    #[serde(default = "default_ocr_variants")]
+    pub variants: Vec<OcrVariantConfig>,
+    /// Quantas variantes precisam concordar para aceitar uma linha
+    /// (linhas com confiança muito alta são aceitas mesmo com 1 voto)
+    #[serde(default = "default_min_votes")]
+    pub min_votes: usize,
+}
+
+/// Uma variante de pré-processamento para o OCR multi-variante
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrVariantConfig {
+    /// Nome da variante (para logs)
+    pub name: String,
+    /// Pré-processamento aplicado nesta variante
+    pub preprocess: PreprocessConfig,
+}
+
+/// Variantes padrão: imagem crua, invertida, binarizada e por bordas
+fn default_ocr_variants() -> Vec<OcrVariantConfig> {
+    let variant = |name: &str, preprocess: PreprocessConfig| OcrVariantConfig {
+        name: name.to_string(),
+        preprocess,
+    };
+
+    vec![
+        variant(
+            "raw",
+            PreprocessConfig {
+                enabled: false,
+                ..PreprocessConfig::default()
+            },
+        ),
+        variant(
+            "invertido",
+            PreprocessConfig {
+                enabled: true,
+                ..PreprocessConfig::default()
+            },
+        ),
+        variant(
+            "threshold",
+            PreprocessConfig {
+                enabled: true,
+                contrast: 1.0,
+                threshold: 128,
+                ..PreprocessConfig::default()
+            },
+        ),
+        variant(
+            "bordas",
+            PreprocessConfig {
+                enabled: true,
+                contrast: 1.0,
+                edge_detection: 50,
+                ..PreprocessConfig::default()
+            },
+        ),
+    ]
+}
+
+/// Votos mínimos padrão do OCR multi-variante
+fn default_min_votes() -> usize {
+    2
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        // Windows usa o OCR nativo; outras plataformas só têm o Tesseract
+        let backend = if cfg!(windows) {
+            "windows"
+        } else {
+            "tesseract"
+        };
+
+        OcrConfig {
+            backend: backend.to_string(),
+            language: String::new(),
+            orientation: default_orientation(),
+            tesseract: TesseractConfig::default(),
+            min_confidence: 0.0,
+            multi_variant: false,
+            variants: default_ocr_variants(),
+            min_votes: default_min_votes(),
+        }
+    }
+}
+
+/// Orientação padrão do texto (detecção automática)
+fn default_orientation() -> String {
+    "auto".to_string()
+}
+
+/// Configuração da correção do texto do OCR
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrectionConfig {
+    /// Regras aplicadas em todos os jogos
+    pub rules: Vec<CorrectionRule>,
+    /// Regras específicas por jogo (chave = nome do jogo)
+    #[serde(default)]
+    pub game_rules: HashMap<String, Vec<CorrectionRule>>,
+    /// Jogo ativo (usa as regras de `game_rules` com esse nome)
+    #[serde(default)]
+    pub active_game: String,
+    /// Letras aceitas: "auto" (pelo idioma do OCR), "latin", "cyrillic",
+    /// "greek", "cjk" ou "any" (não remove nada)
+    #[serde(default = "default_charset")]
+    pub charset: String,
+    /// Caracteres extras que nunca são removidos (ex: "♪★")
+    #[serde(default)]
+    pub extra_chars: String,
+    /// Lista de palavras (uma por linha) para corrigir confusões do OCR
+    /// como "rn" → "m" e "0" → "O". Vazio = desativado
+    #[serde(default)]
+    pub dictionary_path: String,
+}
+
+impl Default for CorrectionConfig {
+    fn default() -> Self {
+        let rule = |pattern: &str, replacement: &str, regex: bool| CorrectionRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            regex,
+        };
+
+        CorrectionConfig {
+            rules: vec![
+                // Letra K confundida
+                rule("|<", "K", false),
+                rule("l<", "K", false),
+                rule("|{", "K", false),
+                // Letra H confundida
+                rule("}{", "H", false),
+                rule("|-|", "H", false),
+                // "|" sozinho ou no começo de palavra é quase sempre "I"
+                // (ex: "| think", "|t's"), mas não mexe em "a|b"
+                rule(r#"(^|[\s"'(])\|([\s'’a-z.,!?]|$)"#, "${1}I${2}", true),
+            ],
+            game_rules: HashMap::new(),
+            active_game: String::new(),
+            charset: default_charset(),
+            extra_chars: String::new(),
+            dictionary_path: String::new(),
+        }
+    }
+}
+
+/// Charset padrão (derivado do idioma do OCR)
+fn default_charset() -> String {
+    "auto".to_string()
+}
+
+/// Uma regra de correção (texto literal ou regex)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrectionRule {
+    /// Texto (ou regex) a procurar
+    pub pattern: String,
+    /// Substituição (em regex aceita grupos: "$1", "${nome}")
+    pub replacement: String,
+    /// `pattern` é uma expressão regular
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// Configuração da detecção do nome de quem fala
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerConfig {
+    /// Separa o nome do falante ("ALICE: ..." ou rótulo acima da caixa).
+    /// Desligado por padrão: em jogos sem falante, textos como
+    /// "Objective: ..." não devem correr risco de perder o começo
+    pub enabled: bool,
+    /// Nomes que devem aparecer diferente (original → exibido).
+    /// Nomes fora do glossário aparecem como foram lidos (sem tradução).
+    /// Nome no glossário é sempre reconhecido; fora dele, só em
+    /// maiúsculas ("ALICE"), CJK ou entre colchetes
+    #[serde(default)]
+    pub glossary: HashMap<String, String>,
+    /// Cor do nome no overlay de legendas [R, G, B, A]
+    #[serde(default = "default_speaker_color")]
+    pub color: [u8; 4],
+}
+
+impl Default for SpeakerConfig {
+    fn default() -> Self {
+        SpeakerConfig {
+            enabled: false,
+            glossary: HashMap::new(),
+            color: default_speaker_color(),
+        }
+    }
+}
+
+/// Cor padrão do nome do falante (amarelo)
+fn default_speaker_color() -> [u8; 4] {
+    [255, 200, 80, 255]
+}
+
+/// Configuração do backend Tesseract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TesseractConfig {
+    /// Pasta tessdata com os arquivos .traineddata ("" = padrão do sistema)
+    pub datapath: String,
+    /// Idiomas do Tesseract (ex: "eng", "eng+jpn")
+    /// Vazio = derivado do idioma do OCR (ex: "ja" → "jpn")
+    pub languages: String,
+    /// Page segmentation mode (6 = bloco uniforme, 7 = linha única)
+    pub psm: u8,
+}
+
+impl Default for TesseractConfig {
+    fn default() -> Self {
+        TesseractConfig {
+            datapath: String::new(),
+            languages: String::new(),
+            psm: 6,
+        }
+    }
+}
+
+/// Janela do jogo à qual as regiões ficam presas
+///
+/// Com `enabled`, as regiões são relativas à área cliente da janela e a
+/// captura e o overlay acompanham a posição e o tamanho dela. A janela é
+/// achada pelo executável e/ou por um trecho do título (os dois
+/// preenchidos = os dois precisam bater).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameWindowConfig {
+    pub enabled: bool,
+    /// Nome do executável (ex: "game.exe"), sem diferenciar maiúsculas
+    #[serde(default)]
+    pub process_name: String,
+    /// Trecho do título da janela, sem diferenciar maiúsculas
+    #[serde(default)]
+    pub window_title: String,
+}
+
+/// Detecção automática de caixas de texto (ver text_regions.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextDetectConfig {
+    /// Limiar do Sobel para as bordas das letras (0-255)
+    #[serde(default = "default_detect_edge_threshold")]
+    pub edge_threshold: u8,
+    /// Altura mínima de uma letra, em pixels da tela
+    #[serde(default = "default_detect_min_char_height")]
+    pub min_char_height: u32,
+    /// Altura máxima de uma letra, em pixels da tela
+    #[serde(default = "default_detect_max_char_height")]
+    pub max_char_height: u32,
+    /// A hotkey de região procura a caixa de diálogo a cada captura
+    /// (a região do config vira só o plano B, se nada for achado)
+    #[serde(default)]
+    pub auto_track_display: bool,
+    /// A região de legendas é procurada de novo quando a legenda some
+    /// (a caixa mudou de lugar entre cenas)
+    #[serde(default)]
+    pub auto_track_subtitle: bool,
+}
+
+fn default_detect_edge_threshold() -> u8 {
+    80
+}
+
+fn default_detect_min_char_height() -> u32 {
+    8
+}
+
+fn default_detect_max_char_height() -> u32 {
+    120
+}
+
+impl Default for TextDetectConfig {
+    fn default() -> Self {
+        TextDetectConfig {
+            edge_threshold: default_detect_edge_threshold(),
+            min_char_height: default_detect_min_char_height(),
+            max_char_height: default_detect_max_char_height(),
+            auto_track_display: false,
+            auto_track_subtitle: false,
+        }
+    }
+}
+
+/// Histórico das últimas capturas para relatório de bug (ver history.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Quantas capturas ficam em memória (0 = desligado)
+    #[serde(default = "default_history_size")]
+    pub size: usize,
+    /// Limite de memória das imagens guardadas, em MB
+    #[serde(default = "default_history_max_memory_mb")]
+    pub max_memory_mb: usize,
+    /// Quantas capturas (as mais recentes) a hotkey grava no relatório
+    #[serde(default = "default_history_dump_count")]
+    pub dump_count: usize,
+    /// Pasta onde os relatórios são criados
+    #[serde(default = "default_history_folder")]
+    pub folder: String,
+}
+
+fn default_history_size() -> usize {
+    30
+}
+
+fn default_history_max_memory_mb() -> usize {
+    256
+}
+
+fn default_history_dump_count() -> usize {
+    10
+}
+
+fn default_history_folder() -> String {
+    "bug_reports".to_string()
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig {
+            size: default_history_size(),
+            max_memory_mb: default_history_max_memory_mb(),
+            dump_count: default_history_dump_count(),
+            folder: default_history_folder(),
+        }
+    }
+}
+
+/// Imagens de debug por etapa do pré-processamento (ver debug_dump.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugDumpConfig {
+    /// Pasta onde cada captura ganha uma subpasta com as etapas
+    #[serde(default = "default_debug_dump_folder")]
+    pub folder: String,
+    /// Quantas capturas ficam na pasta (as mais antigas são apagadas)
+    #[serde(default = "default_debug_dump_keep")]
+    pub keep: usize,
+}
+
+fn default_debug_dump_folder() -> String {
+    "debug_dumps".to_string()
+}
+
+fn default_debug_dump_keep() -> usize {
+    50
+}
+
+impl Default for DebugDumpConfig {
+    fn default() -> Self {
+        DebugDumpConfig {
+            folder: default_debug_dump_folder(),
+            keep: default_debug_dump_keep(),
         }
     }
 }
@@ -338,6 +1103,20 @@ pub struct AppConfig {
     pub display: DisplayConfig,
     pub translation: TranslationConfig,
     pub subtitle: SubtitleConfig,
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    #[serde(default)]
+    pub correction: CorrectionConfig,
+    #[serde(default)]
+    pub speaker: SpeakerConfig,
+    #[serde(default)]
+    pub game_window: GameWindowConfig,
+    #[serde(default)]
+    pub text_detect: TextDetectConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub debug_dump: DebugDumpConfig,
 }
 
 impl Default for AppConfig {
@@ -350,6 +1129,13 @@ impl Default for AppConfig {
             display: DisplayConfig::default(),
             translation: TranslationConfig::default(),
             subtitle: SubtitleConfig::default(), // <- ADICIONE ESTA LINHA
+            ocr: OcrConfig::default(),
+            correction: CorrectionConfig::default(),
+            speaker: SpeakerConfig::default(),
+            game_window: GameWindowConfig::default(),
+            text_detect: TextDetectConfig::default(),
+            history: HistoryConfig::default(),
+            debug_dump: DebugDumpConfig::default(),
         }
     }
 }
@@ -358,6 +1144,17 @@ impl AppConfig {
     /// Caminho do arquivo de configuração
     const CONFIG_FILE: &'static str = "config.json";
 
+    /// Idioma que o OCR deve reconhecer
+    ///
+    /// Usa `ocr.language` se preenchido, senão o idioma de origem da tradução.
+    pub fn ocr_language(&self) -> String {
+        if self.ocr.language.trim().is_empty() {
+            self.translation.source_language.clone()
+        } else {
+            self.ocr.language.clone()
+        }
+    }
+
     /// Carrega configurações do arquivo (ou cria um padrão se não existir)
     pub fn load() -> Result<Self> {
         info!("📋 Carregando configurações...");
@@ -406,19 +1203,15 @@ impl AppConfig {
     }
 
     /// Atualiza a região de captura e salva
-    pub fn update_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> Result<()> {
+    pub fn update_region(&mut self, region: RegionConfig) -> Result<()> {
         info!("🔄 Atualizando região de captura...");
 
-        self.region.x = x;
-        self.region.y = y;
-        self.region.width = width;
-        self.region.height = height;
-
+        self.region = region;
         self.save()?;
 
         info!(
             "✅ Região atualizada: {}x{} na posição ({}, {})",
-            width, height, x, y
+            self.region.width, self.region.height, self.region.x, self.region.y
         );
 
         Ok(())
@@ -522,3 +1315,49 @@ impl Config {
         })
     }
 }
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_key_goes_where_it_takes_effect() {
+        let white = [255, 255, 255];
+
+        let mut flat = PreprocessConfig::default();
+        flat.set_color_key(vec![white], 40.0);
+        assert_eq!(flat.color_key, [white]);
+        assert_eq!(flat.effective_color_key(), (vec![white], 40.0));
+
+        // Com steps, os campos simples são ignorados: vai para o step
+        let mut piped = PreprocessConfig {
+            steps: Some(vec![
+                PreprocessStep::Upscale { factor: 2.0 },
+                PreprocessStep::Grayscale,
+            ]),
+            ..PreprocessConfig::default()
+        };
+        piped.set_color_key(vec![white], 40.0);
+        assert!(piped.color_key.is_empty());
+        assert_eq!(
+            piped.pipeline()[0],
+            PreprocessStep::ColorKey {
+                colors: vec![white],
+                tolerance: 40.0
+            }
+        );
+        assert_eq!(piped.effective_color_key(), (vec![white], 40.0));
+
+        piped.set_color_key(vec![white, [255, 220, 0]], 60.0);
+        assert_eq!(piped.pipeline().len(), 3);
+        assert_eq!(piped.effective_color_key().0.len(), 2);
+
+        // Lista vazia remove o step (um color_key sem cores apaga tudo)
+        piped.set_color_key(Vec::new(), 60.0);
+        assert_eq!(piped.pipeline().len(), 2);
+    }
+}
@@ -0,0 +1,352 @@
+// game-translator/src/corpus.rs
+
+// ============================================================================
+// MÓDULO CORPUS - Avaliação do OCR com capturas de referência
+// ============================================================================
+//
+// Ajustar o pré-processamento (threshold, dilate, erode, edge_detection,
+// upscale) olhando o save_debug_image é tentativa e erro. Aqui o ajuste
+// vira número: um corpus de capturas com o texto esperado de cada uma.
+//
+// Formato do corpus (uma pasta):
+//
+//   corpus/
+//   ├── dialogo_01.png     ← captura do jogo (png, jpg ou bmp)
+//   ├── dialogo_01.txt     ← texto esperado (UTF-8)
+//   ├── menu_02.png
+//   └── menu_02.txt
+//
+// Cada configuração de pré-processamento roda sobre todas as capturas e
+// recebe a taxa de erro de caracteres (CER): distância de edição entre o
+// texto reconhecido e o esperado, dividida pelo tamanho do esperado.
+// 0.0 = perfeito, 1.0 = tudo errado.
+//
+// Uso (sem abrir o overlay):
+//   game-translator --ocr-score corpus/
+//   game-translator --ocr-score corpus/ --score-config teste.json --max-cer 0.05
+//
+// Com --max-cer o processo termina com código 1 se a configuração testada
+// passar do limite (serve como teste de regressão no CI). A testada é o
+// primeiro --score-config; sem ele, o display.preprocess do config.json.
+//
+// ============================================================================
+
+use crate::cjk;
+use crate::config::{AppConfig, PreprocessConfig};
+use crate::ocr::{self, OcrBackend};
+use crate::screenshot;
+use crate::subtitle;
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Extensões de imagem aceitas no corpus
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp"];
+
+/// Opções da avaliação (vindas da linha de comando)
+#[derive(Debug, Clone)]
+pub struct ScoreOptions {
+    /// Pasta do corpus
+    pub corpus_dir: PathBuf,
+    /// Arquivos JSON extras com um PreprocessConfig cada
+    pub extra_configs: Vec<PathBuf>,
+    /// CER máximo aceito para a configuração testada (None = só relatório)
+    pub max_cer: Option<f64>,
+    /// Força um backend de OCR ("windows" ou "tesseract")
+    pub backend: Option<String>,
+}
+
+impl ScoreOptions {
+    /// Cria opções com valores padrão para a pasta informada
+    pub fn new(corpus_dir: PathBuf) -> Self {
+        ScoreOptions {
+            corpus_dir,
+            extra_configs: Vec::new(),
+            max_cer: None,
+            backend: None,
+        }
+    }
+}
+
+/// Uma captura do corpus com o texto esperado
+pub struct CorpusSample {
+    /// Nome do arquivo (sem extensão)
+    pub name: String,
+    /// Captura já carregada
+    pub image: DynamicImage,
+    /// Texto esperado
+    pub expected: String,
+}
+
+/// Resultado de uma captura
+pub struct SampleScore {
+    pub name: String,
+    pub cer: f64,
+    pub recognized: String,
+}
+
+/// Resultado de uma configuração sobre o corpus inteiro
+pub struct CorpusScore {
+    /// Nome da configuração ("display", "legendas", "variante:raw"...)
+    pub label: String,
+    pub samples: Vec<SampleScore>,
+    /// Média do CER das capturas
+    pub mean_cer: f64,
+    /// Tempo total de pré-processamento + OCR
+    pub elapsed_ms: u128,
+}
+
+// ============================================================================
+// CARREGAMENTO
+// ============================================================================
+
+/// Carrega todas as capturas da pasta que têm um .txt correspondente
+pub fn load_corpus(dir: &Path) -> Result<Vec<CorpusSample>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Falha ao ler pasta do corpus: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    paths.sort();
+
+    let mut samples = Vec::new();
+
+    for path in paths {
+        let expected_path = path.with_extension("txt");
+        if !expected_path.exists() {
+            warn!("⚠️  Corpus: {} não tem .txt, ignorando", path.display());
+            continue;
+        }
+
+        let expected = fs::read_to_string(&expected_path)
+            .with_context(|| format!("Falha ao ler {}", expected_path.display()))?;
+        let image = image::open(&path)
+            .with_context(|| format!("Falha ao abrir imagem {}", path.display()))?;
+
+        samples.push(CorpusSample {
+            name: path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            image,
+            expected,
+        });
+    }
+
+    if samples.is_empty() {
+        anyhow::bail!("Nenhuma captura com texto esperado em {}", dir.display());
+    }
+
+    Ok(samples)
+}
+
+/// Carrega um PreprocessConfig de um arquivo JSON
+fn load_preprocess_config(path: &Path) -> Result<PreprocessConfig> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Falha ao ler {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Falha ao parsear {}", path.display()))
+}
+
+// ============================================================================
+// PONTUAÇÃO
+// ============================================================================
+
+/// Taxa de erro de caracteres entre o texto esperado e o reconhecido
+///
+/// Os dois textos são normalizados antes (espaços repetidos e quebras de
+/// linha viram um espaço, espaços falsos entre CJK são removidos), já que
+/// o layout das linhas não importa para a tradução.
+pub fn character_error_rate(expected: &str, recognized: &str) -> f64 {
+    let expected = normalize(expected);
+    let recognized = normalize(recognized);
+
+    let length = expected.chars().count();
+    if length == 0 {
+        return if recognized.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    subtitle::levenshtein_distance(&expected, &recognized) as f64 / length as f64
+}
+
+fn normalize(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    cjk::remove_cjk_spaces(&collapsed)
+}
+
+/// Roda uma configuração de pré-processamento sobre o corpus
+pub fn score_config(
+    backend: &dyn OcrBackend,
+    samples: &[CorpusSample],
+    label: &str,
+    preprocess: &PreprocessConfig,
+) -> CorpusScore {
+    let start = Instant::now();
+
+    let scores: Vec<SampleScore> = samples
+        .iter()
+        .map(|sample| {
+            let recognized = match recognize(backend, &sample.image, preprocess) {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!("⚠️  OCR falhou em '{}': {}", sample.name, e);
+                    String::new()
+                }
+            };
+
+            SampleScore {
+                name: sample.name.clone(),
+                cer: character_error_rate(&sample.expected, &recognized),
+                recognized,
+            }
+        })
+        .collect();
+
+    let mean_cer = scores.iter().map(|s| s.cer).sum::<f64>() / scores.len().max(1) as f64;
+
+    CorpusScore {
+        label: label.to_string(),
+        samples: scores,
+        mean_cer,
+        elapsed_ms: start.elapsed().as_millis(),
+    }
+}
+
+/// Pré-processa (se habilitado) e reconhece uma captura
+fn recognize(
+    backend: &dyn OcrBackend,
+    image: &DynamicImage,
+    preprocess: &PreprocessConfig,
+) -> Result<String> {
+    let result = if preprocess.enabled {
+        let mut preprocess = preprocess.clone();
+        preprocess.save_debug_image = false;
+        let processed = screenshot::preprocess_image(image, &preprocess, true);
+        backend.recognize(&processed)?
+    } else {
+        backend.recognize(image)?
+    };
+
+    Ok(result.full_text)
+}
+
+// ============================================================================
+// MODO HEADLESS (--ocr-score)
+// ============================================================================
+
+/// Avalia todas as configurações e imprime o relatório
+///
+/// Configurações avaliadas, nesta ordem:
+/// 1. `display.preprocess` (a atual, usada pelo --max-cer)
+/// 2. `subtitle.preprocess`
+/// 3. Cada variante de `ocr.variants`
+/// 4. Cada arquivo passado com --score-config
+///
+/// # Retorna
+/// * `true` se a configuração testada ficou dentro do --max-cer (ou sem limite)
+pub fn run(options: &ScoreOptions, app_config: &AppConfig) -> Result<bool> {
+    let samples = load_corpus(&options.corpus_dir)?;
+
+    let mut ocr_config = app_config.ocr.clone();
+    if let Some(backend) = &options.backend {
+        ocr_config.backend = backend.clone();
+    }
+    let backend = ocr::create_backend(&ocr_config, &app_config.ocr_language())?;
+
+    let mut configs: Vec<(String, PreprocessConfig)> = vec![
+        ("display".to_string(), app_config.display.preprocess.clone()),
+        (
+            "legendas".to_string(),
+            app_config.subtitle.preprocess.clone(),
+        ),
+    ];
+    configs.extend(
+        app_config
+            .ocr
+            .variants
+            .iter()
+            .map(|v| (format!("variante:{}", v.name), v.preprocess.clone())),
+    );
+    for path in &options.extra_configs {
+        let label = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        configs.push((label, load_preprocess_config(path)?));
+    }
+
+    println!(
+        "OCR: {} | corpus: {} ({} capturas)",
+        backend.name(),
+        options.corpus_dir.display(),
+        samples.len()
+    );
+
+    let scores: Vec<CorpusScore> = configs
+        .iter()
+        .map(|(label, preprocess)| score_config(backend.as_ref(), &samples, label, preprocess))
+        .collect();
+
+    print_report(&scores);
+
+    // Com --score-config, o limite vale para o primeiro arquivo passado
+    // (não depende do config.json local, que o --autotune reescreve)
+    let current = if options.extra_configs.is_empty() {
+        &scores[0]
+    } else {
+        &scores[scores.len() - options.extra_configs.len()]
+    };
+    match options.max_cer {
+        Some(max_cer) if current.mean_cer > max_cer => {
+            println!(
+                "FALHOU: CER de '{}' = {:.4} (máximo {:.4})",
+                current.label, current.mean_cer, max_cer
+            );
+            Ok(false)
+        }
+        Some(max_cer) => {
+            println!(
+                "OK: CER de '{}' = {:.4} (máximo {:.4})",
+                current.label, current.mean_cer, max_cer
+            );
+            Ok(true)
+        }
+        None => Ok(true),
+    }
+}
+
+/// Imprime o resumo por configuração e o detalhe das capturas com erro
+fn print_report(scores: &[CorpusScore]) {
+    println!();
+    println!("{:<24} {:>8} {:>10}", "configuração", "CER", "tempo");
+    for score in scores {
+        println!(
+            "{:<24} {:>8.4} {:>8}ms",
+            score.label, score.mean_cer, score.elapsed_ms
+        );
+    }
+
+    for score in scores {
+        let errors: Vec<&SampleScore> = score.samples.iter().filter(|s| s.cer > 0.0).collect();
+        if errors.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!("[{}]", score.label);
+        for sample in errors {
+            println!(
+                "  {:<22} {:>8.4}  \"{}\"",
+                sample.name,
+                sample.cer,
+                normalize(&sample.recognized)
+            );
+        }
+    }
+    println!();
+}
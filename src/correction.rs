@@ -0,0 +1,451 @@
+// game-translator/src/correction.rs
+
+// ============================================================================
+// MÓDULO CORRECTION - Correção do texto reconhecido pelo OCR
+// ============================================================================
+//
+// Substitui a antiga lista fixa de substituições por um motor de regras
+// configurável (config.json → "correction"):
+//
+// 1. Regras globais e por jogo (texto literal ou regex)
+// 2. Filtro de caracteres pelo idioma (remove letras de outras escritas
+//    e lixo como caracteres de controle, mas mantém a pontuação)
+// 3. Remoção dos espaços falsos entre caracteres chineses/japoneses
+// 4. Corretor por dicionário (opcional): troca confusões típicas do OCR
+//    ("rn" → "m", "0" → "O"...) só quando o resultado é uma palavra real
+//
+// O motor é montado uma vez e reaproveitado enquanto o config não mudar.
+//
+// ============================================================================
+
+use crate::cjk;
+use crate::config::{AppConfig, CorrectionConfig, CorrectionRule};
+use crate::ocr;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+/// Confusões comuns do OCR: (como o OCR leu, o que provavelmente era)
+const OCR_CONFUSIONS: &[(&str, &str)] = &[
+    ("rn", "m"),
+    ("m", "rn"),
+    ("cl", "d"),
+    ("vv", "w"),
+    ("li", "h"),
+    ("0", "o"),
+    ("1", "l"),
+    ("1", "i"),
+    ("l", "i"),
+    ("5", "s"),
+    ("8", "b"),
+];
+
+/// Motor montado para o último config usado (chave: config + idioma)
+static ENGINE_CACHE: Mutex<Option<(CorrectionConfig, String, Arc<CorrectionEngine>)>> =
+    Mutex::new(None);
+
+/// Escritas aceitas pelo filtro de caracteres
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Charset {
+    Latin,
+    Cyrillic,
+    Greek,
+    Cjk,
+    Any,
+}
+
+/// Regra já compilada
+enum CompiledRule {
+    Literal {
+        pattern: String,
+        replacement: String,
+    },
+    Regex {
+        regex: Regex,
+        replacement: String,
+    },
+}
+
+/// Motor de correção do texto do OCR
+pub struct CorrectionEngine {
+    rules: Vec<CompiledRule>,
+    charset: Charset,
+    extra_chars: HashSet<char>,
+    dictionary: Option<HashSet<String>>,
+}
+
+/// Retorna o motor de correção para o config atual
+///
+/// O motor só é remontado quando `correction` ou o idioma do OCR mudam
+/// (compilar regex e carregar o dicionário a cada linha seria lento).
+pub fn engine_for(app_config: &AppConfig) -> Arc<CorrectionEngine> {
+    let language = app_config.ocr_language();
+    let mut cache = ENGINE_CACHE.lock().unwrap();
+
+    if let Some((config, cached_language, engine)) = cache.as_ref() {
+        if *config == app_config.correction && *cached_language == language {
+            return engine.clone();
+        }
+    }
+
+    let engine = Arc::new(CorrectionEngine::new(&app_config.correction, &language));
+    *cache = Some((app_config.correction.clone(), language, engine.clone()));
+    engine
+}
+
+impl CorrectionEngine {
+    /// Monta o motor a partir do config
+    ///
+    /// Regras inválidas e dicionário ausente geram aviso no log, mas não
+    /// impedem o funcionamento (o resto das correções continua valendo).
+    pub fn new(config: &CorrectionConfig, language: &str) -> Self {
+        let mut rules: Vec<CompiledRule> = config.rules.iter().filter_map(compile_rule).collect();
+
+        if !config.active_game.is_empty() {
+            match config.game_rules.get(&config.active_game) {
+                Some(game_rules) => {
+                    info!(
+                        "🎮 {} regras de correção do jogo '{}'",
+                        game_rules.len(),
+                        config.active_game
+                    );
+                    rules.extend(game_rules.iter().filter_map(compile_rule));
+                }
+                None => warn!(
+                    "⚠️  Nenhuma regra de correção para o jogo '{}'",
+                    config.active_game
+                ),
+            }
+        }
+
+        let dictionary = if config.dictionary_path.is_empty() {
+            None
+        } else {
+            match load_dictionary(&config.dictionary_path) {
+                Ok(words) => {
+                    info!("📖 Dicionário de correção: {} palavras", words.len());
+                    Some(words)
+                }
+                Err(e) => {
+                    warn!("⚠️  Dicionário de correção desativado: {}", e);
+                    None
+                }
+            }
+        };
+
+        CorrectionEngine {
+            rules,
+            charset: charset_for(&config.charset, language),
+            extra_chars: config.extra_chars.chars().collect(),
+            dictionary,
+        }
+    }
+
+    /// Corrige um texto reconhecido pelo OCR
+    pub fn clean(&self, text: &str) -> String {
+        let mut cleaned = text.to_string();
+
+        // 1. Regras (globais e do jogo)
+        for rule in &self.rules {
+            cleaned = match rule {
+                CompiledRule::Literal {
+                    pattern,
+                    replacement,
+                } => cleaned.replace(pattern.as_str(), replacement),
+                CompiledRule::Regex { regex, replacement } => regex
+                    .replace_all(&cleaned, replacement.as_str())
+                    .into_owned(),
+            };
+        }
+
+        // 2. Filtro de caracteres pelo idioma
+        cleaned = cleaned.chars().filter(|&c| self.keep_char(c)).collect();
+
+        // 3. Espaços duplicados e espaços falsos entre CJK
+        cleaned = cleaned.split_whitespace().collect::<Vec<&str>>().join(" ");
+        cleaned = cjk::remove_cjk_spaces(&cleaned);
+
+        // 4. Corretor por dicionário
+        if let Some(dictionary) = &self.dictionary {
+            cleaned = cleaned
+                .split(' ')
+                .map(|token| repair_token(token, dictionary))
+                .collect::<Vec<String>>()
+                .join(" ");
+        }
+
+        cleaned.trim().to_string()
+    }
+
+    /// Decide se um caractere fica no texto
+    fn keep_char(&self, c: char) -> bool {
+        if self.extra_chars.contains(&c) || c == ' ' {
+            return true;
+        }
+
+        // Lixo que nunca é texto de jogo
+        if c.is_control()
+            || ('\u{E000}'..='\u{F8FF}').contains(&c) // Uso privado
+            || ('\u{2500}'..='\u{259F}').contains(&c) // Desenho de caixas e blocos
+            || c == '\u{FFFD}'
+        {
+            return false;
+        }
+
+        // Pontuação, números e símbolos são mantidos
+        if !c.is_alphabetic() {
+            return true;
+        }
+
+        // Letras: só as das escritas do idioma (o latim é aceito em
+        // todos, já que nomes e siglas aparecem em qualquer jogo)
+        match self.charset {
+            Charset::Any => true,
+            Charset::Latin => is_latin(c),
+            Charset::Cyrillic => is_latin(c) || ('\u{0400}'..='\u{052F}').contains(&c),
+            Charset::Greek => is_latin(c) || ('\u{0370}'..='\u{03FF}').contains(&c),
+            Charset::Cjk => is_latin(c) || cjk::is_cjk(c),
+        }
+    }
+}
+
+/// Compila uma regra do config (regra inválida = aviso e ignora)
+fn compile_rule(rule: &CorrectionRule) -> Option<CompiledRule> {
+    if rule.pattern.is_empty() {
+        return None;
+    }
+
+    if !rule.regex {
+        return Some(CompiledRule::Literal {
+            pattern: rule.pattern.clone(),
+            replacement: rule.replacement.clone(),
+        });
+    }
+
+    match Regex::new(&rule.pattern) {
+        Ok(regex) => Some(CompiledRule::Regex {
+            regex,
+            replacement: rule.replacement.clone(),
+        }),
+        Err(e) => {
+            warn!("⚠️  Regra de correção inválida '{}': {}", rule.pattern, e);
+            None
+        }
+    }
+}
+
+/// Escolhe as escritas aceitas (a partir do config ou do idioma do OCR)
+fn charset_for(charset: &str, language: &str) -> Charset {
+    match charset.to_lowercase().as_str() {
+        "latin" => Charset::Latin,
+        "cyrillic" => Charset::Cyrillic,
+        "greek" => Charset::Greek,
+        "cjk" => Charset::Cjk,
+        "any" => Charset::Any,
+        _ => {
+            let tag = ocr::normalize_language_tag(language).unwrap_or_default();
+            match tag.split('-').next().unwrap_or("") {
+                "ru" | "uk" | "bg" | "sr" | "be" => Charset::Cyrillic,
+                "el" => Charset::Greek,
+                "ja" | "zh" | "ko" => Charset::Cjk,
+                // "auto" ou idioma desconhecido: não filtra letras
+                "" => Charset::Any,
+                _ => Charset::Latin,
+            }
+        }
+    }
+}
+
+/// Letras latinas (ASCII + acentuadas)
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic()
+        || ('\u{00C0}'..='\u{024F}').contains(&c)
+        || ('\u{1E00}'..='\u{1EFF}').contains(&c)
+}
+
+// ============================================================================
+// CORRETOR POR DICIONÁRIO
+// ============================================================================
+
+/// Carrega a lista de palavras (uma por linha, comparação sem maiúsculas)
+fn load_dictionary(path: &str) -> Result<HashSet<String>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Falha ao ler dicionário: {}", path))?;
+
+    Ok(contents
+        .lines()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// Tenta consertar uma palavra trocando confusões típicas do OCR
+///
+/// Só troca se a palavra original NÃO está no dicionário e a trocada está.
+/// Pontuação em volta da palavra ("rnore," → "more,") é preservada.
+fn repair_token(token: &str, dictionary: &HashSet<String>) -> String {
+    let start = token
+        .find(|c: char| c.is_alphanumeric())
+        .unwrap_or(token.len());
+    let end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, |c| c.len_utf8()))
+        .unwrap_or(start);
+
+    if start >= end {
+        return token.to_string();
+    }
+
+    let word = &token[start..end];
+
+    // Números puros, palavras já corretas e CJK ficam como estão
+    if !word.chars().any(|c| c.is_alphabetic())
+        || cjk::contains_cjk(word)
+        || dictionary.contains(&word.to_lowercase())
+    {
+        return token.to_string();
+    }
+
+    let uppercase = word.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && word
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| c.is_uppercase());
+
+    for (wrong, right) in OCR_CONFUSIONS {
+        // Em palavra toda maiúscula a confusão também é maiúscula ("RN" → "M")
+        let (wrong, right) = if uppercase {
+            (wrong.to_uppercase(), right.to_uppercase())
+        } else {
+            (wrong.to_string(), right.to_string())
+        };
+
+        // Testa cada ocorrência isolada e depois todas de uma vez
+        let mut candidates: Vec<String> = word
+            .match_indices(wrong.as_str())
+            .map(|(i, _)| format!("{}{}{}", &word[..i], right, &word[i + wrong.len()..]))
+            .collect();
+        if candidates.len() > 1 {
+            candidates.push(word.replace(wrong.as_str(), &right));
+        }
+
+        if let Some(fixed) = candidates
+            .into_iter()
+            .find(|c| dictionary.contains(&c.to_lowercase()))
+        {
+            trace!("📖 Correção: \"{}\" → \"{}\"", word, fixed);
+            return format!("{}{}{}", &token[..start], fixed, &token[end..]);
+        }
+    }
+
+    token.to_string()
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn rule(pattern: &str, replacement: &str, regex: bool) -> CorrectionRule {
+        CorrectionRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            regex,
+        }
+    }
+
+    fn dictionary(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn rules_run_in_order_global_then_active_game() {
+        let mut config = CorrectionConfig::default();
+        config.rules.push(rule("Hp", "HP", false));
+        config.game_rules = HashMap::from([
+            ("rpg".to_string(), vec![rule(r"(\d+) ?HP", "${1} PV", true)]),
+            ("other".to_string(), vec![rule("left", "restantes", false)]),
+        ]);
+        config.active_game = "rpg".to_string();
+
+        let engine = CorrectionEngine::new(&config, "en");
+
+        // A regra do jogo só casa depois da global ter trocado "Hp" → "HP"
+        assert_eq!(engine.clean("10 Hp left"), "10 PV left");
+        // Regras padrão: "|" isolado vira "I", mas "a|b", "@" e "|<" → "K" não
+        assert_eq!(
+            engine.clean("| think |t's a|b @ the |<ey"),
+            "I think It's a|b @ the Key"
+        );
+        // Regra inválida é ignorada sem derrubar as outras
+        config.rules.push(rule("(", "x", true));
+        assert_eq!(CorrectionEngine::new(&config, "en").clean("10 Hp"), "10 PV");
+    }
+
+    #[test]
+    fn confusions_only_apply_when_they_make_a_dictionary_word() {
+        let words = dictionary(&["more", "modern", "corner", "door", "open"]);
+
+        assert_eq!(repair_token("rnore", &words), "more");
+        assert_eq!(repair_token("rnodern", &words), "modern");
+        assert_eq!(repair_token("0pen", &words), "open");
+        // Palavra já correta não é mexida ("corner" tem "rn" de verdade)
+        assert_eq!(repair_token("corner", &words), "corner");
+        // Troca que não vira palavra do dicionário é descartada
+        assert_eq!(repair_token("barn", &words), "barn");
+        assert_eq!(repair_token("x0x", &words), "x0x");
+        assert_eq!(repair_token("2024", &words), "2024");
+    }
+
+    #[test]
+    fn repair_keeps_case_punctuation_and_cjk() {
+        let words = dictionary(&["more", "door", "modern"]);
+
+        // Tudo maiúsculo: a troca também sai maiúscula
+        assert_eq!(repair_token("D00R", &words), "DOOR");
+        assert_eq!(repair_token("RNODERN", &words), "MODERN");
+        assert_eq!(repair_token("RNORE!", &words), "MORE!");
+        assert_eq!(repair_token("DOOR", &words), "DOOR");
+        // Pontuação em volta fica no lugar
+        assert_eq!(repair_token("\"rnore,\"", &words), "\"more,\"");
+        assert_eq!(repair_token("(d00r)...", &words), "(door)...");
+        // CJK passa direto
+        assert_eq!(repair_token("こんにちは", &words), "こんにちは");
+        assert_eq!(repair_token("「rnore」", &words), "「more」");
+    }
+
+    #[test]
+    fn engine_filters_charset_and_uses_dictionary_file() {
+        let path = std::env::temp_dir().join(format!(
+            "game-translator-dictionary-{}.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "More\ndoor\n").unwrap();
+
+        let config = CorrectionConfig {
+            dictionary_path: path.to_string_lossy().to_string(),
+            ..CorrectionConfig::default()
+        };
+        let engine = CorrectionEngine::new(&config, "en");
+        assert_eq!(
+            engine.clean("Open the  d00r, rnore\u{FFFD}!"),
+            "Open the door, more!"
+        );
+        // Latim: letras cirílicas somem, pontuação e números ficam
+        assert_eq!(engine.clean("HP 10/10 Привет!"), "HP 10/10 !");
+
+        let japanese = CorrectionEngine::new(&config, "ja");
+        assert_eq!(
+            japanese.clean("こ ん に ち は 、 rnore"),
+            "こんにちは、 more"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,303 @@
+// game-translator/src/debug_dump.rs
+
+// ============================================================================
+// MÓDULO DEBUG_DUMP - Imagens de debug por etapa do pré-processamento
+// ============================================================================
+//
+// O save_debug_image grava só o resultado final, sempre no mesmo
+// arquivo. Com `save_debug_stages` cada captura ganha uma pasta com a
+// imagem depois de cada filtro, e as caixas do OCR desenhadas por cima
+// de todas (dá para ver em que etapa o texto se perdeu):
+//
+//   debug_dumps/20261018_153012_0007_legenda/
+//   ├── 00_raw.png
+//   ├── 01_upscale.png
+//   ├── 02_grayscale.png
+//   ├── 03_threshold.png
+//   ├── ...
+//   └── ocr.txt               (texto e caixas de cada linha)
+//
+// Linhas do OCR em vermelho, palavras em verde. As caixas estão nas
+// coordenadas da imagem final: nas etapas antes do upscale elas são
+// reduzidas na mesma proporção.
+//
+// Só as `keep` capturas mais recentes ficam na pasta (as pastas são
+// nomeadas por data, então a ordem alfabética é a cronológica): dá para
+// deixar ligado a sessão inteira. Outras pastas no mesmo lugar não são
+// tocadas.
+//
+// ============================================================================
+
+use crate::config::DebugDumpConfig;
+use crate::history;
+use crate::ocr::OcrResultWithPositions;
+use crate::screenshot::DebugStage;
+use anyhow::{Context, Result};
+use image::{DynamicImage, Rgb, RgbImage};
+use regex::Regex;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// Cor das caixas de linha (vermelho)
+const LINE_COLOR: Rgb<u8> = Rgb([255, 40, 40]);
+
+/// Cor das caixas de palavra (verde)
+const WORD_COLOR: Rgb<u8> = Rgb([40, 220, 40]);
+
+/// Número da captura na sessão (desempata pastas do mesmo segundo)
+static DUMP_SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// Nome das pastas gravadas aqui: "<data>_<hora>_<sequência>_<modo>"
+fn dump_name_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^\d{8}_\d{6}_\d{4,}_\w+$").unwrap())
+}
+
+/// Grava as etapas de uma captura com as caixas do OCR
+///
+/// # Argumentos
+/// * `mode` - Vai no nome da pasta ("regiao", "tela_cheia", "legenda")
+/// * `stages` - Etapas do pré-processamento (a última foi para o OCR)
+/// * `ocr` - Resultado do OCR, em coordenadas da última etapa
+///
+/// # Retorna
+/// * Caminho da pasta criada
+pub fn save_stages(
+    config: &DebugDumpConfig,
+    mode: &str,
+    stages: &[DebugStage],
+    ocr: &OcrResultWithPositions,
+) -> Result<PathBuf> {
+    let Some(last) = stages.last() else {
+        anyhow::bail!("Nenhuma etapa para gravar");
+    };
+    let (ocr_width, ocr_height) = (last.image.width().max(1), last.image.height().max(1));
+
+    let folder = Path::new(&config.folder);
+    let sequence = DUMP_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let target = folder.join(format!(
+        "{}_{:04}_{}",
+        history::file_stamp(SystemTime::now()),
+        sequence,
+        mode
+    ));
+    fs::create_dir_all(&target)
+        .with_context(|| format!("Falha ao criar pasta {}", target.display()))?;
+
+    for (index, stage) in stages.iter().enumerate() {
+        let scale_x = stage.image.width() as f64 / ocr_width as f64;
+        let scale_y = stage.image.height() as f64 / ocr_height as f64;
+        let file = format!("{:02}_{}.png", index, stage.name);
+
+        draw_boxes(&stage.image, ocr, scale_x, scale_y)
+            .save(target.join(&file))
+            .with_context(|| format!("Falha ao salvar {}", file))?;
+    }
+
+    fs::write(target.join("ocr.txt"), describe_ocr(ocr)).context("Falha ao escrever ocr.txt")?;
+
+    let removed = prune(folder, config.keep)?;
+    if removed > 0 {
+        trace!("🧹 {} pasta(s) de debug antigas apagadas", removed);
+    }
+
+    Ok(target)
+}
+
+/// Cópia RGB da imagem com as caixas das linhas e palavras do OCR
+fn draw_boxes(
+    image: &DynamicImage,
+    ocr: &OcrResultWithPositions,
+    scale_x: f64,
+    scale_y: f64,
+) -> RgbImage {
+    let mut canvas = image.to_rgb8();
+
+    for line in &ocr.lines {
+        for word in &line.words {
+            draw_rect(
+                &mut canvas,
+                (word.x * scale_x, word.y * scale_y),
+                (word.width * scale_x, word.height * scale_y),
+                WORD_COLOR,
+            );
+        }
+        draw_rect(
+            &mut canvas,
+            (line.x * scale_x, line.y * scale_y),
+            (line.width * scale_x, line.height * scale_y),
+            LINE_COLOR,
+        );
+    }
+
+    canvas
+}
+
+/// Contorno de 1 pixel de um retângulo (cortado nas bordas da imagem)
+fn draw_rect(
+    canvas: &mut RgbImage,
+    (x, y): (f64, f64),
+    (width, height): (f64, f64),
+    color: Rgb<u8>,
+) {
+    let (canvas_width, canvas_height) = (canvas.width() as i64, canvas.height() as i64);
+    if canvas_width == 0 || canvas_height == 0 {
+        return;
+    }
+
+    let left = (x.round() as i64).clamp(0, canvas_width - 1);
+    let top = (y.round() as i64).clamp(0, canvas_height - 1);
+    let right = ((x + width).round() as i64).clamp(0, canvas_width - 1);
+    let bottom = ((y + height).round() as i64).clamp(0, canvas_height - 1);
+
+    for px in left..=right {
+        canvas.put_pixel(px as u32, top as u32, color);
+        canvas.put_pixel(px as u32, bottom as u32, color);
+    }
+    for py in top..=bottom {
+        canvas.put_pixel(left as u32, py as u32, color);
+        canvas.put_pixel(right as u32, py as u32, color);
+    }
+}
+
+/// Texto do OCR linha a linha, com caixa e confiança
+fn describe_ocr(ocr: &OcrResultWithPositions) -> String {
+    let mut text = format!("{}\n\n", ocr.full_text);
+
+    for (index, line) in ocr.lines.iter().enumerate() {
+        let confidence = line
+            .confidence
+            .map_or("-".to_string(), |c| format!("{:.2}", c));
+        let _ = writeln!(
+            text,
+            "{:02} ({:.0}, {:.0}) {:.0}x{:.0} conf={}{} {}",
+            index + 1,
+            line.x,
+            line.y,
+            line.width,
+            line.height,
+            confidence,
+            if line.vertical { " vertical" } else { "" },
+            line.text
+        );
+    }
+
+    text
+}
+
+/// Apaga as pastas mais antigas, deixando só as `keep` mais recentes
+///
+/// Só mexe em pastas com o nome das gravadas aqui: a pasta configurada
+/// pode ser compartilhada (ou até ".") e o resto dela não é nosso.
+///
+/// # Retorna
+/// * Quantas pastas foram apagadas
+fn prune(folder: &Path, keep: usize) -> Result<usize> {
+    let mut dumps: Vec<PathBuf> = fs::read_dir(folder)
+        .with_context(|| format!("Falha ao ler pasta {}", folder.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| dump_name_regex().is_match(name))
+        })
+        .collect();
+
+    // Sempre fica pelo menos a que acabou de ser gravada
+    let keep = keep.max(1);
+    if dumps.len() <= keep {
+        return Ok(0);
+    }
+
+    dumps.sort();
+    let excess = dumps.len() - keep;
+    for old in &dumps[..excess] {
+        // Outra gravação (em outra thread) pode ter apagado antes
+        match fs::remove_dir_all(old) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(e).with_context(|| format!("Falha ao apagar {}", old.display()));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(excess)
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::DetectedText;
+
+    #[test]
+    fn saves_every_stage_with_scaled_boxes_and_prunes_old_dumps() {
+        let folder =
+            std::env::temp_dir().join(format!("game-translator-dumps-{}", std::process::id()));
+        let config = DebugDumpConfig {
+            folder: folder.to_string_lossy().to_string(),
+            keep: 2,
+        };
+
+        // Upscale 2x: a caixa (10, 10) 20x10 do OCR fica em (5, 5) no raw
+        let stages = vec![
+            DebugStage {
+                name: "raw",
+                image: DynamicImage::new_rgb8(40, 30),
+            },
+            DebugStage {
+                name: "upscale",
+                image: DynamicImage::new_rgb8(80, 60),
+            },
+        ];
+        let ocr = OcrResultWithPositions {
+            full_text: "Hello".to_string(),
+            lines: vec![DetectedText {
+                text: "Hello".to_string(),
+                x: 10.0,
+                y: 10.0,
+                width: 20.0,
+                height: 10.0,
+                words: Vec::new(),
+                confidence: Some(0.9),
+                vertical: false,
+            }],
+        };
+
+        let first = save_stages(&config, "legenda", &stages, &ocr).unwrap();
+        let raw = image::open(first.join("00_raw.png")).unwrap().to_rgb8();
+        assert_eq!(*raw.get_pixel(5, 5), LINE_COLOR);
+        assert_eq!(*raw.get_pixel(15, 10), LINE_COLOR);
+        assert_eq!(*raw.get_pixel(10, 7), Rgb([0, 0, 0]));
+        let upscaled = image::open(first.join("01_upscale.png")).unwrap().to_rgb8();
+        assert_eq!(*upscaled.get_pixel(10, 10), LINE_COLOR);
+        assert!(fs::read_to_string(first.join("ocr.txt"))
+            .unwrap()
+            .contains("conf=0.90 Hello"));
+
+        // Pastas que não são dumps (pasta compartilhada) ficam intactas,
+        // mesmo com nome que vem antes na ordem alfabética
+        let foreign = ["00000000_src", "bug_reports", "target"].map(|name| folder.join(name));
+        for dir in &foreign {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("keep.txt"), "not ours").unwrap();
+        }
+
+        save_stages(&config, "legenda", &stages, &ocr).unwrap();
+        save_stages(&config, "legenda", &stages, &ocr).unwrap();
+        assert!(!first.exists());
+        assert!(foreign.iter().all(|dir| dir.join("keep.txt").exists()));
+        assert_eq!(fs::read_dir(&folder).unwrap().count(), 2 + foreign.len());
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+// game-translator/src/filters.rs
+
+// ============================================================================
+// MÓDULO FILTERS - Filtros rápidos sobre buffers de bytes
+// ============================================================================
+//
+// Erosão, dilatação e Sobel rodam a cada frame no modo legenda, muitas
+// vezes depois de um upscale 3x (1200x100 → 3600x300 = 1 milhão de
+// pixels). As versões ingênuas (get_pixel por vizinho, O(w·h·r²)) eram
+// a parte mais cara do pré-processamento.
+//
+// Aqui tudo trabalha em `&[u8]` (um byte por pixel, linha após linha):
+//
+// - Morfologia (mínimo/máximo numa janela quadrada) é separável:
+//   primeiro na horizontal, depois na vertical. Cada passada usa o
+//   algoritmo de van Herk / Gil-Werman: custo constante por pixel,
+//   QUALQUER que seja o raio (3 comparações por pixel por passada).
+// - Sobel lê as três linhas vizinhas direto do slice, com aritmética
+//   inteira, e compara o quadrado da magnitude (sem raiz quadrada).
+//
+// Com a feature "parallel" as linhas são processadas em paralelo (rayon).
+//
+// Os resultados são idênticos aos das versões ingênuas (filters/naive.rs,
+// ver testes).
+//
+// Este arquivo não depende do resto do crate: o benchmark
+// (benches/filters.rs) inclui ele direto, junto com o naive.rs para
+// comparar antes/depois.
+//
+// ============================================================================
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Operação da morfologia
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Morphology {
+    /// Mínimo da janela (erosão)
+    Erode,
+    /// Máximo da janela (dilatação)
+    Dilate,
+}
+
+impl Morphology {
+    fn apply(self, a: u8, b: u8) -> u8 {
+        match self {
+            Morphology::Erode => a.min(b),
+            Morphology::Dilate => a.max(b),
+        }
+    }
+
+    /// Valor que não altera o resultado (usado fora da imagem)
+    fn identity(self) -> u8 {
+        match self {
+            Morphology::Erode => u8::MAX,
+            Morphology::Dilate => u8::MIN,
+        }
+    }
+}
+
+/// Mínimo/máximo numa janela quadrada de lado `2 * radius + 1`
+///
+/// A janela é cortada nas bordas da imagem (pixels de fora não contam).
+///
+/// # Argumentos
+/// * `pixels` - Um byte por pixel, `width * height` bytes
+pub fn morphology(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    radius: usize,
+    op: Morphology,
+) -> Vec<u8> {
+    assert_eq!(pixels.len(), width * height, "buffer com tamanho errado");
+
+    if radius == 0 || pixels.is_empty() {
+        return pixels.to_vec();
+    }
+
+    let horizontal = morphology_rows(pixels, width, radius, op);
+    morphology_columns(&horizontal, width, height, radius, op)
+}
+
+/// Passada horizontal: van Herk / Gil-Werman em cada linha
+fn morphology_rows(pixels: &[u8], width: usize, radius: usize, op: Morphology) -> Vec<u8> {
+    let mut result = vec![0u8; pixels.len()];
+    let window = 2 * radius + 1;
+
+    for_each_row(&mut result, width, |y, out| {
+        let row = &pixels[y * width..(y + 1) * width];
+
+        // Linha com `radius` pixels neutros de cada lado, completada até
+        // um múltiplo da janela
+        let padded_len = (width + 2 * radius).div_ceil(window) * window;
+        let mut padded = vec![op.identity(); padded_len];
+        padded[radius..radius + width].copy_from_slice(row);
+
+        // g = acumulado do começo de cada bloco, h = do fim de cada bloco
+        let mut g = padded.clone();
+        let mut h = padded.clone();
+        for block in 0..padded_len / window {
+            let start = block * window;
+            for i in start + 1..start + window {
+                g[i] = op.apply(g[i - 1], padded[i]);
+            }
+            for i in (start..start + window - 1).rev() {
+                h[i] = op.apply(h[i + 1], padded[i]);
+            }
+        }
+
+        // Janela [i, i + window) = h[i] (até o fim do bloco) + g[fim] (do
+        // começo do bloco seguinte)
+        for (x, value) in out.iter_mut().enumerate() {
+            *value = op.apply(h[x], g[x + window - 1]);
+        }
+    });
+
+    result
+}
+
+/// Passada vertical: o mesmo algoritmo, com linhas inteiras como unidade
+///
+/// Cada passo opera sobre uma linha inteira (elemento a elemento), o que
+/// percorre a memória em ordem e vetoriza bem.
+fn morphology_columns(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    radius: usize,
+    op: Morphology,
+) -> Vec<u8> {
+    let window = 2 * radius + 1;
+    let padded_rows = (height + 2 * radius).div_ceil(window) * window;
+
+    // Linha `r` do buffer com bordas (None = fora da imagem)
+    let source_row = |r: usize| -> Option<&[u8]> {
+        r.checked_sub(radius)
+            .filter(|&y| y < height)
+            .map(|y| &pixels[y * width..(y + 1) * width])
+    };
+
+    let mut g = vec![op.identity(); padded_rows * width];
+    let mut h = vec![op.identity(); padded_rows * width];
+
+    // Cada bloco de `window` linhas é independente
+    for_each_row(&mut g, width * window, |block, g_block| {
+        for i in 0..window {
+            let r = block * window + i;
+            let (before, current) = g_block.split_at_mut(i * width);
+            let current = &mut current[..width];
+
+            if let Some(row) = source_row(r) {
+                current.copy_from_slice(row);
+            }
+            if i > 0 {
+                let previous = &before[(i - 1) * width..];
+                for (value, &prev) in current.iter_mut().zip(previous) {
+                    *value = op.apply(*value, prev);
+                }
+            }
+        }
+    });
+
+    for_each_row(&mut h, width * window, |block, h_block| {
+        for i in (0..window).rev() {
+            let r = block * window + i;
+            let (current, after) = h_block.split_at_mut((i + 1) * width);
+            let current = &mut current[i * width..];
+
+            if let Some(row) = source_row(r) {
+                current.copy_from_slice(row);
+            }
+            if i + 1 < window {
+                let next = &after[..width];
+                for (value, &next) in current.iter_mut().zip(next) {
+                    *value = op.apply(*value, next);
+                }
+            }
+        }
+    });
+
+    let mut result = vec![0u8; width * height];
+    for_each_row(&mut result, width, |y, out| {
+        let h_row = &h[y * width..(y + 1) * width];
+        let g_row = &g[(y + window - 1) * width..(y + window) * width];
+        for ((value, &a), &b) in out.iter_mut().zip(h_row).zip(g_row) {
+            *value = op.apply(a, b);
+        }
+    });
+
+    result
+}
+
+/// Bordas (Sobel 3x3) binarizadas: magnitude acima de `threshold` = 255
+///
+/// A moldura de 1 pixel na borda da imagem fica 0 (sem vizinhos).
+/// A magnitude é limitada a 255 como no filtro original, então
+/// `magnitude > threshold` equivale a `gx² + gy² >= (threshold + 1)²`.
+pub fn sobel_threshold(pixels: &[u8], width: usize, height: usize, threshold: u8) -> Vec<u8> {
+    assert_eq!(pixels.len(), width * height, "buffer com tamanho errado");
+
+    let mut result = vec![0u8; width * height];
+    if width < 3 || height < 3 || threshold == u8::MAX {
+        return result;
+    }
+
+    let limit = (threshold as i32 + 1) * (threshold as i32 + 1);
+
+    for_each_row(&mut result, width, |y, out| {
+        if y == 0 || y == height - 1 {
+            return;
+        }
+
+        let above = &pixels[(y - 1) * width..y * width];
+        let row = &pixels[y * width..(y + 1) * width];
+        let below = &pixels[(y + 1) * width..(y + 2) * width];
+
+        // Janelas 3x3 deslizando pelas três linhas (sem checagem de índice)
+        let windows = above.windows(3).zip(row.windows(3)).zip(below.windows(3));
+        for (value, ((a, r), b)) in out[1..width - 1].iter_mut().zip(windows) {
+            let [a0, a1, a2] = [a[0] as i32, a[1] as i32, a[2] as i32];
+            let [b0, b1, b2] = [b[0] as i32, b[1] as i32, b[2] as i32];
+
+            let gx = (a2 + 2 * r[2] as i32 + b2) - (a0 + 2 * r[0] as i32 + b0);
+            let gy = (b0 + 2 * b1 + b2) - (a0 + 2 * a1 + a2);
+
+            if gx * gx + gy * gy >= limit {
+                *value = 255;
+            }
+        }
+    });
+
+    result
+}
+
+/// Roda `f(índice, pedaço)` para cada pedaço de `chunk` bytes do buffer
+/// (em paralelo com a feature "parallel")
+fn for_each_row(buffer: &mut [u8], chunk: usize, f: impl Fn(usize, &mut [u8]) + Send + Sync) {
+    #[cfg(feature = "parallel")]
+    buffer
+        .par_chunks_mut(chunk)
+        .enumerate()
+        .for_each(|(index, part)| f(index, part));
+
+    #[cfg(not(feature = "parallel"))]
+    buffer
+        .chunks_mut(chunk)
+        .enumerate()
+        .for_each(|(index, part)| f(index, part));
+}
+
+/// Versões ingênuas (a implementação antiga): referência dos testes e
+/// grupo de comparação do benchmark (que também compila com cfg(test)).
+/// Caminho explícito para resolver igual quando o benchmark inclui este
+/// arquivo via #[path]
+#[cfg(test)]
+#[path = "filters/naive.rs"]
+pub mod naive;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Imagem pseudoaleatória determinística (LCG), com blocos de texto
+    /// e ruído para exercitar bordas e janelas maiores que a imagem
+    fn sample_image(width: usize, height: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..width * height)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn morphology_matches_naive() {
+        for &(width, height) in &[(1, 1), (2, 7), (13, 5), (40, 17), (64, 64)] {
+            let pixels = sample_image(width, height, (width * 31 + height) as u32);
+
+            for radius in 0..=6 {
+                for op in [Morphology::Erode, Morphology::Dilate] {
+                    assert_eq!(
+                        morphology(&pixels, width, height, radius, op),
+                        naive::morphology(&pixels, width, height, radius, op),
+                        "{:?} raio {} em {}x{}",
+                        op,
+                        radius,
+                        width,
+                        height
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sobel_matches_naive() {
+        for &(width, height) in &[(1, 1), (2, 9), (3, 3), (37, 11), (64, 48)] {
+            let pixels = sample_image(width, height, (width + height * 7) as u32);
+
+            for threshold in [0, 1, 30, 80, 128, 254, 255] {
+                assert_eq!(
+                    sobel_threshold(&pixels, width, height, threshold),
+                    naive::sobel_threshold(&pixels, width, height, threshold),
+                    "threshold {} em {}x{}",
+                    threshold,
+                    width,
+                    height
+                );
+            }
+        }
+    }
+}
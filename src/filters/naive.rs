@@ -0,0 +1,68 @@
+// game-translator/src/filters/naive.rs
+
+// ============================================================================
+// FILTROS INGÊNUOS - A implementação antiga, pixel a pixel
+// ============================================================================
+//
+// Referência para os testes de src/filters.rs (os resultados têm que ser
+// idênticos) e grupo de comparação do benchmark (benches/filters.rs).
+// Não é usada pelo programa.
+//
+// ============================================================================
+
+use super::Morphology;
+
+/// Mínimo/máximo percorrendo a janela inteira de cada pixel: O(w·h·r²)
+pub fn morphology(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    radius: usize,
+    op: Morphology,
+) -> Vec<u8> {
+    let radius = radius as i64;
+    let mut result = vec![0u8; pixels.len()];
+
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let mut value = match op {
+                Morphology::Erode => u8::MAX,
+                Morphology::Dilate => u8::MIN,
+            };
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if nx >= 0 && nx < width as i64 && ny >= 0 && ny < height as i64 {
+                        let pixel = pixels[(ny * width as i64 + nx) as usize];
+                        value = match op {
+                            Morphology::Erode => value.min(pixel),
+                            Morphology::Dilate => value.max(pixel),
+                        };
+                    }
+                }
+            }
+            result[(y * width as i64 + x) as usize] = value;
+        }
+    }
+
+    result
+}
+
+/// Sobel em float com raiz quadrada, como o antigo
+pub fn sobel_threshold(pixels: &[u8], width: usize, height: usize, threshold: u8) -> Vec<u8> {
+    let mut result = vec![0u8; pixels.len()];
+
+    for y in 1..height.saturating_sub(1) {
+        for x in 1..width.saturating_sub(1) {
+            let p = |dx: i32, dy: i32| -> f32 {
+                pixels[(y as i32 + dy) as usize * width + (x as i32 + dx) as usize] as f32
+            };
+            let gx = -p(-1, -1) - 2.0 * p(-1, 0) - p(-1, 1) + p(1, -1) + 2.0 * p(1, 0) + p(1, 1);
+            let gy = -p(-1, -1) - 2.0 * p(0, -1) - p(1, -1) + p(-1, 1) + 2.0 * p(0, 1) + p(1, 1);
+            let magnitude = (gx * gx + gy * gy).sqrt().min(255.0) as u8;
+            result[y * width + x] = if magnitude > threshold { 255 } else { 0 };
+        }
+    }
+
+    result
+}
@@ -0,0 +1,488 @@
+// game-translator/src/game_window.rs
+
+// ============================================================================
+// MÓDULO GAME_WINDOW - Captura presa à janela do jogo
+// ============================================================================
+//
+// Regiões em coordenadas fixas de tela quebram quando um jogo em janela
+// é movido ou muda de resolução. Com `game_window.enabled`, as regiões
+// do config ficam relativas à área cliente da janela do jogo (sem borda
+// e barra de título) e são convertidas para a tela a cada captura:
+//
+//   janela em (300, 200), região em (40, 500) → captura em (340, 700)
+//
+// Se a área cliente mudar de tamanho, a região é escalada na mesma
+// proporção (o tamanho de quando ela foi selecionada fica no config).
+//
+// A janela é achada pelo nome do executável e/ou por um trecho do
+// título. Janela minimizada ou fechada vira erro com mensagem clara
+// (em vez de capturar a área errada da tela).
+//
+// Só há implementação para Windows; nos outros sistemas a lista de
+// janelas fica vazia (o replay desliga a janela, ver main.rs).
+//
+// ============================================================================
+
+use crate::config::{GameWindowConfig, RegionConfig};
+use crate::screenshot::{self, MonitorInfo};
+use anyhow::Result;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Área cliente de uma janela (desktop virtual, pixels físicos)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClientArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Janela de nível superior visível
+#[derive(Debug, Clone)]
+pub struct WindowInfo {
+    pub title: String,
+    /// Nome do executável (ex: "game.exe")
+    pub process: String,
+    pub minimized: bool,
+    pub client: ClientArea,
+}
+
+impl WindowInfo {
+    /// A janela bate com o config? (campos vazios não filtram)
+    fn matches(&self, config: &GameWindowConfig) -> bool {
+        let process = config.process_name.trim();
+        let title = config.window_title.trim().to_lowercase();
+
+        (process.is_empty() || self.process.eq_ignore_ascii_case(process))
+            && (title.is_empty() || self.title.to_lowercase().contains(&title))
+    }
+}
+
+/// Região pronta para capturar: monitor + x/y relativos a ele
+#[derive(Debug, Clone, Copy)]
+pub struct CaptureTarget {
+    /// Monitor da região (None = monitor não detectado, ex: sem tela)
+    pub monitor: Option<MonitorInfo>,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Área onde o overlay se acomoda: a janela do jogo, se presa a uma,
+    /// senão o monitor (None = monitor não detectado)
+    pub bounds: Option<ClientArea>,
+}
+
+impl CaptureTarget {
+    /// Índice do monitor para a CaptureSource
+    pub fn monitor_index(&self) -> Option<u32> {
+        self.monitor.map(|m| m.index)
+    }
+
+    /// Canto da região no desktop virtual
+    pub fn virtual_origin(&self) -> (i32, i32) {
+        match self.monitor {
+            Some(monitor) => monitor.to_virtual(self.x, self.y),
+            None => (self.x as i32, self.y as i32),
+        }
+    }
+}
+
+// ============================================================================
+// RESOLUÇÃO DAS REGIÕES
+// ============================================================================
+
+/// Converte uma região do config para a tela
+///
+/// Sem janela presa, usa o monitor da região. Com janela presa, soma a
+/// origem da área cliente (e escala se a janela mudou de tamanho).
+pub fn resolve_region(window: &GameWindowConfig, region: &RegionConfig) -> Result<CaptureTarget> {
+    if !window.enabled {
+        let monitor = screenshot::monitor(region.monitor).ok();
+        return Ok(CaptureTarget {
+            monitor,
+            x: region.x,
+            y: region.y,
+            width: region.width,
+            height: region.height,
+            bounds: monitor.map(monitor_area),
+        });
+    }
+
+    let client = find_client_area(window)?;
+    let (x, y, width, height) = region_in_window(region, client)?;
+
+    let (monitor, x, y, width, height) =
+        screenshot::to_monitor_region(client.x + x as i32, client.y + y as i32, width, height)?;
+
+    Ok(CaptureTarget {
+        monitor: Some(monitor),
+        x,
+        y,
+        width,
+        height,
+        bounds: Some(client),
+    })
+}
+
+/// Região do config dentro da área cliente atual: escalada pela mudança
+/// de tamanho desde a seleção e cortada no que passar da janela
+///
+/// # Retorna
+/// * `(x, y, largura, altura)` relativos à área cliente
+fn region_in_window(region: &RegionConfig, client: ClientArea) -> Result<(u32, u32, u32, u32)> {
+    let (scale_x, scale_y) = match region.window_size {
+        Some([width, height]) if width > 0 && height > 0 => (
+            client.width as f64 / width as f64,
+            client.height as f64 / height as f64,
+        ),
+        _ => (1.0, 1.0),
+    };
+    let scaled = |value: u32, scale: f64| (value as f64 * scale).round() as u32;
+
+    let x = scaled(region.x, scale_x).min(client.width);
+    let y = scaled(region.y, scale_y).min(client.height);
+    let width = scaled(region.width, scale_x).min(client.width - x);
+    let height = scaled(region.height, scale_y).min(client.height - y);
+
+    if width == 0 || height == 0 {
+        anyhow::bail!(
+            "Região ({}, {} {}x{}) está fora da janela do jogo ({}x{})",
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            client.width,
+            client.height
+        );
+    }
+
+    Ok((x, y, width, height))
+}
+
+/// Área cliente inteira da janela (o "tela inteira" com janela presa)
+///
+/// # Retorna
+/// * `None` se não há janela presa (captura o monitor, como antes)
+pub fn resolve_window(window: &GameWindowConfig) -> Result<Option<CaptureTarget>> {
+    if !window.enabled {
+        return Ok(None);
+    }
+
+    let client = find_client_area(window)?;
+    let (monitor, x, y, width, height) =
+        screenshot::to_monitor_region(client.x, client.y, client.width, client.height)?;
+
+    Ok(Some(CaptureTarget {
+        monitor: Some(monitor),
+        x,
+        y,
+        width,
+        height,
+        bounds: Some(client),
+    }))
+}
+
+/// Converte um retângulo do desktop virtual (vindo do seletor) para uma
+/// região do config: relativa à janela do jogo, se presa a uma, senão
+/// ao monitor onde está o centro dele
+pub fn region_from_virtual(
+    window: &GameWindowConfig,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<RegionConfig> {
+    if !window.enabled {
+        let (monitor, x, y, width, height) = screenshot::to_monitor_region(x, y, width, height)?;
+        return Ok(RegionConfig {
+            x,
+            y,
+            width,
+            height,
+            monitor: Some(monitor.index),
+            window_size: None,
+        });
+    }
+
+    let client = find_client_area(window)?;
+    let right = client.x + client.width as i32;
+    let bottom = client.y + client.height as i32;
+
+    let left = x.clamp(client.x, right);
+    let top = y.clamp(client.y, bottom);
+    let width = ((x + width as i32).clamp(left, right) - left) as u32;
+    let height = ((y + height as i32).clamp(top, bottom) - top) as u32;
+
+    if width == 0 || height == 0 {
+        anyhow::bail!("A seleção está fora da janela do jogo");
+    }
+
+    Ok(RegionConfig {
+        x: (left - client.x) as u32,
+        y: (top - client.y) as u32,
+        width,
+        height,
+        monitor: None,
+        window_size: Some([client.width, client.height]),
+    })
+}
+
+/// Área do monitor (para `CaptureTarget::bounds`)
+fn monitor_area(monitor: MonitorInfo) -> ClientArea {
+    ClientArea {
+        x: monitor.x,
+        y: monitor.y,
+        width: monitor.width,
+        height: monitor.height,
+    }
+}
+
+// ============================================================================
+// BUSCA DA JANELA
+// ============================================================================
+
+/// Tempo que a lista de janelas fica em cache
+///
+/// O overlay de legendas acompanha a janela a cada frame; 200ms é rápido
+/// o bastante para seguir uma janela sendo arrastada.
+const WINDOW_CACHE_TTL: Duration = Duration::from_millis(200);
+
+static WINDOW_CACHE: Mutex<Option<(Instant, Vec<WindowInfo>)>> = Mutex::new(None);
+
+/// Área cliente da janela do jogo
+///
+/// Com várias janelas batendo, usa a maior que não está minimizada.
+pub fn find_client_area(window: &GameWindowConfig) -> Result<ClientArea> {
+    let description = describe(window);
+    if description.is_empty() {
+        anyhow::bail!("Janela do jogo ligada, mas sem processo nem título configurado");
+    }
+
+    let matching: Vec<WindowInfo> = list_windows()
+        .into_iter()
+        .filter(|w| w.matches(window))
+        .collect();
+
+    if matching.is_empty() {
+        anyhow::bail!("Janela do jogo não encontrada ({})", description);
+    }
+
+    matching
+        .iter()
+        .filter(|w| !w.minimized && w.client.width > 0 && w.client.height > 0)
+        .max_by_key(|w| w.client.width as u64 * w.client.height as u64)
+        .map(|w| w.client)
+        .ok_or_else(|| anyhow::anyhow!("Janela do jogo está minimizada ({})", description))
+}
+
+/// Descrição da janela para mensagens ("game.exe, título \"Jogo\"")
+pub fn describe(window: &GameWindowConfig) -> String {
+    let process = window.process_name.trim();
+    let title = window.window_title.trim();
+
+    match (process.is_empty(), title.is_empty()) {
+        (false, false) => format!("{}, título \"{}\"", process, title),
+        (false, true) => process.to_string(),
+        (true, false) => format!("título \"{}\"", title),
+        (true, true) => String::new(),
+    }
+}
+
+/// Janelas visíveis com título (em cache por `WINDOW_CACHE_TTL`)
+pub fn list_windows() -> Vec<WindowInfo> {
+    let mut cache = WINDOW_CACHE.lock().unwrap();
+    if let Some((time, windows)) = cache.as_ref() {
+        if time.elapsed() < WINDOW_CACHE_TTL {
+            return windows.clone();
+        }
+    }
+
+    let windows = enumerate_windows();
+    *cache = Some((Instant::now(), windows.clone()));
+    windows
+}
+
+/// Lista as janelas de nível superior (Win32)
+#[cfg(windows)]
+fn enumerate_windows() -> Vec<WindowInfo> {
+    use winapi::shared::minwindef::{BOOL, DWORD, LPARAM, TRUE};
+    use winapi::shared::windef::{HWND, POINT, RECT};
+    use winapi::um::winuser::{
+        ClientToScreen, EnumWindows, GetClientRect, GetWindowTextLengthW, GetWindowTextW,
+        GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let handles = &mut *(lparam as *mut Vec<HWND>);
+        handles.push(hwnd);
+        TRUE
+    }
+
+    let mut handles: Vec<HWND> = Vec::new();
+    unsafe {
+        EnumWindows(Some(collect), &mut handles as *mut Vec<HWND> as LPARAM);
+    }
+
+    handles
+        .into_iter()
+        .filter_map(|hwnd| unsafe {
+            if IsWindowVisible(hwnd) == 0 {
+                return None;
+            }
+
+            let length = GetWindowTextLengthW(hwnd);
+            if length <= 0 {
+                return None;
+            }
+            let mut buffer = vec![0u16; length as usize + 1];
+            let copied = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+            let title = String::from_utf16_lossy(&buffer[..copied.max(0) as usize]);
+
+            let mut pid: DWORD = 0;
+            GetWindowThreadProcessId(hwnd, &mut pid);
+
+            // Área cliente: tamanho pelo GetClientRect, origem convertida
+            // para a tela (com DPI awareness, tudo em pixels físicos)
+            let mut rect: RECT = std::mem::zeroed();
+            let mut origin = POINT { x: 0, y: 0 };
+            GetClientRect(hwnd, &mut rect);
+            ClientToScreen(hwnd, &mut origin);
+
+            Some(WindowInfo {
+                title,
+                process: process_name(pid).unwrap_or_default(),
+                minimized: IsIconic(hwnd) != 0,
+                client: ClientArea {
+                    x: origin.x,
+                    y: origin.y,
+                    width: (rect.right - rect.left).max(0) as u32,
+                    height: (rect.bottom - rect.top).max(0) as u32,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Nome do executável de um processo (ex: "game.exe")
+#[cfg(windows)]
+fn process_name(pid: u32) -> Option<String> {
+    use winapi::shared::minwindef::{DWORD, FALSE};
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::OpenProcess;
+    use winapi::um::winbase::QueryFullProcessImageNameW;
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+        if handle.is_null() {
+            return None;
+        }
+
+        let mut buffer = [0u16; 1024];
+        let mut size = buffer.len() as DWORD;
+        let ok = QueryFullProcessImageNameW(handle, 0, buffer.as_mut_ptr(), &mut size);
+        CloseHandle(handle);
+
+        if ok == 0 {
+            return None;
+        }
+
+        let path = String::from_utf16_lossy(&buffer[..size as usize]);
+        std::path::Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+}
+
+/// Fora do Windows não há como listar janelas
+#[cfg(not(windows))]
+fn enumerate_windows() -> Vec<WindowInfo> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        window_size: Option<[u32; 2]>,
+    ) -> RegionConfig {
+        RegionConfig {
+            x,
+            y,
+            width,
+            height,
+            monitor: None,
+            window_size,
+        }
+    }
+
+    fn client(width: u32, height: u32) -> ClientArea {
+        ClientArea {
+            x: 300,
+            y: 200,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn region_follows_window_size() {
+        // Mesmo tamanho (ou sem tamanho salvo): região como está
+        let r = region(40, 500, 600, 80, Some([1280, 720]));
+        assert_eq!(
+            region_in_window(&r, client(1280, 720)).unwrap(),
+            (40, 500, 600, 80)
+        );
+        let r = region(40, 500, 600, 80, None);
+        assert_eq!(
+            region_in_window(&r, client(1920, 1080)).unwrap(),
+            (40, 500, 600, 80)
+        );
+
+        // Jogo passou de 1280x720 para 1920x1080: escala 1.5x
+        let r = region(40, 500, 600, 80, Some([1280, 720]));
+        assert_eq!(
+            region_in_window(&r, client(1920, 1080)).unwrap(),
+            (60, 750, 900, 120)
+        );
+    }
+
+    #[test]
+    fn region_is_clipped_to_window() {
+        let r = region(1000, 600, 600, 200, None);
+        assert_eq!(
+            region_in_window(&r, client(1280, 720)).unwrap(),
+            (1000, 600, 280, 120)
+        );
+
+        let r = region(1300, 100, 100, 100, None);
+        assert!(region_in_window(&r, client(1280, 720)).is_err());
+    }
+
+    #[test]
+    fn window_matching_ignores_case_and_empty_fields() {
+        let window = WindowInfo {
+            title: "Persona 5 Royal".to_string(),
+            process: "P5R.exe".to_string(),
+            minimized: false,
+            client: client(1280, 720),
+        };
+        let config = |process: &str, title: &str| GameWindowConfig {
+            enabled: true,
+            process_name: process.to_string(),
+            window_title: title.to_string(),
+        };
+
+        assert!(window.matches(&config("p5r.exe", "")));
+        assert!(window.matches(&config("", "persona 5")));
+        assert!(window.matches(&config("P5R.exe", "Royal")));
+        assert!(!window.matches(&config("p5r.exe", "Persona 4")));
+        assert!(!window.matches(&config("game.exe", "")));
+    }
+}
@@ -0,0 +1,460 @@
+// game-translator/src/history.rs
+
+// ============================================================================
+// MÓDULO HISTORY - Histórico das últimas capturas (relatório de bug)
+// ============================================================================
+//
+// Quando uma tradução sai errada, o frame já se foi: o
+// debug_preprocessed.png é sobrescrito a cada captura. Aqui ficam em
+// memória as últimas capturas com tudo que passou por elas:
+//
+//   captura → imagem pré-processada → texto do OCR → tradução
+//
+// O tamanho é limitado pela quantidade de capturas E pela memória
+// (uma captura de tela cheia 4K tem ~33MB): ao passar de qualquer um
+// dos dois, as mais antigas saem.
+//
+// A hotkey de relatório (`hotkeys.dump_history`) grava as últimas N
+// numa pasta com data e hora, pronta para anexar num bug report:
+//
+//   bug_reports/bug_report_20261018_153012/
+//   ├── report.json          (OCR, traduções, região, horário de cada captura)
+//   ├── config.json          (config usado, sem as chaves de API)
+//   ├── 01_capture.png
+//   ├── 01_preprocessed.png
+//   └── ...
+//
+// ============================================================================
+
+use crate::config::{AppConfig, HistoryConfig};
+use crate::ocr::DetectedText;
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ============================================================================
+// TIPOS
+// ============================================================================
+
+/// Uma captura que acabou de passar pelo OCR
+pub struct CaptureEntry {
+    /// Modo que capturou ("região", "tela cheia", "legenda")
+    pub mode: &'static str,
+    /// Canto da captura no desktop virtual
+    pub origin: (i32, i32),
+    /// Imagem capturada, antes do pré-processamento
+    pub capture: DynamicImage,
+    /// Imagem que foi para o OCR (None = sem pré-processamento)
+    pub preprocessed: Option<DynamicImage>,
+    /// Texto completo lido pelo OCR (depois das correções)
+    pub ocr_text: String,
+    /// Linhas do OCR com posição e confiança, nas coordenadas de `capture`
+    /// (sem o upscale do pré-processamento)
+    pub lines: Vec<DetectedText>,
+}
+
+/// Captura guardada no histórico
+///
+/// A captura fica num Arc: o relatório copia a lista e grava os PNGs sem
+/// segurar o lock (a thread de legendas continua gravando no histórico).
+#[derive(Clone)]
+struct HistoryRecord {
+    id: u64,
+    time: SystemTime,
+    entry: Arc<CaptureEntry>,
+    /// Pares (original, tradução), preenchidos quando a tradução chega
+    translations: Vec<(String, String)>,
+}
+
+impl HistoryRecord {
+    /// Memória ocupada pelas imagens
+    fn bytes(&self) -> usize {
+        self.entry.capture.as_bytes().len()
+            + self
+                .entry
+                .preprocessed
+                .as_ref()
+                .map_or(0, |image| image.as_bytes().len())
+    }
+}
+
+struct HistoryInner {
+    records: VecDeque<HistoryRecord>,
+    next_id: u64,
+    bytes: usize,
+}
+
+/// Histórico das últimas capturas (compartilhado entre as threads)
+#[derive(Clone)]
+pub struct CaptureHistory {
+    inner: Arc<Mutex<HistoryInner>>,
+}
+
+// ============================================================================
+// HISTÓRICO
+// ============================================================================
+
+impl CaptureHistory {
+    pub fn new() -> Self {
+        CaptureHistory {
+            inner: Arc::new(Mutex::new(HistoryInner {
+                records: VecDeque::new(),
+                next_id: 0,
+                bytes: 0,
+            })),
+        }
+    }
+
+    /// Guarda uma captura, descartando as mais antigas se passar dos limites
+    ///
+    /// # Retorna
+    /// * `Some(id)` para anexar a tradução depois (`set_translations`)
+    /// * `None` se o histórico está desligado (`size` = 0)
+    pub fn record(&self, config: &HistoryConfig, entry: CaptureEntry) -> Option<u64> {
+        if config.size == 0 {
+            return None;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        let record = HistoryRecord {
+            id,
+            time: SystemTime::now(),
+            entry: Arc::new(entry),
+            translations: Vec::new(),
+        };
+        inner.bytes += record.bytes();
+        inner.records.push_back(record);
+
+        // A captura mais nova sempre fica, mesmo sozinha acima do limite
+        let max_bytes = config.max_memory_mb * 1024 * 1024;
+        while inner.records.len() > config.size
+            || (inner.records.len() > 1 && inner.bytes > max_bytes)
+        {
+            if let Some(old) = inner.records.pop_front() {
+                inner.bytes -= old.bytes();
+            }
+        }
+
+        Some(id)
+    }
+
+    /// Anexa as traduções de uma captura (se ela ainda está no histórico)
+    pub fn set_translations(&self, id: u64, translations: Vec<(String, String)>) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(record) = inner.records.iter_mut().find(|r| r.id == id) {
+            record.translations = translations;
+        }
+    }
+
+    /// Anexa a tradução de uma legenda à captura mais recente com esse texto
+    ///
+    /// A legenda é traduzida depois do debounce, em outra thread: o id da
+    /// captura já se perdeu, mas o texto é o mesmo.
+    pub fn set_translation_for_text(&self, original: &str, translated: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(record) = inner
+            .records
+            .iter_mut()
+            .rev()
+            .find(|r| r.entry.ocr_text == original)
+        {
+            record.translations = vec![(original.to_string(), translated.to_string())];
+        }
+    }
+
+    /// Quantas capturas estão guardadas
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.inner.lock().unwrap().records.len()
+    }
+
+    // ========================================================================
+    // RELATÓRIO
+    // ========================================================================
+
+    /// Grava as últimas `count` capturas numa pasta nova dentro de `folder`
+    ///
+    /// # Retorna
+    /// * Caminho da pasta do relatório e quantas capturas foram gravadas
+    pub fn dump(
+        &self,
+        folder: &Path,
+        count: usize,
+        config: &AppConfig,
+    ) -> Result<(PathBuf, usize)> {
+        let records: Vec<HistoryRecord> = {
+            let inner = self.inner.lock().unwrap();
+            let skip = inner.records.len().saturating_sub(count);
+            inner.records.iter().skip(skip).cloned().collect()
+        };
+        if records.is_empty() {
+            anyhow::bail!("Histórico de capturas vazio");
+        }
+
+        let now = SystemTime::now();
+        let mut target = folder.join(format!("bug_report_{}", file_stamp(now)));
+        // Dois relatórios no mesmo segundo não se sobrescrevem
+        let mut suffix = 2;
+        while target.exists() {
+            target = folder.join(format!("bug_report_{}_{}", file_stamp(now), suffix));
+            suffix += 1;
+        }
+        fs::create_dir_all(&target)
+            .with_context(|| format!("Falha ao criar pasta {}", target.display()))?;
+
+        let mut entries = Vec::new();
+
+        for (index, record) in records.iter().enumerate() {
+            let prefix = format!("{:02}", index + 1);
+
+            let capture_file = format!("{}_capture.png", prefix);
+            record
+                .entry
+                .capture
+                .save(target.join(&capture_file))
+                .with_context(|| format!("Falha ao salvar {}", capture_file))?;
+
+            let preprocessed_file = match &record.entry.preprocessed {
+                Some(image) => {
+                    let file = format!("{}_preprocessed.png", prefix);
+                    image
+                        .save(target.join(&file))
+                        .with_context(|| format!("Falha ao salvar {}", file))?;
+                    Some(file)
+                }
+                None => None,
+            };
+
+            entries.push(EntryReport {
+                time: readable_stamp(record.time),
+                mode: record.entry.mode,
+                x: record.entry.origin.0,
+                y: record.entry.origin.1,
+                width: record.entry.capture.width(),
+                height: record.entry.capture.height(),
+                capture: capture_file,
+                preprocessed: preprocessed_file,
+                ocr_text: &record.entry.ocr_text,
+                lines: record
+                    .entry
+                    .lines
+                    .iter()
+                    .map(|line| LineReport {
+                        text: &line.text,
+                        x: line.x,
+                        y: line.y,
+                        width: line.width,
+                        height: line.height,
+                        confidence: line.confidence,
+                        vertical: line.vertical,
+                    })
+                    .collect(),
+                translations: record
+                    .translations
+                    .iter()
+                    .map(|(original, translated)| TranslationReport {
+                        original,
+                        translated,
+                    })
+                    .collect(),
+            });
+        }
+
+        let report = Report {
+            created: readable_stamp(now),
+            version: env!("CARGO_PKG_VERSION"),
+            entries,
+        };
+        let json = serde_json::to_string_pretty(&report)
+            .context("Falha ao serializar relatório de capturas")?;
+        fs::write(target.join("report.json"), json).context("Falha ao escrever report.json")?;
+
+        // Config junto, sem as chaves de API (o relatório vai ser compartilhado)
+        let mut config = config.clone();
+        config.translation.deepl_api_key.clear();
+        config.translation.elevenlabs_api_key.clear();
+        let json =
+            serde_json::to_string_pretty(&config).context("Falha ao serializar configurações")?;
+        fs::write(target.join("config.json"), json).context("Falha ao escrever config.json")?;
+
+        Ok((target, report.entries.len()))
+    }
+}
+
+// ============================================================================
+// FORMATO DO report.json
+// ============================================================================
+
+#[derive(Serialize)]
+struct Report<'a> {
+    created: String,
+    version: &'static str,
+    entries: Vec<EntryReport<'a>>,
+}
+
+#[derive(Serialize)]
+struct EntryReport<'a> {
+    time: String,
+    mode: &'static str,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    capture: String,
+    preprocessed: Option<String>,
+    ocr_text: &'a str,
+    lines: Vec<LineReport<'a>>,
+    translations: Vec<TranslationReport<'a>>,
+}
+
+#[derive(Serialize)]
+struct LineReport<'a> {
+    text: &'a str,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    confidence: Option<f32>,
+    vertical: bool,
+}
+
+#[derive(Serialize)]
+struct TranslationReport<'a> {
+    original: &'a str,
+    translated: &'a str,
+}
+
+// ============================================================================
+// DATA E HORA (UTC, sem dependência extra)
+// ============================================================================
+
+/// (ano, mês, dia, hora, minuto, segundo) em UTC
+fn utc_parts(time: SystemTime) -> (i64, u32, u32, u32, u32, u32) {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) as i64;
+    let (days, rest) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Dias desde 1970-01-01 → data civil (algoritmo de Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (
+        year,
+        month,
+        day,
+        (rest / 3600) as u32,
+        (rest % 3600 / 60) as u32,
+        (rest % 60) as u32,
+    )
+}
+
+/// "20261018_153012" (nome de pasta)
+pub fn file_stamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = utc_parts(time);
+    format!(
+        "{:04}{:02}{:02}_{:02}{:02}{:02}",
+        year, month, day, hour, minute, second
+    )
+}
+
+/// "2026-10-18 15:30:12 UTC" (report.json)
+fn readable_stamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = utc_parts(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, hour, minute, second
+    )
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+    use std::time::Duration;
+
+    fn entry(text: &str, size: u32) -> CaptureEntry {
+        CaptureEntry {
+            mode: "região",
+            origin: (10, 20),
+            capture: DynamicImage::ImageRgba8(RgbaImage::new(size, size)),
+            preprocessed: None,
+            ocr_text: text.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn ring_drops_oldest_by_count_and_memory() {
+        let history = CaptureHistory::new();
+        let mut config = HistoryConfig {
+            size: 3,
+            ..HistoryConfig::default()
+        };
+
+        for i in 0..5 {
+            history.record(&config, entry(&i.to_string(), 8));
+        }
+        assert_eq!(history.len(), 3);
+
+        // 1MB de limite: cada captura 512x512 RGBA tem 1MB
+        config.max_memory_mb = 1;
+        history.record(&config, entry("grande", 512));
+        assert_eq!(history.len(), 1);
+        history.record(&config, entry("grande 2", 512));
+        assert_eq!(history.len(), 1);
+
+        config.size = 0;
+        assert_eq!(history.record(&config, entry("desligado", 8)), None);
+    }
+
+    #[test]
+    fn dump_writes_bundle_without_api_keys() {
+        let history = CaptureHistory::new();
+        let config = HistoryConfig::default();
+        let id = history.record(&config, entry("Hello", 16)).unwrap();
+        history.set_translations(id, vec![("Hello".to_string(), "Olá".to_string())]);
+
+        let mut app_config = AppConfig::default();
+        app_config.translation.deepl_api_key = "segredo".to_string();
+
+        let folder =
+            std::env::temp_dir().join(format!("game-translator-history-{}", std::process::id()));
+        let (target, count) = history.dump(&folder, 10, &app_config).unwrap();
+        assert_eq!(count, 1);
+
+        let report = fs::read_to_string(target.join("report.json")).unwrap();
+        assert!(report.contains("\"ocr_text\": \"Hello\""));
+        assert!(report.contains("Olá"));
+        assert!(target.join("01_capture.png").exists());
+        assert!(!fs::read_to_string(target.join("config.json"))
+            .unwrap()
+            .contains("segredo"));
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn stamps_are_utc_calendar_dates() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_792_337_412);
+        assert_eq!(file_stamp(time), "20261018_153012");
+        assert_eq!(readable_stamp(UNIX_EPOCH), "1970-01-01 00:00:00 UTC");
+    }
+}
@@ -21,6 +21,7 @@ pub struct HotkeyManager {
     hotkey_toggle_subtitle_mode: Keycode,
     hotkey_hide_translation: Keycode,
     hotkey_open_settings: Keycode,
+    hotkey_dump_history: Keycode,
 }
 
 /// Tipo de ação solicitada pelo usuário
@@ -40,6 +41,8 @@ pub enum HotkeyAction {
     HideTranslation,
     /// Abre a janela de configurações
     OpenSettings,
+    /// Grava as últimas capturas num relatório de bug
+    DumpHistory,
 }
 
 /// Converte uma string do config para Keycode
@@ -95,6 +98,7 @@ impl HotkeyManager {
         let hotkey_toggle_subtitle_mode = string_to_keycode(&hotkeys.toggle_subtitle_mode);
         let hotkey_hide_translation = string_to_keycode(&hotkeys.hide_translation);
         let hotkey_open_settings = Keycode::Numpad5; // Fixo por enquanto
+        let hotkey_dump_history = string_to_keycode(&hotkeys.dump_history);
 
         info!("✅ Detecção de teclas configurada!");
         info!("   Tela cheia: {:?}", hotkey_translate_fullscreen);
@@ -112,6 +116,7 @@ impl HotkeyManager {
             hotkey_toggle_subtitle_mode,
             hotkey_hide_translation,
             hotkey_open_settings,
+            hotkey_dump_history,
         }
     }
 
@@ -146,6 +151,7 @@ impl HotkeyManager {
             ),
             (self.hotkey_hide_translation, HotkeyAction::HideTranslation),
             (self.hotkey_open_settings, HotkeyAction::OpenSettings),
+            (self.hotkey_dump_history, HotkeyAction::DumpHistory),
         ];
 
         for &(key, action) in &key_actions {
@@ -0,0 +1,374 @@
+// game-translator/src/layout.rs
+
+// ============================================================================
+// MÓDULO LAYOUT - Agrupa linhas do OCR em blocos de texto
+// ============================================================================
+//
+// O OCR devolve linhas soltas. Uma caixa de diálogo com três linhas vira
+// três frases independentes, e a tradução de cada pedaço fica sem sentido.
+//
+// Este módulo junta linhas que pertencem ao mesmo parágrafo/caixa usando:
+// - Espaço vertical: linhas próximas (gap <= max_line_gap × altura)
+// - Tamanho da fonte: alturas parecidas
+// - Alinhamento: esquerda, centro ou direita dentro da tolerância
+// - Recuo: linha recuada num bloco alinhado à esquerda começa novo parágrafo
+//
+// Texto vertical (colunas de japonês/chinês) usa as mesmas regras: as
+// colunas são "deitadas" (transpostas), agrupadas como linhas e depois
+// voltam à orientação original.
+//
+// Cada bloco vira um único DetectedText, traduzido como uma unidade.
+//
+// ============================================================================
+
+use crate::cjk;
+use crate::config::LayoutConfig;
+use crate::ocr::{DetectedText, DetectedWord};
+
+/// Razão mínima entre alturas de duas linhas do mesmo bloco
+/// (abaixo disso consideramos que são fontes diferentes, ex: título vs texto)
+const MIN_HEIGHT_RATIO: f64 = 0.6;
+
+/// Recuo máximo da primeira linha de um parágrafo (em alturas de linha)
+const MAX_FIRST_LINE_INDENT: f64 = 4.0;
+
+/// Sobreposição vertical máxima permitida entre linhas consecutivas
+/// (mais que isso = estão lado a lado, ex: duas colunas)
+const MAX_VERTICAL_OVERLAP: f64 = 0.5;
+
+/// Agrupa as linhas do OCR em blocos (parágrafos/caixas de diálogo)
+///
+/// # Argumentos
+/// * `lines` - Linhas detectadas pelo OCR
+/// * `config` - Tolerâncias do agrupamento
+///
+/// # Retorna
+/// * Um `DetectedText` por bloco, na ordem de leitura (de cima para baixo)
+pub fn group_into_blocks(lines: &[DetectedText], config: &LayoutConfig) -> Vec<DetectedText> {
+    if !config.enabled || lines.len() < 2 {
+        return lines.to_vec();
+    }
+
+    let (vertical, horizontal): (Vec<DetectedText>, Vec<DetectedText>) =
+        lines.iter().cloned().partition(|l| l.vertical);
+
+    let mut blocks = group_lines(&horizontal, config);
+
+    // Colunas verticais: transpõe, agrupa como linhas e transpõe de volta
+    if !vertical.is_empty() {
+        let transposed: Vec<DetectedText> = vertical.iter().map(transpose).collect();
+        blocks.extend(group_lines(&transposed, config).iter().map(untranspose));
+    }
+
+    info!(
+        "   🧩 Layout: {} linhas agrupadas em {} blocos",
+        lines.len(),
+        blocks.len()
+    );
+
+    blocks
+}
+
+/// Agrupa linhas horizontais em blocos
+fn group_lines(lines: &[DetectedText], config: &LayoutConfig) -> Vec<DetectedText> {
+    // Ordena de cima para baixo (e da esquerda para a direita)
+    let mut sorted: Vec<&DetectedText> = lines.iter().collect();
+    sorted.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let mut blocks: Vec<Vec<&DetectedText>> = Vec::new();
+
+    for line in sorted {
+        // Entre os blocos compatíveis, escolhe o mais próximo verticalmente
+        let best = blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| belongs_to_block(block, line, config))
+            .min_by(|(_, a), (_, b)| {
+                let gap_a = line.y - bottom(a.last().unwrap());
+                let gap_b = line.y - bottom(b.last().unwrap());
+                gap_a.total_cmp(&gap_b)
+            })
+            .map(|(index, _)| index);
+
+        match best {
+            Some(index) => blocks[index].push(line),
+            None => blocks.push(vec![line]),
+        }
+    }
+
+    blocks.iter().map(|block| merge_block(block)).collect()
+}
+
+/// Verifica se a linha continua o bloco (comparando com a última linha dele)
+fn belongs_to_block(block: &[&DetectedText], line: &DetectedText, config: &LayoutConfig) -> bool {
+    let first = block[0];
+    let last = block[block.len() - 1];
+
+    let height = last.height.max(line.height);
+    if height <= 0.0 {
+        return false;
+    }
+
+    // Fontes de tamanhos muito diferentes não são do mesmo bloco
+    if last.height.min(line.height) / height < MIN_HEIGHT_RATIO {
+        return false;
+    }
+
+    // Espaço vertical entre a última linha do bloco e esta
+    let gap = line.y - bottom(last);
+    if gap < -MAX_VERTICAL_OVERLAP * height || gap > config.max_line_gap as f64 * height {
+        return false;
+    }
+
+    // Precisam se sobrepor horizontalmente
+    if line.x.max(last.x) >= right(line).min(right(last)) {
+        return false;
+    }
+
+    let tolerance = config.align_tolerance as f64 * height;
+
+    let left_diff = line.x - last.x;
+    let aligned_left = left_diff.abs() <= tolerance;
+    let aligned_center = (center_x(line) - center_x(last)).abs() <= tolerance;
+    let aligned_right = (right(line) - right(last)).abs() <= tolerance;
+
+    // Recuo: num bloco alinhado à esquerda, uma linha recuada começa
+    // um novo parágrafo (mesmo que a direita esteja alinhada).
+    // Blocos centralizados (caixas de diálogo) não seguem essa regra.
+    let centered_block = block
+        .iter()
+        .all(|l| (center_x(l) - center_x(first)).abs() <= tolerance);
+    let left_aligned_block = block.len() >= 2
+        && block[1..]
+            .iter()
+            .all(|l| (l.x - block[1].x).abs() <= tolerance);
+    if !(centered_block && aligned_center) && left_aligned_block && line.x - block[1].x > tolerance
+    {
+        return false;
+    }
+
+    // Primeira linha do parágrafo recuada: a segunda começa mais à esquerda
+    let after_indented_first_line =
+        block.len() == 1 && left_diff < 0.0 && -left_diff <= MAX_FIRST_LINE_INDENT * height;
+
+    // Mesmo alinhado pelo centro, não pode fugir muito do início do bloco
+    let near_block = (line.x - first.x).abs() <= MAX_FIRST_LINE_INDENT * height
+        || aligned_center
+        || aligned_right;
+
+    near_block && (aligned_left || aligned_center || aligned_right || after_indented_first_line)
+}
+
+/// Junta as linhas de um bloco em um único DetectedText
+fn merge_block(block: &[&DetectedText]) -> DetectedText {
+    if block.len() == 1 {
+        return block[0].clone();
+    }
+
+    let left = block.iter().map(|l| l.x).fold(f64::MAX, f64::min);
+    let top = block.iter().map(|l| l.y).fold(f64::MAX, f64::min);
+    let right_edge = block.iter().map(|l| right(l)).fold(f64::MIN, f64::max);
+    let bottom_edge = block.iter().map(|l| bottom(l)).fold(f64::MIN, f64::max);
+
+    let mut text = String::new();
+    for line in block {
+        join_line(&mut text, line.text.trim());
+    }
+
+    let confidences: Vec<f32> = block.iter().filter_map(|l| l.confidence).collect();
+    let confidence = if confidences.is_empty() {
+        None
+    } else {
+        Some(confidences.iter().sum::<f32>() / confidences.len() as f32)
+    };
+
+    DetectedText {
+        text,
+        x: left,
+        y: top,
+        width: right_edge - left,
+        height: bottom_edge - top,
+        words: block.iter().flat_map(|l| l.words.clone()).collect(),
+        confidence,
+        vertical: block[0].vertical,
+    }
+}
+
+/// Acrescenta uma linha ao texto do bloco
+///
+/// Palavras quebradas com hífen no fim da linha ("conver-" + "sation")
+/// são juntadas de volta; chinês/japonês são juntados sem espaço;
+/// nos outros casos as linhas são separadas por espaço.
+fn join_line(text: &mut String, line: &str) {
+    if text.is_empty() || cjk::joins_without_space(text, line) {
+        text.push_str(line);
+        return;
+    }
+
+    let mut previous = text.chars().rev();
+    let hyphenated = previous.next() == Some('-')
+        && previous.next().is_some_and(|c| c.is_alphabetic())
+        && line.chars().next().is_some_and(|c| c.is_lowercase());
+
+    if hyphenated {
+        text.pop();
+    } else {
+        text.push(' ');
+    }
+    text.push_str(line);
+}
+
+// ============================================================================
+// TRANSPOSIÇÃO (texto vertical)
+// ============================================================================
+//
+// Uma coluna vertical vira uma "linha deitada":
+//   x' = y, y' = -(x + largura), largura' = altura, altura' = largura
+// Assim, colunas lidas da direita para a esquerda viram linhas lidas
+// de cima para baixo, e o alinhamento pelo topo vira alinhamento à esquerda.
+
+/// Deita uma coluna vertical (vira uma linha horizontal)
+fn transpose(line: &DetectedText) -> DetectedText {
+    let (x, y, width, height) = transpose_box(line.x, line.y, line.width, line.height);
+
+    DetectedText {
+        x,
+        y,
+        width,
+        height,
+        words: line
+            .words
+            .iter()
+            .map(|w| {
+                let (x, y, width, height) = transpose_box(w.x, w.y, w.width, w.height);
+                DetectedWord {
+                    x,
+                    y,
+                    width,
+                    height,
+                    ..w.clone()
+                }
+            })
+            .collect(),
+        ..line.clone()
+    }
+}
+
+/// Volta uma linha deitada para a orientação vertical original
+fn untranspose(line: &DetectedText) -> DetectedText {
+    let (x, y, width, height) = untranspose_box(line.x, line.y, line.width, line.height);
+
+    DetectedText {
+        x,
+        y,
+        width,
+        height,
+        words: line
+            .words
+            .iter()
+            .map(|w| {
+                let (x, y, width, height) = untranspose_box(w.x, w.y, w.width, w.height);
+                DetectedWord {
+                    x,
+                    y,
+                    width,
+                    height,
+                    ..w.clone()
+                }
+            })
+            .collect(),
+        ..line.clone()
+    }
+}
+
+fn transpose_box(x: f64, y: f64, width: f64, height: f64) -> (f64, f64, f64, f64) {
+    (y, -(x + width), height, width)
+}
+
+fn untranspose_box(x: f64, y: f64, width: f64, height: f64) -> (f64, f64, f64, f64) {
+    (-(y + height), x, height, width)
+}
+
+fn right(line: &DetectedText) -> f64 {
+    line.x + line.width
+}
+
+fn bottom(line: &DetectedText) -> f64 {
+    line.y + line.height
+}
+
+fn center_x(line: &DetectedText) -> f64 {
+    line.x + line.width / 2.0
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, x: f64, y: f64, width: f64, height: f64) -> DetectedText {
+        DetectedText {
+            text: text.to_string(),
+            x,
+            y,
+            width,
+            height,
+            words: Vec::new(),
+            confidence: None,
+            vertical: false,
+        }
+    }
+
+    #[test]
+    fn centered_dialogue_box_becomes_one_block() {
+        // Caixa de diálogo centralizada em x = 960, linhas de tamanhos diferentes
+        let lines = vec![
+            line("You shouldn't have come", 660.0, 500.0, 600.0, 24.0),
+            line("here, traveler. The conver-", 640.0, 530.0, 640.0, 24.0),
+            line("sation is over.", 760.0, 560.0, 400.0, 24.0),
+        ];
+
+        let blocks = group_into_blocks(&lines, &LayoutConfig::default());
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            blocks[0].text,
+            "You shouldn't have come here, traveler. The conversation is over."
+        );
+        assert_eq!(
+            (blocks[0].x, blocks[0].y, blocks[0].width, blocks[0].height),
+            (640.0, 500.0, 640.0, 84.0)
+        );
+    }
+
+    #[test]
+    fn separate_boxes_stay_apart() {
+        let lines = vec![
+            // Duas caixas lado a lado, na mesma altura
+            line("Left box, first line", 100.0, 100.0, 400.0, 24.0),
+            line("Right box, first line", 1200.0, 100.0, 400.0, 24.0),
+            line("left box, second line", 100.0, 130.0, 380.0, 24.0),
+            line("right box, second line", 1200.0, 130.0, 380.0, 24.0),
+            // Mesma coluna, mas muito abaixo (outra caixa)
+            line("Far below", 100.0, 600.0, 200.0, 24.0),
+            // Título com fonte bem maior logo acima da caixa de baixo
+            line("CHAPTER ONE", 100.0, 540.0, 500.0, 50.0),
+        ];
+
+        let blocks = group_into_blocks(&lines, &LayoutConfig::default());
+        let texts: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+
+        assert_eq!(
+            texts,
+            [
+                "Left box, first line left box, second line",
+                "Right box, first line right box, second line",
+                "CHAPTER ONE",
+                "Far below",
+            ]
+        );
+    }
+}
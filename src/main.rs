@@ -12,20 +12,38 @@ extern crate log;
 // ============================================================================
 // DECLARAÇÃO DE MÓDULOS
 // ============================================================================
+mod autotune;
 mod cache;
+mod capture;
+mod cjk;
+mod cli;
 mod config;
+mod corpus;
+mod correction;
+mod debug_dump;
+mod filters;
+mod game_window;
+mod history;
 mod hotkey;
+mod layout;
 mod ocr;
+mod prewarm;
+#[cfg(windows)]
+mod region_selector;
+#[cfg(not(windows))]
+#[path = "region_selector_stub.rs"]
 mod region_selector;
 mod screenshot;
+mod speaker;
 mod subtitle;
+mod text_regions;
 mod translator;
 mod tts;
 
 // ============================================================================
 // IMPORTS
 // ============================================================================
-use anyhow::Result;
+use anyhow::{Context, Result};
 use config::Config;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use notify::{RecursiveMode, Watcher};
@@ -48,6 +66,10 @@ enum AppCommand {
     OpenRegionSelector,
     /// Abre o seletor de região de legendas
     OpenSubtitleRegionSelector,
+    /// Conta-gotas: adiciona uma cor-chave ao pré-processamento do display
+    PickDisplayKeyColor,
+    /// Conta-gotas: adiciona uma cor-chave ao pré-processamento das legendas
+    PickSubtitleKeyColor,
     /// Abre a janela de configurações
     OpenSettings,
     /// Fecha a janela de configurações
@@ -58,11 +80,11 @@ enum AppCommand {
 // ESTRUTURA DE ESTADO COMPARTILHADO
 // ============================================================================
 /// Estado compartilhado entre a UI (overlay) e a thread de hotkeys
-/// Região onde o texto foi capturado
+/// Região onde o texto foi capturado (coordenadas do desktop virtual)
 #[derive(Clone, Debug)]
 struct CaptureRegion {
-    x: u32,
-    y: u32,
+    x: i32,
+    y: i32,
     width: u32,
     height: u32,
 }
@@ -75,6 +97,17 @@ enum CaptureMode {
     FullScreen,
 }
 
+/// Backend de OCR já criado, com o config e o idioma usados para criá-lo
+type CachedOcrBackend = ((config::OcrConfig, String), Arc<dyn ocr::OcrBackend>);
+
+/// O que decide o texto lido de uma captura de legenda: região,
+/// pré-processamento e OCR (config e idioma)
+type FrameSettings = (
+    config::RegionConfig,
+    config::PreprocessConfig,
+    (config::OcrConfig, String),
+);
+
 #[derive(Clone)]
 struct AppState {
     config: Arc<Mutex<Config>>,
@@ -95,6 +128,12 @@ struct AppState {
     settings_mode: Arc<Mutex<bool>>,
     /// Fator de escala DPI (ex: 1.25 para 125%)
     dpi_scale: f32,
+    /// De onde vêm as capturas (tela ao vivo ou gravação no --replay)
+    capture_source: Arc<Mutex<Box<dyn capture::CaptureSource>>>,
+    /// Últimas capturas com OCR e tradução (relatório de bug)
+    history: history::CaptureHistory,
+    /// Backend de OCR das hotkeys (reaproveitado entre capturas)
+    ocr_backend: Arc<Mutex<Option<CachedOcrBackend>>>,
 }
 
 impl AppState {
@@ -121,9 +160,36 @@ impl AppState {
             overlay_hidden: Arc::new(Mutex::new(false)),
             settings_mode: Arc::new(Mutex::new(false)),
             dpi_scale,
+            capture_source: Arc::new(Mutex::new(Box::new(capture::ScreenSource))),
+            history: history::CaptureHistory::new(),
+            ocr_backend: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Backend de OCR das hotkeys, recriado só quando o config de OCR
+    /// ou o idioma mudam (mesma chave da thread de legendas)
+    ///
+    /// Criar o backend carrega os modelos de idioma (o Tesseract relê o
+    /// traineddata): caro demais para repetir a cada tecla.
+    fn ocr_backend(
+        &self,
+        ocr_config: &config::OcrConfig,
+        language: &str,
+    ) -> Result<Arc<dyn ocr::OcrBackend>> {
+        let mut cached = self.ocr_backend.lock().unwrap();
+
+        if let Some(((config, cached_language), backend)) = cached.as_ref() {
+            if config == ocr_config && cached_language == language {
+                return Ok(backend.clone());
+            }
+        }
+
+        let backend: Arc<dyn ocr::OcrBackend> =
+            Arc::from(ocr::create_backend(ocr_config, language)?);
+        *cached = Some(((ocr_config.clone(), language.to_string()), backend.clone()));
+        Ok(backend)
+    }
+
     /// Define a lista de textos traduzidos com posições, região e modo de captura
     fn set_translations(
         &self,
@@ -181,6 +247,19 @@ struct OverlayApp {
     settings_status: Option<(String, std::time::Instant)>,
 }
 
+impl OverlayApp {
+    /// Pixels físicos por ponto do egui no monitor onde a janela está agora
+    ///
+    /// Posições vêm em pixels físicos do desktop virtual. Com monitores de
+    /// escalas diferentes o fator muda quando a janela troca de monitor;
+    /// como posição e tamanho são reenviados a cada frame, no frame
+    /// seguinte já vale o fator do monitor novo.
+    fn pixels_per_point(&self, ctx: &eframe::egui::Context) -> f32 {
+        ctx.input(|i| i.viewport().native_pixels_per_point)
+            .unwrap_or(self.state.dpi_scale)
+    }
+}
+
 impl eframe::App for OverlayApp {
     fn clear_color(&self, _visuals: &eframe::egui::Visuals) -> [f32; 4] {
         [0.0, 0.0, 0.0, 0.0] // Totalmente transparente
@@ -240,27 +319,45 @@ impl eframe::App for OverlayApp {
                     ));
 
                     // Abre o seletor de região
-                    match region_selector::select_region(None) {
-                        Ok(Some(selected)) => {
+                    let window = self
+                        .state
+                        .config
+                        .lock()
+                        .unwrap()
+                        .app_config
+                        .game_window
+                        .clone();
+                    let candidates = selector_candidates(&self.state);
+                    match region_selector::select_region(None, &candidates).and_then(|selected| {
+                        selected
+                            .map(|r| {
+                                game_window::region_from_virtual(
+                                    &window, r.x, r.y, r.width, r.height,
+                                )
+                            })
+                            .transpose()
+                    }) {
+                        Ok(Some(region)) => {
                             info!(
-                                "✅ Região selecionada: {}x{} na posição ({}, {})",
-                                selected.width, selected.height, selected.x, selected.y
+                                "✅ Região selecionada: {}x{} na posição ({}, {}) {}",
+                                region.width,
+                                region.height,
+                                region.x,
+                                region.y,
+                                region_reference(&region)
                             );
 
                             let mut config = self.state.config.lock().unwrap();
-                            if let Err(e) = config.app_config.update_region(
-                                selected.x,
-                                selected.y,
-                                selected.width,
-                                selected.height,
-                            ) {
+                            let (x, y, width, height) =
+                                (region.x, region.y, region.width, region.height);
+                            if let Err(e) = config.app_config.update_region(region) {
                                 error!("❌ Erro ao salvar região: {}", e);
                             } else {
                                 info!("💾 Região salva no config.json!");
-                                config.region_x = selected.x;
-                                config.region_y = selected.y;
-                                config.region_width = selected.width;
-                                config.region_height = selected.height;
+                                config.region_x = x;
+                                config.region_y = y;
+                                config.region_width = width;
+                                config.region_height = height;
                             }
                         }
                         Ok(None) => info!("❌ Seleção cancelada"),
@@ -277,19 +374,41 @@ impl eframe::App for OverlayApp {
                     ));
 
                     // Abre o seletor de região
-                    match region_selector::select_region(Some("SELEÇÃO ÁREA DE LEGENDA")) {
-                        Ok(Some(selected)) => {
+                    let window = self
+                        .state
+                        .config
+                        .lock()
+                        .unwrap()
+                        .app_config
+                        .game_window
+                        .clone();
+                    let candidates = selector_candidates(&self.state);
+                    match region_selector::select_region(
+                        Some("SELEÇÃO ÁREA DE LEGENDA"),
+                        &candidates,
+                    )
+                    .and_then(|selected| {
+                        selected
+                            .map(|r| {
+                                game_window::region_from_virtual(
+                                    &window, r.x, r.y, r.width, r.height,
+                                )
+                            })
+                            .transpose()
+                    }) {
+                        Ok(Some(region)) => {
                             info!(
-                                "✅ Região de legendas selecionada: {}x{} na posição ({}, {})",
-                                selected.width, selected.height, selected.x, selected.y
+                                "✅ Região de legendas selecionada: {}x{} na posição ({}, {}) {}",
+                                region.width,
+                                region.height,
+                                region.x,
+                                region.y,
+                                region_reference(&region)
                             );
 
                             let mut config = self.state.config.lock().unwrap();
                             // Atualiza a região de legendas
-                            config.app_config.subtitle.region.x = selected.x;
-                            config.app_config.subtitle.region.y = selected.y;
-                            config.app_config.subtitle.region.width = selected.width;
-                            config.app_config.subtitle.region.height = selected.height;
+                            config.app_config.subtitle.region = region;
 
                             // Salva no arquivo
                             if let Err(e) = config.app_config.save() {
@@ -303,6 +422,33 @@ impl eframe::App for OverlayApp {
                     }
                 }
 
+                AppCommand::PickDisplayKeyColor | AppCommand::PickSubtitleKeyColor => {
+                    // Esconde o overlay temporariamente
+                    ctx.send_viewport_cmd(eframe::egui::ViewportCommand::InnerSize(
+                        eframe::egui::vec2(1.0, 1.0),
+                    ));
+
+                    match region_selector::pick_color(Some("CLIQUE NA COR DO TEXTO")) {
+                        Ok(Some(color)) => {
+                            // Vai para a cópia em edição (salva com o resto)
+                            if let Some(ref mut cfg) = self.settings_config {
+                                let preprocess = match command {
+                                    AppCommand::PickDisplayKeyColor => &mut cfg.display.preprocess,
+                                    _ => &mut cfg.subtitle.preprocess,
+                                };
+                                // Com pipeline de steps, vai para o step color_key
+                                let (mut colors, tolerance) = preprocess.effective_color_key();
+                                if !colors.contains(&color) {
+                                    colors.push(color);
+                                    preprocess.set_color_key(colors, tolerance);
+                                }
+                            }
+                        }
+                        Ok(None) => info!("❌ Conta-gotas cancelado"),
+                        Err(e) => error!("❌ Erro no conta-gotas: {}", e),
+                    }
+                }
+
                 AppCommand::OpenSettings => {
                     info!("⚙️  Entrando no modo configurações...");
 
@@ -337,14 +483,8 @@ impl eframe::App for OverlayApp {
             ctx.send_viewport_cmd(eframe::egui::ViewportCommand::InnerSize(
                 eframe::egui::vec2(520.0, 650.0),
             ));
-            let screen_w =
-                unsafe { winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_CXSCREEN) }
-                    as f32
-                    / self.state.dpi_scale;
-            let screen_h =
-                unsafe { winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_CYSCREEN) }
-                    as f32
-                    / self.state.dpi_scale;
+            let screen_w = screenshot::primary_screen_size().0 / self.state.dpi_scale;
+            let screen_h = screenshot::primary_screen_size().1 / self.state.dpi_scale;
             ctx.send_viewport_cmd(eframe::egui::ViewportCommand::OuterPosition(
                 eframe::egui::pos2((screen_w - 520.0) / 2.0, (screen_h - 650.0) / 2.0),
             ));
@@ -468,6 +608,19 @@ impl eframe::App for OverlayApp {
                                 ui.heading("🖥️ Display - Pré-processamento OCR");
                                 ui.add_space(10.0);
 
+                                game_window_controls(ui, &mut cfg.game_window);
+                                if !cfg.game_window.enabled {
+                                    monitor_combo(ui, "display_monitor", &mut cfg.region.monitor);
+                                }
+                                ui.checkbox(
+                                    &mut cfg.text_detect.auto_track_display,
+                                    "Procurar a caixa de diálogo automaticamente",
+                                )
+                                .on_hover_text(
+                                    "A cada tradução procura a caixa de diálogo na tela. A região salva só é usada se nenhuma for achada",
+                                );
+                                ui.add_space(5.0);
+
                                 ui.checkbox(
                                     &mut cfg.display.preprocess.enabled,
                                     "Pré-processamento ativado",
@@ -476,6 +629,13 @@ impl eframe::App for OverlayApp {
                                 if cfg.display.preprocess.enabled {
                                     ui.add_space(10.0);
                                     ui.indent("preprocess", |ui| {
+                                        preprocess_steps_notice(ui, &mut cfg.display.preprocess);
+                                        color_key_controls(
+                                            ui,
+                                            &mut cfg.display.preprocess,
+                                            &self.state.command_sender,
+                                            AppCommand::PickDisplayKeyColor,
+                                        );
                                         ui.checkbox(
                                             &mut cfg.display.preprocess.grayscale,
                                             "Escala de cinza",
@@ -496,20 +656,11 @@ impl eframe::App for OverlayApp {
                                             );
                                         });
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Threshold:");
-                                            let mut threshold =
-                                                cfg.display.preprocess.threshold as i32;
-                                            if ui
-                                                .add(eframe::egui::Slider::new(
-                                                    &mut threshold,
-                                                    0..=255,
-                                                ))
-                                                .changed()
-                                            {
-                                                cfg.display.preprocess.threshold = threshold as u8;
-                                            }
-                                        });
+                                        threshold_mode_controls(
+                                            ui,
+                                            "display_threshold_mode",
+                                            &mut cfg.display.preprocess,
+                                        );
 
                                         ui.horizontal(|ui| {
                                             ui.label("Blur:");
@@ -564,6 +715,13 @@ impl eframe::App for OverlayApp {
                                             &mut cfg.display.preprocess.save_debug_image,
                                             "Salvar imagem debug",
                                         );
+                                        ui.checkbox(
+                                            &mut cfg.display.preprocess.save_debug_stages,
+                                            "Salvar etapas (debug_dumps/)",
+                                        )
+                                        .on_hover_text(
+                                            "Uma pasta por captura com cada filtro e as caixas do OCR.\nSó com pré-processamento ligado e multi-variante desligado.",
+                                        );
                                     });
                                 }
                             }
@@ -572,6 +730,15 @@ impl eframe::App for OverlayApp {
                                 ui.heading("📺 Legendas");
                                 ui.add_space(10.0);
 
+                                if !cfg.game_window.enabled {
+                                    monitor_combo(
+                                        ui,
+                                        "subtitle_monitor",
+                                        &mut cfg.subtitle.region.monitor,
+                                    );
+                                }
+                                ui.add_space(5.0);
+
                                 ui.horizontal(|ui| {
                                     ui.label("Intervalo de captura:");
                                     let mut interval = cfg.subtitle.capture_interval_ms as i32;
@@ -586,6 +753,27 @@ impl eframe::App for OverlayApp {
                                     }
                                 });
 
+                                ui.horizontal(|ui| {
+                                    ui.label("Pular OCR se a região mudar menos que:");
+                                    ui.add(eframe::egui::Slider::new(
+                                        &mut cfg.subtitle.change_threshold,
+                                        0..=64,
+                                    ))
+                                    .on_hover_text(
+                                        "Diferença de cinza no bloco que mais mudou. 0 = sempre roda o OCR",
+                                    );
+                                });
+
+                                stabilization_controls(ui, &mut cfg.subtitle);
+
+                                ui.checkbox(
+                                    &mut cfg.text_detect.auto_track_subtitle,
+                                    "Procurar a legenda quando ela sumir",
+                                )
+                                .on_hover_text(
+                                    "Sem texto por algumas leituras, procura a legenda na tela de novo (para jogos que mudam a legenda de lugar)",
+                                );
+
                                 ui.horizontal(|ui| {
                                     ui.label("Máximo de linhas:");
                                     let mut lines = cfg.subtitle.max_lines as i32;
@@ -657,6 +845,13 @@ impl eframe::App for OverlayApp {
 
                                 if cfg.subtitle.preprocess.enabled {
                                     ui.indent("sub_preprocess", |ui| {
+                                        preprocess_steps_notice(ui, &mut cfg.subtitle.preprocess);
+                                        color_key_controls(
+                                            ui,
+                                            &mut cfg.subtitle.preprocess,
+                                            &self.state.command_sender,
+                                            AppCommand::PickSubtitleKeyColor,
+                                        );
                                         ui.checkbox(
                                             &mut cfg.subtitle.preprocess.grayscale,
                                             "Escala de cinza",
@@ -677,20 +872,11 @@ impl eframe::App for OverlayApp {
                                             );
                                         });
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Threshold:");
-                                            let mut threshold =
-                                                cfg.subtitle.preprocess.threshold as i32;
-                                            if ui
-                                                .add(eframe::egui::Slider::new(
-                                                    &mut threshold,
-                                                    0..=255,
-                                                ))
-                                                .changed()
-                                            {
-                                                cfg.subtitle.preprocess.threshold = threshold as u8;
-                                            }
-                                        });
+                                        threshold_mode_controls(
+                                            ui,
+                                            "subtitle_threshold_mode",
+                                            &mut cfg.subtitle.preprocess,
+                                        );
 
                                         ui.horizontal(|ui| {
                                             ui.label("Blur:");
@@ -745,6 +931,13 @@ impl eframe::App for OverlayApp {
                                             &mut cfg.subtitle.preprocess.save_debug_image,
                                             "Salvar debug",
                                         );
+                                        ui.checkbox(
+                                            &mut cfg.subtitle.preprocess.save_debug_stages,
+                                            "Salvar etapas (debug_dumps/)",
+                                        )
+                                        .on_hover_text(
+                                            "Uma pasta por captura com cada filtro e as caixas do OCR.\nSó com pré-processamento ligado e multi-variante desligado.",
+                                        );
                                     });
                                 }
                             }
@@ -911,6 +1104,24 @@ impl eframe::App for OverlayApp {
                                                 }
                                             });
                                     });
+                                    ui.horizontal(|ui| {
+                                        ui.label("   Relatório de bug:");
+                                        eframe::egui::ComboBox::from_id_source("hotkey_dump")
+                                            .selected_text(&cfg.hotkeys.dump_history)
+                                            .show_ui(ui, |ui: &mut eframe::egui::Ui| {
+                                                for tecla in &teclas_disponiveis {
+                                                    ui.selectable_value(
+                                                        &mut cfg.hotkeys.dump_history,
+                                                        tecla.to_string(),
+                                                        *tecla,
+                                                    );
+                                                }
+                                            });
+                                    })
+                                    .response
+                                    .on_hover_text(
+                                        "Grava as últimas capturas (imagens, OCR e tradução) numa pasta em bug_reports/",
+                                    );
                                 });
 
                                 ui.add_space(15.0);
@@ -964,6 +1175,35 @@ impl eframe::App for OverlayApp {
                                             &mut cfg.translation.target_language,
                                         );
                                     });
+
+                                    ui.horizontal(|ui| {
+                                        ui.label("   Idioma do OCR:");
+                                        ui.text_edit_singleline(&mut cfg.ocr.language);
+                                    });
+                                    ui.label(
+                                        "   ℹ️ Vazio = igual ao idioma origem, \"auto\" = idiomas do Windows",
+                                    );
+
+                                    let orientations = ["auto", "horizontal", "vertical"];
+                                    ui.horizontal(|ui| {
+                                        ui.label("   Orientação do texto:");
+                                        eframe::egui::ComboBox::from_id_source("ocr_orientation")
+                                            .selected_text(&cfg.ocr.orientation)
+                                            .show_ui(ui, |ui| {
+                                                for o in &orientations {
+                                                    ui.selectable_value(
+                                                        &mut cfg.ocr.orientation,
+                                                        o.to_string(),
+                                                        *o,
+                                                    );
+                                                }
+                                            });
+                                    });
+
+                                    ui.checkbox(
+                                        &mut cfg.ocr.multi_variant,
+                                        "   OCR multi-variante (várias versões da imagem + votação)",
+                                    );
                                 });
 
                                 ui.add_space(10.0);
@@ -1133,16 +1373,22 @@ impl eframe::App for OverlayApp {
         // MODO LEGENDA: Exibe histórico de legendas acima da região
         // ====================================================================
         if subtitle_mode_active && has_subtitles {
-            // Pega a região de legenda do config
-            let (_sub_x, sub_y, _sub_w, _sub_h) = {
+            // Pega a região de legenda e a área onde ela está (o monitor,
+            // ou a janela do jogo, que o overlay acompanha)
+            let target = {
                 let config = self.state.config.lock().unwrap();
-                (
-                    config.app_config.subtitle.region.x as f32,
-                    config.app_config.subtitle.region.y as f32,
-                    config.app_config.subtitle.region.width as f32,
-                    config.app_config.subtitle.region.height as f32,
+                game_window::resolve_region(
+                    &config.app_config.game_window,
+                    &config.app_config.subtitle.region,
                 )
+                .ok()
             };
+            let sub_y = target.map(|t| t.virtual_origin().1 as f32).unwrap_or(0.0);
+            let (monitor_x, monitor_width) = target
+                .and_then(|t| t.bounds)
+                .map(|area| (area.x as f32, area.width as f32))
+                .unwrap_or((0.0, screenshot::primary_screen_size().0));
+            let scale = self.pixels_per_point(ctx);
 
             // Pega configurações de fonte (específica de legendas) e fundo
             let (
@@ -1153,6 +1399,7 @@ impl eframe::App for OverlayApp {
                 outline_enabled,
                 outline_width,
                 outline_color,
+                speaker_color,
             ) = {
                 let config = self.state.config.lock().unwrap();
                 (
@@ -1163,6 +1410,7 @@ impl eframe::App for OverlayApp {
                     config.app_config.subtitle.font.outline.enabled,
                     config.app_config.subtitle.font.outline.width,
                     config.app_config.subtitle.font.outline.color,
+                    config.app_config.speaker.color,
                 )
             };
 
@@ -1184,14 +1432,11 @@ impl eframe::App for OverlayApp {
 
             // Calcula altura dinâmica baseada no conteúdo real
             let font_id_calc = eframe::egui::FontId::proportional(font_size);
-            let screen_width_calc =
-                unsafe { winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_CXSCREEN) }
-                    as f32;
-            let max_width_calc = (screen_width_calc - 100.0) / self.state.dpi_scale - 100.0;
+            let max_width_calc = (monitor_width - 100.0) / scale - 100.0;
 
             let mut calculated_height = 10.0; // Margem superior
             for entry in &visible_history {
-                let text = format!("- {}", entry.translated);
+                let text = format!("- {}", entry.display_text());
                 let galley = ctx.fonts(|f| {
                     f.layout(
                         text,
@@ -1207,15 +1452,11 @@ impl eframe::App for OverlayApp {
             let overlay_height = calculated_height.max(50.0); // Mínimo de 50px
 
             // Posiciona o overlay ACIMA da região de legenda
-            // Usa largura TOTAL da tela para a caixa de tradução
-            let scale = self.state.dpi_scale;
-            let screen_width =
-                unsafe { winapi::um::winuser::GetSystemMetrics(winapi::um::winuser::SM_CXSCREEN) }
-                    as f32;
+            // Usa largura TOTAL do monitor (ou da janela do jogo) para a caixa de tradução
             // Margem lateral (pixels físicos) - ajuste se quiser mais/menos borda
             let side_margin = 50.0;
-            let overlay_width = (screen_width - side_margin * 2.0) / scale;
-            let overlay_x = side_margin / scale;
+            let overlay_width = (monitor_width - side_margin * 2.0) / scale;
+            let overlay_x = (monitor_x + side_margin) / scale;
             // overlay_height já está em lógico (calculado pelo galley)
             let overlay_y = sub_y / scale - overlay_height - 10.0;
 
@@ -1256,12 +1497,18 @@ impl eframe::App for OverlayApp {
                         font_color[2],
                         font_color[3],
                     );
+                    let speaker_color = eframe::egui::Color32::from_rgba_unmultiplied(
+                        speaker_color[0],
+                        speaker_color[1],
+                        speaker_color[2],
+                        speaker_color[3],
+                    );
 
                     // Renderiza cada legenda do histórico
                     let mut y_offset = 5.0;
 
                     for entry in &visible_history {
-                        let text = format!("- {}", entry.translated);
+                        let text = format!("- {}", entry.display_text());
 
                         // Centraliza horizontalmente: calcula largura do texto
                         // e posiciona no centro do overlay
@@ -1276,12 +1523,33 @@ impl eframe::App for OverlayApp {
                         let text_pos = eframe::egui::pos2(center_x.max(10.0), y_offset);
 
                         // Calcula o galley para obter a altura real
-                        let galley = ui.painter().layout(
-                            text.clone(),
-                            font_id.clone(),
-                            text_color,
-                            max_width,
-                        );
+                        // (o nome do falante sai com cor própria)
+                        let galley = match &entry.speaker {
+                            Some(speaker) => {
+                                let text_format = eframe::egui::TextFormat {
+                                    font_id: font_id.clone(),
+                                    color: text_color,
+                                    ..Default::default()
+                                };
+                                let speaker_format = eframe::egui::TextFormat {
+                                    color: speaker_color,
+                                    ..text_format.clone()
+                                };
+
+                                let mut job = eframe::egui::text::LayoutJob::default();
+                                job.wrap.max_width = max_width;
+                                job.append("- ", 0.0, text_format.clone());
+                                job.append(&format!("{}:", speaker), 0.0, speaker_format);
+                                job.append(&format!(" {}", entry.translated), 0.0, text_format);
+                                ui.painter().layout_job(job)
+                            }
+                            None => ui.painter().layout(
+                                text.clone(),
+                                font_id.clone(),
+                                text_color,
+                                max_width,
+                            ),
+                        };
                         let text_height = galley.rect.height();
 
                         // Desenha contorno se habilitado OU se não tem fundo
@@ -1378,9 +1646,9 @@ impl eframe::App for OverlayApp {
                     } else {
                         // Adiciona margem
                         let margin = 20.0;
-                        let scale = self.state.dpi_scale;
-                        let overlay_x = (min_x - margin).max(0.0) as f32 / scale;
-                        let overlay_y = (min_y - margin).max(0.0) as f32 / scale;
+                        let scale = self.pixels_per_point(ctx);
+                        let overlay_x = (min_x - margin) as f32 / scale;
+                        let overlay_y = (min_y - margin) as f32 / scale;
                         let overlay_width = (max_x - min_x + margin * 2.0) as f32 / scale;
                         let overlay_height = (max_y - min_y + margin * 2.0 + 50.0) as f32 / scale;
 
@@ -1416,12 +1684,19 @@ impl eframe::App for OverlayApp {
                                     let text_pos = eframe::egui::pos2(text_x, text_y);
 
                                     // Largura máxima baseada na largura original do texto
-                                    let max_width = (item.width as f32 / scale * 1.5).max(200.0);
+                                    // Texto vertical: a tradução é horizontal, então
+                                    // usa parte da altura da coluna como largura
+                                    let base_width = if item.vertical {
+                                        item.width.max(item.height / 2.0)
+                                    } else {
+                                        item.width
+                                    };
+                                    let max_width = (base_width as f32 / scale * 1.5).max(200.0);
 
                                     // Se show_background, desenha fundo atrás do texto
                                     if show_background {
                                         let galley = ui.painter().layout(
-                                            item.translated.clone(),
+                                            item.display_text(),
                                             font_id.clone(),
                                             eframe::egui::Color32::WHITE,
                                             max_width,
@@ -1467,7 +1742,7 @@ impl eframe::App for OverlayApp {
                                                 let offset_pos =
                                                     text_pos + eframe::egui::vec2(dx, dy);
                                                 let outline_galley = ui.painter().layout(
-                                                    item.translated.clone(),
+                                                    item.display_text(),
                                                     font_id.clone(),
                                                     color,
                                                     max_width,
@@ -1483,7 +1758,7 @@ impl eframe::App for OverlayApp {
 
                                     // Desenha o texto principal
                                     let galley = ui.painter().layout(
-                                        item.translated.clone(),
+                                        item.display_text(),
                                         font_id.clone(),
                                         eframe::egui::Color32::WHITE,
                                         max_width,
@@ -1500,7 +1775,7 @@ impl eframe::App for OverlayApp {
                     // ========================================================
                     // MODO REGIÃO: Texto combinado em bloco único
                     // ========================================================
-                    let scale = self.state.dpi_scale;
+                    let scale = self.pixels_per_point(ctx);
                     let overlay_x = region.x as f32 / scale;
                     let overlay_y = region.y as f32 / scale;
                     let overlay_width = region.width as f32 / scale;
@@ -1518,13 +1793,13 @@ impl eframe::App for OverlayApp {
                     eframe::egui::CentralPanel::default()
                         .frame(eframe::egui::Frame::none().fill(eframe::egui::Color32::TRANSPARENT))
                         .show(ctx, |ui| {
-                            // Junta todas as traduções em um texto só
+                            // Junta as traduções, um bloco (caixa de diálogo) por parágrafo
                             let combined_text: String = items
                                 .iter()
                                 .filter(|item| item.original != item.translated)
-                                .map(|item| item.translated.as_str())
-                                .collect::<Vec<&str>>()
-                                .join(" ");
+                                .map(|item| item.display_text())
+                                .collect::<Vec<String>>()
+                                .join("\n");
 
                             if !combined_text.is_empty() {
                                 // Se show_background = true, desenha o fundo preto
@@ -1619,6 +1894,404 @@ impl eframe::App for OverlayApp {
     }
 }
 
+/// Aviso/conversão do pipeline de steps nas abas de pré-processamento
+///
+/// Com `steps` definido os controles simples são ignorados, então a aba
+/// avisa e oferece voltar para eles. Sem `steps`, oferece converter os
+/// campos atuais num pipeline (editável no config.json).
+fn preprocess_steps_notice(ui: &mut eframe::egui::Ui, preprocess: &mut config::PreprocessConfig) {
+    match &preprocess.steps {
+        Some(steps) => {
+            ui.colored_label(
+                eframe::egui::Color32::YELLOW,
+                format!(
+                    "⚠ Pipeline com {} steps no config.json (controles abaixo ignorados, exceto as cores do texto)",
+                    steps.len()
+                ),
+            );
+            if ui
+                .button("Descartar pipeline e usar os controles")
+                .clicked()
+            {
+                preprocess.steps = None;
+            }
+        }
+        None => {
+            if ui
+                .button("Converter em pipeline (steps)")
+                .on_hover_text("Grava os filtros atuais como lista ordenada no config.json")
+                .clicked()
+            {
+                preprocess.steps = Some(preprocess.pipeline());
+            }
+        }
+    }
+    ui.add_space(5.0);
+}
+
+/// Escolha do monitor de uma região
+///
+/// Trocar o monitor mantém x/y (relativos ao monitor); o seletor de
+/// região já grava o monitor onde a seleção foi feita.
+fn monitor_combo(ui: &mut eframe::egui::Ui, id_source: &str, monitor: &mut Option<u32>) {
+    let monitors = screenshot::monitors().unwrap_or_default();
+    if monitors.len() < 2 && monitor.is_none() {
+        return;
+    }
+
+    let selected = match *monitor {
+        Some(index) => monitors
+            .iter()
+            .find(|m| m.index == index)
+            .map(|m| m.label())
+            .unwrap_or_else(|| format!("{}: desconectado", index + 1)),
+        None => "Principal".to_string(),
+    };
+
+    ui.horizontal(|ui| {
+        ui.label("Monitor da região:");
+        eframe::egui::ComboBox::from_id_source(id_source)
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(monitor, None, "Principal");
+                for m in &monitors {
+                    ui.selectable_value(monitor, Some(m.index), m.label());
+                }
+            });
+    });
+}
+
+/// A que a região é relativa, para os logs ("do monitor 2", "da janela do jogo")
+fn region_reference(region: &config::RegionConfig) -> String {
+    match (region.window_size, region.monitor) {
+        (Some([width, height]), _) => format!("da janela do jogo ({}x{})", width, height),
+        (None, Some(index)) => format!("do monitor {}", index + 1),
+        (None, None) => "do monitor principal".to_string(),
+    }
+}
+
+/// Grava as etapas do pré-processamento numa thread à parte
+///
+/// Salvar os PNGs demora mais que o OCR; a tradução não espera.
+fn spawn_stage_dump(
+    state: &AppState,
+    mode: &'static str,
+    stages: Vec<screenshot::DebugStage>,
+    ocr: ocr::OcrResultWithPositions,
+) {
+    let config = state.config.lock().unwrap().app_config.debug_dump.clone();
+    thread::spawn(
+        move || match debug_dump::save_stages(&config, mode, &stages, &ocr) {
+            Ok(folder) => trace!("🧪 Etapas de debug salvas em {}", folder.display()),
+            Err(e) => error!("❌ Erro ao salvar etapas de debug: {}", e),
+        },
+    );
+}
+
+/// Caixas de texto achadas na tela, para o seletor de região
+///
+/// Com janela presa, procura só na janela do jogo; senão, em todos os
+/// monitores. Se a detecção falhar o seletor só abre sem sugestões.
+fn selector_candidates(state: &AppState) -> Vec<region_selector::SelectedRegion> {
+    let (window, detect_config) = {
+        let config = state.config.lock().unwrap();
+        (
+            config.app_config.game_window.clone(),
+            config.app_config.text_detect.clone(),
+        )
+    };
+
+    let monitors: Vec<Option<u32>> = if window.enabled {
+        vec![None]
+    } else {
+        screenshot::monitors()
+            .map(|list| list.iter().map(|m| Some(m.index)).collect())
+            .unwrap_or_else(|_| vec![None])
+    };
+
+    let mut candidates = Vec::new();
+    for monitor in monitors {
+        let detection = {
+            let mut source = state.capture_source.lock().unwrap();
+            text_regions::detect_area(source.as_mut(), &window, monitor, &detect_config)
+        };
+        match detection {
+            Ok(detection) => {
+                candidates.extend(detection.regions.iter().map(|region| {
+                    let (x, y) = detection.virtual_origin(region);
+                    region_selector::SelectedRegion {
+                        x,
+                        y,
+                        width: region.width,
+                        height: region.height,
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️  Falha ao procurar caixas de texto: {}", e),
+        }
+    }
+
+    candidates
+}
+
+/// Janela do jogo: as regiões passam a ser relativas à área cliente dela
+///
+/// A lista mostra as janelas abertas; escolher uma preenche o processo
+/// (e o título, que dá para apagar se o jogo muda o título sozinho).
+fn game_window_controls(ui: &mut eframe::egui::Ui, window: &mut config::GameWindowConfig) {
+    ui.checkbox(&mut window.enabled, "Prender regiões à janela do jogo");
+    if !window.enabled {
+        return;
+    }
+
+    ui.indent("game_window", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Processo:");
+            ui.text_edit_singleline(&mut window.process_name);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Título contém:");
+            ui.text_edit_singleline(&mut window.window_title);
+        });
+
+        eframe::egui::ComboBox::from_id_source("game_window_list")
+            .selected_text("Escolher janela aberta...")
+            .show_ui(ui, |ui| {
+                for found in game_window::list_windows() {
+                    let label = format!("{} - {}", found.process, found.title);
+                    if ui.selectable_label(false, label).clicked() {
+                        window.process_name = found.process;
+                        window.window_title = found.title;
+                    }
+                }
+            });
+
+        match game_window::find_client_area(window) {
+            Ok(area) => ui.label(format!(
+                "✅ Janela em ({}, {}), área {}x{}",
+                area.x, area.y, area.width, area.height
+            )),
+            Err(e) => ui.colored_label(eframe::egui::Color32::YELLOW, format!("⚠ {}", e)),
+        };
+        ui.label("Selecione as regiões de novo depois de ligar (x/y passam a ser da janela).");
+    });
+}
+
+/// Lista de cores-chave (color key) com tolerância e conta-gotas
+///
+/// O conta-gotas é um comando para o loop principal, que esconde o
+/// overlay e abre o seletor em modo de clique único. Com pipeline de
+/// steps, os controles editam o step color_key (os campos simples
+/// seriam ignorados).
+fn color_key_controls(
+    ui: &mut eframe::egui::Ui,
+    preprocess: &mut config::PreprocessConfig,
+    command_sender: &Sender<AppCommand>,
+    pick_command: AppCommand,
+) {
+    let (mut colors, mut tolerance) = preprocess.effective_color_key();
+    let (original_colors, original_tolerance) = (colors.clone(), tolerance);
+
+    ui.horizontal(|ui| {
+        ui.label(if preprocess.steps.is_some() {
+            "Cores do texto (step color_key):"
+        } else {
+            "Cores do texto:"
+        });
+
+        let mut remove = None;
+        for (index, color) in colors.iter_mut().enumerate() {
+            ui.color_edit_button_srgb(color);
+            if ui.small_button("✖").on_hover_text("Remover cor").clicked() {
+                remove = Some(index);
+            }
+        }
+        if let Some(index) = remove {
+            colors.remove(index);
+        }
+
+        if ui
+            .button("🎨 Conta-gotas")
+            .on_hover_text("Clique na cor da legenda na tela")
+            .clicked()
+        {
+            if let Err(e) = command_sender.send(pick_command) {
+                error!("❌ Erro ao enviar comando: {}", e);
+            }
+        }
+    });
+
+    if !colors.is_empty() {
+        ui.horizontal(|ui| {
+            ui.label("Tolerância de cor:");
+            ui.add(eframe::egui::Slider::new(&mut tolerance, 5.0..=200.0));
+        });
+        ui.label("   Só os pixels dessas cores seguem para o OCR (vazio = desativado)");
+    }
+
+    if colors != original_colors || tolerance != original_tolerance {
+        preprocess.set_color_key(colors, tolerance);
+    }
+}
+
+/// Controles de binarização (modo + parâmetros do modo escolhido)
+///
+/// O slider de threshold só aparece no modo fixo; os modos adaptativos
+/// mostram a janela e o parâmetro próprio (k do Sauvola, margem da média).
+fn threshold_mode_controls(
+    ui: &mut eframe::egui::Ui,
+    id_source: &str,
+    preprocess: &mut config::PreprocessConfig,
+) {
+    use config::ThresholdMode;
+
+    ui.horizontal(|ui| {
+        ui.label("Binarização:");
+        eframe::egui::ComboBox::from_id_source(id_source)
+            .selected_text(threshold_mode_label(preprocess.threshold_mode))
+            .show_ui(ui, |ui| {
+                for mode in [
+                    ThresholdMode::Fixed,
+                    ThresholdMode::Otsu,
+                    ThresholdMode::Sauvola,
+                    ThresholdMode::LocalMean,
+                ] {
+                    ui.selectable_value(
+                        &mut preprocess.threshold_mode,
+                        mode,
+                        threshold_mode_label(mode),
+                    );
+                }
+            });
+    });
+
+    match preprocess.threshold_mode {
+        ThresholdMode::Fixed => {
+            ui.horizontal(|ui| {
+                ui.label("Threshold:");
+                let mut threshold = preprocess.threshold as i32;
+                if ui
+                    .add(eframe::egui::Slider::new(&mut threshold, 0..=255))
+                    .changed()
+                {
+                    preprocess.threshold = threshold as u8;
+                }
+            });
+        }
+        ThresholdMode::Otsu => {
+            ui.label("   Nível calculado a cada captura pelo histograma");
+        }
+        ThresholdMode::Sauvola | ThresholdMode::LocalMean => {
+            ui.horizontal(|ui| {
+                ui.label("Janela:");
+                ui.add(
+                    eframe::egui::Slider::new(&mut preprocess.adaptive_window, 3..=101)
+                        .suffix("px"),
+                );
+            });
+
+            if preprocess.threshold_mode == ThresholdMode::Sauvola {
+                ui.horizontal(|ui| {
+                    ui.label("k:");
+                    ui.add(eframe::egui::Slider::new(
+                        &mut preprocess.sauvola_k,
+                        0.05..=0.8,
+                    ));
+                });
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Margem:");
+                    ui.add(eframe::egui::Slider::new(
+                        &mut preprocess.local_mean_offset,
+                        -30.0..=60.0,
+                    ));
+                });
+            }
+            ui.label("   Janela ≈ 2× a altura das letras; bom para fundos com degradê");
+        }
+    }
+}
+
+/// Controles de estabilização das legendas (métrica e thresholds)
+///
+/// Trocar a métrica volta o threshold para o recomendado dela: os
+/// valores de uma métrica não servem para a outra.
+fn stabilization_controls(ui: &mut eframe::egui::Ui, subtitle: &mut config::SubtitleConfig) {
+    use config::SimilarityMetric;
+
+    ui.horizontal(|ui| {
+        ui.label("Comparar leituras por:");
+        let before = subtitle.similarity_metric;
+        eframe::egui::ComboBox::from_id_source("subtitle_similarity_metric")
+            .selected_text(similarity_metric_label(subtitle.similarity_metric))
+            .show_ui(ui, |ui| {
+                for metric in [
+                    SimilarityMetric::Levenshtein,
+                    SimilarityMetric::Charset,
+                    SimilarityMetric::Jaccard,
+                ] {
+                    ui.selectable_value(
+                        &mut subtitle.similarity_metric,
+                        metric,
+                        similarity_metric_label(metric),
+                    );
+                }
+            });
+        if subtitle.similarity_metric != before {
+            subtitle.similarity_threshold = subtitle.similarity_metric.default_threshold();
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Similaridade mínima:");
+        ui.add(eframe::egui::Slider::new(
+            &mut subtitle.similarity_threshold,
+            0.3..=1.0,
+        ))
+        .on_hover_text(
+            "Acima disso duas leituras são a mesma legenda. Mais alto = mais sensível a mudanças (e a erros do OCR)",
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Leituras iguais para confirmar:");
+        ui.add(eframe::egui::Slider::new(&mut subtitle.stable_count, 1..=6))
+            .on_hover_text("1 = traduz na primeira leitura (sem debounce)");
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Mínimo de caracteres:");
+        ui.add(eframe::egui::Slider::new(
+            &mut subtitle.min_text_length,
+            1..=20,
+        ));
+        ui.label("CJK:");
+        ui.add(eframe::egui::Slider::new(
+            &mut subtitle.min_cjk_text_length,
+            1..=10,
+        ));
+    });
+}
+
+/// Nome da métrica de similaridade na interface
+fn similarity_metric_label(metric: config::SimilarityMetric) -> &'static str {
+    match metric {
+        config::SimilarityMetric::Levenshtein => "Levenshtein (letras trocadas)",
+        config::SimilarityMetric::Charset => "Caracteres em comum",
+        config::SimilarityMetric::Jaccard => "Palavras em comum (Jaccard)",
+    }
+}
+
+/// Nome do modo de binarização na interface
+fn threshold_mode_label(mode: config::ThresholdMode) -> &'static str {
+    match mode {
+        config::ThresholdMode::Fixed => "Fixo",
+        config::ThresholdMode::Otsu => "Otsu (automático)",
+        config::ThresholdMode::Sauvola => "Sauvola (adaptativo)",
+        config::ThresholdMode::LocalMean => "Média local (adaptativo)",
+    }
+}
+
 // ============================================================================
 // THREAD DE HOTKEYS (roda em background)
 // ============================================================================
@@ -1659,6 +2332,33 @@ fn start_hotkey_thread(state: AppState) {
                         }
                     }
 
+                    hotkey::HotkeyAction::DumpHistory => {
+                        info!("");
+                        info!("🐞 ============================================");
+                        info!("🐞 GRAVANDO RELATÓRIO DE CAPTURAS");
+                        info!("🐞 ============================================");
+
+                        // PNGs de tela cheia demoram: grava em outra thread
+                        let state_clone = state.clone();
+                        thread::spawn(move || {
+                            let config = state_clone.config.lock().unwrap().app_config.clone();
+                            match state_clone.history.dump(
+                                Path::new(&config.history.folder),
+                                config.history.dump_count,
+                                &config,
+                            ) {
+                                Ok((folder, count)) => {
+                                    info!(
+                                        "✅ {} captura(s) gravada(s) em {}",
+                                        count,
+                                        folder.display()
+                                    )
+                                }
+                                Err(e) => error!("❌ Erro ao gravar relatório: {}", e),
+                            }
+                        });
+                    }
+
                     hotkey::HotkeyAction::HideTranslation => {
                         info!("");
                         info!("🙈 ============================================");
@@ -1821,6 +2521,34 @@ fn start_config_watcher(state: AppState) {
 // ============================================================================
 
 fn process_translation_blocking(state: &AppState, action: hotkey::HotkeyAction) -> Result<()> {
+    if !matches!(
+        action,
+        hotkey::HotkeyAction::TranslateRegion | hotkey::HotkeyAction::TranslateFullScreen
+    ) {
+        anyhow::bail!("Esta ação não deveria chamar process_translation");
+    }
+
+    // Onde capturar: a região do config (relativa ao monitor ou à janela
+    // do jogo). Na tela cheia, a janela do jogo inteira, ou None = o
+    // monitor da região. Antes de esconder o overlay: janela minimizada
+    // ou fechada é erro e não há o que capturar.
+    let (target, monitor) = {
+        let config = state.config.lock().unwrap();
+        let window = &config.app_config.game_window;
+        let target = match action {
+            hotkey::HotkeyAction::TranslateFullScreen => game_window::resolve_window(window)?,
+            _ => Some(game_window::resolve_region(
+                window,
+                &config.app_config.region,
+            )?),
+        };
+        let monitor = match &target {
+            Some(target) => target.monitor,
+            None => screenshot::monitor(config.app_config.region.monitor).ok(),
+        };
+        (target, monitor)
+    };
+
     // === ESCONDE O OVERLAY ANTES DE CAPTURAR ===
     {
         let mut hidden = state.overlay_hidden.lock().unwrap();
@@ -1844,114 +2572,155 @@ fn process_translation_blocking(state: &AppState, action: hotkey::HotkeyAction)
         config.app_config.display.preprocess.clone()
     };
 
-    // No modo tela cheia, força upscale 1.0 (desativado)
+    // Backend de OCR escolhido no config (criado só na primeira vez)
+    let (ocr_config, ocr_language) = {
+        let config = state.config.lock().unwrap();
+        (
+            config.app_config.ocr.clone(),
+            config.app_config.ocr_language(),
+        )
+    };
+    let ocr_backend = state.ocr_backend(&ocr_config, &ocr_language)?;
+
+    // No modo tela cheia o upscale é ignorado
     // porque a imagem já é grande e upscale deixaria muito lento
-    let effective_upscale = if action == hotkey::HotkeyAction::TranslateFullScreen {
-        1.0
+    let allow_upscale = action != hotkey::HotkeyAction::TranslateFullScreen;
+
+    // Captura e overlay usam a mesma origem (resolvida no começo)
+    let (monitor_x, monitor_y) = monitor.map(|m| (m.x, m.y)).unwrap_or((0, 0));
+    let (origin_x, origin_y) = match &target {
+        Some(target) => target.virtual_origin(),
+        None => (monitor_x, monitor_y),
+    };
+
+    // Rastreamento automático: a caixa de diálogo é procurada a cada
+    // captura (a região do config só vale se nenhuma caixa for achada)
+    let auto_track = action == hotkey::HotkeyAction::TranslateRegion
+        && state
+            .config
+            .lock()
+            .unwrap()
+            .app_config
+            .text_detect
+            .auto_track_display;
+    let tracked = if auto_track {
+        match track_dialogue(state) {
+            Ok(Some(tracked)) => Some(tracked),
+            Ok(None) => {
+                info!("   🟩 Nenhuma caixa de diálogo achada, usando a região do config");
+                None
+            }
+            Err(e) => {
+                warn!("⚠️  Falha ao procurar caixa de diálogo: {}", e);
+                None
+            }
+        }
     } else {
-        preprocess_config.upscale
+        None
+    };
+
+    let capture_label = if use_memory { "MEMÓRIA" } else { "ARQUIVO" };
+    let (image, (origin_x, origin_y), capture_size) = match tracked {
+        Some((image, origin)) => {
+            let size = (image.width(), image.height());
+            (image, origin, size)
+        }
+        None => {
+            let mut source = state.capture_source.lock().unwrap();
+            let image = match &target {
+                Some(target) => {
+                    info!(
+                        "   🎯 Região: {}x{} em ({}, {}) [{}]",
+                        target.width, target.height, target.x, target.y, capture_label
+                    );
+                    source.capture_region(
+                        target.monitor_index(),
+                        target.x,
+                        target.y,
+                        target.width,
+                        target.height,
+                    )?
+                }
+                None => {
+                    info!("   🖥️  Tela inteira [{}]", capture_label);
+                    source.capture_screen(monitor.map(|m| m.index))?
+                }
+            };
+            let size = target.map(|t| (t.width, t.height)).unwrap_or_default();
+            (image, (origin_x, origin_y), size)
+        }
     };
 
+    // Histórico para relatório de bug (cópia da captura só se ligado)
+    let history_config = state.config.lock().unwrap().app_config.history.clone();
+    let history_capture = (history_config.size > 0).then(|| image.clone());
+    let mut history_preprocessed = None;
+    let mut debug_stages = None;
+
     // OCR result vai ser preenchido de acordo com o modo
     let mut ocr_result = if use_memory {
         // ====================================================================
         // MODO MEMÓRIA (RÁPIDO) - Não salva arquivo em disco
         // ====================================================================
-        let image = match action {
-            hotkey::HotkeyAction::TranslateRegion => {
-                let (x, y, w, h) = {
-                    let config = state.config.lock().unwrap();
-                    (
-                        config.region_x,
-                        config.region_y,
-                        config.region_width,
-                        config.region_height,
-                    )
-                };
-                info!("   🎯 Região: {}x{} em ({}, {}) [MEMÓRIA]", w, h, x, y);
-                screenshot::capture_region_to_memory(x, y, w, h)?
-            }
-            hotkey::HotkeyAction::TranslateFullScreen => {
-                info!("   🖥️  Tela inteira [MEMÓRIA]");
-                screenshot::capture_screen_to_memory()?
-            }
-            hotkey::HotkeyAction::SelectRegion
-            | hotkey::HotkeyAction::SelectSubtitleRegion
-            | hotkey::HotkeyAction::ToggleSubtitleMode
-            | hotkey::HotkeyAction::HideTranslation
-            | hotkey::HotkeyAction::OpenSettings => {
-                anyhow::bail!("Esta ação não deveria chamar process_translation")
-            }
-        };
 
         // Aplica pré-processamento se habilitado
-        let processed_image = if preprocess_config.enabled {
-            screenshot::preprocess_image(
-                &image,
-                preprocess_config.grayscale,
-                preprocess_config.invert,
-                preprocess_config.contrast,
-                preprocess_config.threshold,
-                preprocess_config.save_debug_image,
-                effective_upscale,
-                preprocess_config.blur, // Blur gaussiano // Usa effective_upscale (1.0 para tela cheia)
-                preprocess_config.dilate,
-                preprocess_config.erode,
-                preprocess_config.edge_detection,
-            )
+        // (no modo multi-variante cada variante faz o seu)
+        let processed_image = if preprocess_config.enabled && !ocr_config.multi_variant {
+            let processed = if preprocess_config.save_debug_stages {
+                let (processed, stages) =
+                    screenshot::preprocess_image_stages(&image, &preprocess_config, allow_upscale);
+                debug_stages = Some(stages);
+                processed
+            } else {
+                screenshot::preprocess_image(&image, &preprocess_config, allow_upscale)
+            };
+            if history_capture.is_some() {
+                history_preprocessed = Some(processed.clone());
+            }
+            processed
         } else {
             image
         };
 
         info!("✅ Screenshot capturada em memória!");
-        info!("🔍 [2/4] Executando OCR...");
-        ocr::extract_text_from_memory(&processed_image)?
+        info!("🔍 [2/4] Executando OCR ({})...", ocr_backend.name());
+        if ocr_config.multi_variant {
+            ocr::recognize_variants(
+                ocr_backend.as_ref(),
+                &processed_image,
+                &ocr_config.variants,
+                ocr_config.min_votes,
+                allow_upscale,
+            )?
+        } else {
+            ocr_backend.recognize(&processed_image)?
+        }
     } else {
         // ====================================================================
         // MODO ARQUIVO (DEBUG) - Salva screenshot.png em disco
         // ====================================================================
         let screenshot_path = PathBuf::from("screenshot.png");
 
-        match action {
-            hotkey::HotkeyAction::TranslateRegion => {
-                let (x, y, w, h) = {
-                    let config = state.config.lock().unwrap();
-                    (
-                        config.region_x,
-                        config.region_y,
-                        config.region_width,
-                        config.region_height,
-                    )
-                };
-                info!("   🎯 Região: {}x{} em ({}, {}) [ARQUIVO]", w, h, x, y);
-                screenshot::capture_region(&screenshot_path, x, y, w, h)?;
-            }
-            hotkey::HotkeyAction::TranslateFullScreen => {
-                info!("   🖥️  Tela inteira [ARQUIVO]");
-                screenshot::capture_screen(&screenshot_path)?;
-            }
-            hotkey::HotkeyAction::SelectRegion => {
-                anyhow::bail!("SelectRegion não deveria chamar process_translation")
-            }
-            hotkey::HotkeyAction::SelectSubtitleRegion
-            | hotkey::HotkeyAction::ToggleSubtitleMode
-            | hotkey::HotkeyAction::HideTranslation
-            | hotkey::HotkeyAction::OpenSettings => {
-                unreachable!("Esta ação não deveria chamar process_translation")
-            }
-        };
+        image
+            .save(&screenshot_path)
+            .context("Falha ao salvar screenshot")?;
 
         info!("✅ Screenshot capturada!");
-        info!("🔍 [2/4] Executando OCR...");
-        ocr::extract_text_with_positions(&screenshot_path)?
+        info!("🔍 [2/4] Executando OCR ({})...", ocr_backend.name());
+        ocr_backend.recognize_file(&screenshot_path)?
     };
 
-    if ocr_result.lines.is_empty() {
-        info!("⚠️  Nenhum texto detectado!");
-        return Ok(());
-    }
+    // Descarta linhas com confiança baixa demais (lixo do OCR)
+    ocr_result.retain_confident(ocr_config.min_confidence);
+    ocr_result.apply_orientation(&ocr_config.orientation);
 
-    info!("   📍 {} linhas detectadas", ocr_result.lines.len());
+    if let Some(stages) = debug_stages {
+        let mode = match action {
+            hotkey::HotkeyAction::TranslateFullScreen => "tela_cheia",
+            _ => "regiao",
+        };
+        spawn_stage_dump(state, mode, stages, ocr_result.clone());
+    }
 
     // Se upscale foi aplicado, as coordenadas do OCR estão multiplicadas
     // pelo fator de escala. Precisamos corrigir dividindo de volta.
@@ -1959,34 +2728,97 @@ fn process_translation_blocking(state: &AppState, action: hotkey::HotkeyAction)
     //          mas na tela real o texto está em (200, 300)
     // Upscale só é aplicado no modo região/legendas.
     // No modo tela cheia a imagem já é grande demais — upscale deixaria muito lento.
-    let upscale_factor = if preprocess_config.enabled
-        && preprocess_config.upscale > 1.0
-        && action != hotkey::HotkeyAction::TranslateFullScreen
-    {
-        preprocess_config.upscale as f64
-    } else {
-        1.0
-    };
+    // (no modo multi-variante as coordenadas já voltam na escala original)
+    let variants_used = use_memory && ocr_config.multi_variant;
+    let upscale_factor =
+        if use_memory && preprocess_config.enabled && allow_upscale && !variants_used {
+            preprocess_config.upscale_factor() as f64
+        } else {
+            1.0
+        };
 
-    if upscale_factor > 1.0 {
+    if upscale_factor != 1.0 {
         info!(
             "   📐 Corrigindo coordenadas (÷{:.1}x upscale)",
             upscale_factor
         );
         for line in &mut ocr_result.lines {
-            line.x /= upscale_factor;
-            line.y /= upscale_factor;
-            line.width /= upscale_factor;
-            line.height /= upscale_factor;
+            line.unscale(upscale_factor);
         }
     }
 
-    // Extrai textos para traduzir e limpa erros de OCR
-    let texts_to_translate: Vec<String> = ocr_result
-        .lines
-        .iter()
-        .map(|line| ocr::clean_ocr_text(&line.text))
-        .collect();
+    // Guarda no histórico mesmo sem texto (OCR vazio também é bug),
+    // com as caixas já nas coordenadas da captura
+    let history_id = history_capture.and_then(|capture| {
+        state.history.record(
+            &history_config,
+            history::CaptureEntry {
+                mode: match action {
+                    hotkey::HotkeyAction::TranslateFullScreen => "tela cheia",
+                    _ => "região",
+                },
+                origin: (origin_x, origin_y),
+                capture,
+                preprocessed: history_preprocessed,
+                ocr_text: ocr_result.full_text.clone(),
+                lines: ocr_result.lines.clone(),
+            },
+        )
+    });
+
+    if ocr_result.lines.is_empty() {
+        info!("⚠️  Nenhum texto detectado!");
+        return Ok(());
+    }
+
+    info!("   📍 {} linhas detectadas", ocr_result.lines.len());
+
+    // Agrupa as linhas em blocos (parágrafos/caixas de diálogo)
+    // para que cada bloco seja traduzido como uma frase completa
+    let (layout_config, speaker_config, corrector) = {
+        let config = state.config.lock().unwrap();
+        (
+            config.app_config.display.layout.clone(),
+            config.app_config.speaker.clone(),
+            correction::engine_for(&config.app_config),
+        )
+    };
+
+    // Separa o nome do falante (rótulo acima da caixa ou "NOME: fala").
+    // Os rótulos saem antes do agrupamento, senão o layout junta o nome
+    // com a fala logo abaixo
+    let blocks: Vec<(Option<String>, ocr::DetectedText)> = if speaker_config.enabled {
+        let (labels, lines) = speaker::take_labels(&ocr_result.lines, &speaker_config);
+        let blocks = layout::group_into_blocks(&lines, &layout_config);
+        speaker::attach_labels(labels, blocks, &speaker_config)
+    } else {
+        layout::group_into_blocks(&ocr_result.lines, &layout_config)
+            .into_iter()
+            .map(|block| (None, block))
+            .collect()
+    };
+
+    // Extrai textos para traduzir e corrige erros de OCR
+    // (só a fala é traduzida, o nome segue à parte)
+    let mut speakers: Vec<Option<String>> = Vec::with_capacity(blocks.len());
+    let mut texts_to_translate: Vec<String> = Vec::with_capacity(blocks.len());
+
+    for (label, block) in &blocks {
+        let cleaned = corrector.clean(&block.text);
+        let (prefix, dialogue) = if speaker_config.enabled {
+            speaker::split_prefix(&cleaned, &speaker_config)
+        } else {
+            (None, cleaned)
+        };
+
+        speakers.push(
+            label
+                .clone()
+                .or(prefix)
+                .map(|name| speaker::display_name(&name, &speaker_config)),
+        );
+        texts_to_translate.push(dialogue);
+    }
 
     // Tradução em batch
     info!("🌐 [3/4] Traduzindo {} textos...", texts_to_translate.len());
@@ -2069,48 +2901,56 @@ fn process_translation_blocking(state: &AppState, action: hotkey::HotkeyAction)
 
     // Monta lista com posições
     // Calcula offset baseado no modo (região ou tela cheia)79
-    let (offset_x, offset_y) = match action {
-        hotkey::HotkeyAction::TranslateRegion => {
-            let config = state.config.lock().unwrap();
-            (config.region_x as f64, config.region_y as f64)
-        }
-        hotkey::HotkeyAction::TranslateFullScreen => {
-            (0.0, 0.0) // Tela cheia: coordenadas já são absolutas
-        }
-        _ => (0.0, 0.0),
-    };
+    // (coordenadas do desktop virtual: a origem do monitor pode ser negativa)
+    // (tela cheia: relativas ao monitor ou à janela do jogo capturada)
+    let (offset_x, offset_y) = (origin_x as f64, origin_y as f64);
 
-    let translated_items: Vec<TranslatedText> = ocr_result
-        .lines
+    let translated_items: Vec<TranslatedText> = blocks
         .iter()
-        .zip(translated_texts.iter())
-        .map(|(detected, translated)| TranslatedText {
-            original: ocr::clean_ocr_text(&detected.text),
-            translated: translated.clone(),
-            screen_x: detected.x + offset_x,
-            screen_y: detected.y + offset_y,
-            width: detected.width,
-            height: detected.height,
-        })
+        .zip(texts_to_translate)
+        .zip(translated_texts.iter().cloned())
+        .zip(speakers)
+        .map(
+            |((((_, detected), original), translated), speaker)| TranslatedText {
+                original,
+                translated,
+                screen_x: detected.x + offset_x,
+                screen_y: detected.y + offset_y,
+                width: detected.width,
+                height: detected.height,
+                vertical: detected.vertical,
+                speaker,
+            },
+        )
         .collect();
 
+    if let Some(id) = history_id {
+        state.history.set_translations(
+            id,
+            translated_items
+                .iter()
+                .map(|item| (item.original.clone(), item.translated.clone()))
+                .collect(),
+        );
+    }
+
     // Define a região de captura (para posicionar o overlay)
     let capture_region = match action {
         hotkey::HotkeyAction::TranslateRegion => {
-            let config = state.config.lock().unwrap();
+            let (width, height) = capture_size;
             CaptureRegion {
-                x: config.region_x,
-                y: config.region_y,
-                width: config.region_width,
-                height: config.region_height,
+                x: origin_x,
+                y: origin_y,
+                width,
+                height,
             }
         }
         hotkey::HotkeyAction::TranslateFullScreen => {
             // Tela inteira: usa a região do config para o overlay
             let config = state.config.lock().unwrap();
             CaptureRegion {
-                x: config.app_config.overlay.x,
-                y: config.app_config.overlay.y,
+                x: monitor_x + config.app_config.overlay.x as i32,
+                y: monitor_y + config.app_config.overlay.y as i32,
                 width: config.app_config.overlay.width,
                 height: config.app_config.overlay.height,
             }
@@ -2191,10 +3031,69 @@ fn process_translation_blocking(state: &AppState, action: hotkey::HotkeyAction)
 // THREAD DE LEGENDAS (captura contínua)
 // ============================================================================
 
+/// Procura a caixa de diálogo na janela do jogo (ou no monitor da região)
+///
+/// # Retorna
+/// * `Some((recorte, canto no desktop virtual))` da caixa mais provável
+/// * `None` se nenhuma caixa com cara de diálogo foi achada
+fn track_dialogue(state: &AppState) -> Result<Option<(image::DynamicImage, (i32, i32))>> {
+    let (window, monitor, detect_config) = {
+        let config = state.config.lock().unwrap();
+        (
+            config.app_config.game_window.clone(),
+            config.app_config.region.monitor,
+            config.app_config.text_detect.clone(),
+        )
+    };
+
+    let detection = {
+        let mut source = state.capture_source.lock().unwrap();
+        text_regions::detect_area(source.as_mut(), &window, monitor, &detect_config)?
+    };
+
+    let Some(region) =
+        text_regions::best(&detection.regions, text_regions::TextRegionKind::Dialogue)
+    else {
+        return Ok(None);
+    };
+
+    let origin = detection.virtual_origin(region);
+    info!(
+        "   🟩 Caixa de diálogo: {}x{} em ({}, {}), {} linha(s)",
+        region.width, region.height, origin.0, origin.1, region.lines
+    );
+    Ok(Some((detection.crop(region), origin)))
+}
+
+/// Leituras seguidas sem texto antes de procurar a legenda de novo
+const SUBTITLE_TRACK_EMPTY_READS: u32 = 3;
+
+/// Intervalo mínimo entre duas buscas pela legenda (a detecção captura
+/// a tela inteira)
+const SUBTITLE_TRACK_INTERVAL: Duration = Duration::from_secs(3);
+
 fn start_subtitle_thread(state: AppState) {
     thread::spawn(move || {
         info!("📺 Thread de legendas iniciada (aguardando ativação)");
 
+        // Backend de OCR reaproveitado entre capturas
+        // (recriado só quando o config ou o idioma do OCR muda)
+        let mut ocr_backend: Option<((config::OcrConfig, String), Box<dyn ocr::OcrBackend>)> = None;
+
+        // Último erro de captura (para não repetir a mesma mensagem)
+        let mut last_capture_error: Option<String> = None;
+
+        // Miniatura e texto da última captura que passou pelo OCR
+        // (capturas iguais a ela reaproveitam o texto, sem OCR)
+        let mut last_frame: Option<(screenshot::FrameSignature, String)> = None;
+        let mut last_frame_settings: Option<FrameSettings> = None;
+        let mut skipped_frames: u32 = 0;
+
+        // Rastreamento automático: leituras seguidas sem texto e quando a
+        // legenda foi procurada pela última vez
+        let mut empty_reads: u32 = 0;
+        let mut last_track: Option<std::time::Instant> = None;
+
         loop {
             // Timeout em segundos (sem texto = esconde legendas)
             // Pega do config (max_display_secs)
@@ -2214,84 +3113,271 @@ fn start_subtitle_thread(state: AppState) {
                     state.subtitle_state.reset();
                 }
 
-                // Pega configurações da região de legenda
-                let (region_x, region_y, region_w, region_h, interval_ms) = {
+                // A legenda sumiu por algumas leituras: procura a caixa de
+                // novo (só em memória, o config.json fica como estava)
+                let auto_track = state
+                    .config
+                    .lock()
+                    .unwrap()
+                    .app_config
+                    .text_detect
+                    .auto_track_subtitle;
+                if auto_track
+                    && empty_reads >= SUBTITLE_TRACK_EMPTY_READS
+                    && last_track.is_none_or(|t| t.elapsed() >= SUBTITLE_TRACK_INTERVAL)
+                {
+                    last_track = Some(std::time::Instant::now());
+                    match track_subtitle_region(&state) {
+                        Ok(Some(region)) => {
+                            let mut config = state.config.lock().unwrap();
+                            if config.app_config.subtitle.region != region {
+                                info!(
+                                    "🟩 Legenda encontrada: {}x{} na posição ({}, {}) {}",
+                                    region.width,
+                                    region.height,
+                                    region.x,
+                                    region.y,
+                                    region_reference(&region)
+                                );
+                                config.app_config.subtitle.region = region;
+                                last_frame = None;
+                                empty_reads = 0;
+                            }
+                        }
+                        Ok(None) => trace!("Nenhuma legenda achada na tela"),
+                        Err(e) => trace!("Falha ao procurar legenda: {}", e),
+                    }
+                }
+
+                // Pega a região de legenda (acompanha a janela do jogo)
+                let (target, interval_ms) = {
                     let config = state.config.lock().unwrap();
                     (
-                        config.app_config.subtitle.region.x,
-                        config.app_config.subtitle.region.y,
-                        config.app_config.subtitle.region.width,
-                        config.app_config.subtitle.region.height,
+                        game_window::resolve_region(
+                            &config.app_config.game_window,
+                            &config.app_config.subtitle.region,
+                        ),
                         config.app_config.subtitle.capture_interval_ms,
                     )
                 };
 
-                // Pega configurações de pré-processamento
-                let preprocess_config = {
+                // Pega configurações da legenda (pré-processamento, tamanho mínimo)
+                // e do OCR
+                let (subtitle_config, ocr_key) = {
                     let config = state.config.lock().unwrap();
-                    config.app_config.subtitle.preprocess.clone()
+                    (
+                        config.app_config.subtitle.clone(),
+                        (
+                            config.app_config.ocr.clone(),
+                            config.app_config.ocr_language(),
+                        ),
+                    )
                 };
+                let preprocess_config = &subtitle_config.preprocess;
+                let change_threshold = subtitle_config.change_threshold;
+                let multi_variant = ocr_key.0.multi_variant;
+
+                // Região, pré-processamento ou OCR mudaram (hot reload, aba
+                // Configurações): a última leitura não vale mais, mesmo com
+                // a imagem igual
+                let frame_settings = (
+                    subtitle_config.region.clone(),
+                    preprocess_config.clone(),
+                    ocr_key.clone(),
+                );
+                if last_frame_settings.as_ref() != Some(&frame_settings) {
+                    if last_frame.take().is_some() {
+                        trace!("🔄 Configuração da legenda mudou: lendo de novo");
+                    }
+                    last_frame_settings = Some(frame_settings);
+                }
+
+                // Captura a região da legenda (e onde ela está no desktop)
+                let captured = target.and_then(|target| {
+                    let image = state.capture_source.lock().unwrap().capture_region(
+                        target.monitor_index(),
+                        target.x,
+                        target.y,
+                        target.width,
+                        target.height,
+                    )?;
+                    Ok((image, target.virtual_origin()))
+                });
+                match captured {
+                    Ok((image, subtitle_origin)) => {
+                        if last_capture_error.take().is_some() {
+                            info!("✅ Captura da região de legenda voltou ao normal");
+                        }
+
+                        // Região igual à da última leitura: pula pré-processamento e OCR
+                        let signature = screenshot::FrameSignature::of(&image);
+                        if let Some((previous, text)) = &last_frame {
+                            if change_threshold > 0
+                                && !previous.changed(&signature, change_threshold)
+                            {
+                                skipped_frames += 1;
+                                let has_text = subtitle::is_long_enough(text, &subtitle_config);
+
+                                if state.subtitle_state.has_pending_candidate() {
+                                    // O debounce precisa ver o texto de novo para confirmar
+                                    handle_subtitle_text(&state, text);
+                                } else if has_text {
+                                    // A legenda continua na tela (não deixa dar timeout)
+                                    state.subtitle_state.update_detection_time();
+                                }
+                                if !has_text {
+                                    empty_reads += 1;
+                                }
+
+                                thread::sleep(Duration::from_millis(interval_ms));
+                                continue;
+                            }
+                        }
+                        if skipped_frames > 0 {
+                            trace!("⏭️  Região mudou após {} capturas iguais", skipped_frames);
+                            skipped_frames = 0;
+                        }
+
+                        // Histórico para relatório de bug (cópia só se ligado)
+                        let history_config =
+                            state.config.lock().unwrap().app_config.history.clone();
+                        let history_capture = (history_config.size > 0).then(|| image.clone());
 
-                // Captura a região da legenda
-                match screenshot::capture_region_to_memory(region_x, region_y, region_w, region_h) {
-                    Ok(image) => {
                         // Aplica pré-processamento se habilitado
                         // No modo tela cheia, força upscale 1.0 (desativado)
                         // porque a imagem já é grande e upscale deixaria muito lento
-                        let processed_image = if preprocess_config.enabled {
-                            screenshot::preprocess_image(
-                                &image,
-                                preprocess_config.grayscale,
-                                preprocess_config.invert,
-                                preprocess_config.contrast,
-                                preprocess_config.threshold,
-                                preprocess_config.save_debug_image,
-                                preprocess_config.upscale, // Legendas sempre usam upscale do config
-                                preprocess_config.blur,    // Blur gaussiano
-                                preprocess_config.dilate,
-                                preprocess_config.erode,
-                                preprocess_config.edge_detection,
-                            )
+                        // (no modo multi-variante cada variante faz o seu)
+                        let preprocessed = preprocess_config.enabled && !multi_variant;
+                        let mut debug_stages = None;
+                        let processed_image = if preprocessed {
+                            // Legendas sempre usam o upscale do config
+                            if preprocess_config.save_debug_stages {
+                                let (processed, stages) = screenshot::preprocess_image_stages(
+                                    &image,
+                                    preprocess_config,
+                                    true,
+                                );
+                                debug_stages = Some(stages);
+                                processed
+                            } else {
+                                screenshot::preprocess_image(&image, preprocess_config, true)
+                            }
                         } else {
                             image
                         };
 
+                        // (Re)cria o backend de OCR se necessário
+                        if ocr_backend.as_ref().map(|(key, _)| key) != Some(&ocr_key) {
+                            match ocr::create_backend(&ocr_key.0, &ocr_key.1) {
+                                Ok(backend) => ocr_backend = Some((ocr_key, backend)),
+                                Err(e) => {
+                                    error!("❌ Erro ao criar backend de OCR: {}", e);
+                                    thread::sleep(Duration::from_millis(interval_ms));
+                                    continue;
+                                }
+                            }
+                        }
+                        let ((ocr_config, _), backend) = ocr_backend.as_ref().unwrap();
+
                         // Executa OCR
-                        match ocr::extract_text_from_memory(&processed_image) {
-                            Ok(ocr_result) => {
-                                // Junta todo o texto detectado e limpa erros de OCR
-                                let full_text = ocr::clean_ocr_text(&ocr_result.full_text);
+                        let ocr_result = if ocr_config.multi_variant {
+                            ocr::recognize_variants(
+                                backend.as_ref(),
+                                &processed_image,
+                                &ocr_config.variants,
+                                ocr_config.min_votes,
+                                true,
+                            )
+                        } else {
+                            backend.recognize(&processed_image)
+                        };
 
-                                // Se detectou texto, atualiza o tempo
-                                if full_text.len() >= 3 {
-                                    state.subtitle_state.update_detection_time();
+                        match ocr_result {
+                            Ok(mut ocr_result) => {
+                                // Descarta linhas com confiança baixa demais
+                                ocr_result.retain_confident(ocr_config.min_confidence);
+                                ocr_result.apply_orientation(&ocr_config.orientation);
+
+                                if let Some(stages) = debug_stages {
+                                    spawn_stage_dump(&state, "legenda", stages, ocr_result.clone());
                                 }
 
-                                // Processa o texto detectado
-                                if let Some(text_to_translate) =
-                                    state.subtitle_state.process_detected_text(&full_text)
+                                // Junta todo o texto detectado e corrige erros de OCR
+                                let (corrector, speaker_config) = {
+                                    let config = state.config.lock().unwrap();
+                                    (
+                                        correction::engine_for(&config.app_config),
+                                        config.app_config.speaker.clone(),
+                                    )
+                                };
+
+                                // Nome acima da caixa vira prefixo ("Nome: fala"),
+                                // separado de novo na hora de traduzir
+                                if speaker_config.enabled
+                                    && speaker::first_line_is_label(
+                                        &ocr_result.lines,
+                                        &speaker_config,
+                                    )
                                 {
-                                    // Texto mudou! Traduz
-                                    let state_clone = state.clone();
-
-                                    thread::spawn(move || {
-                                        if let Err(e) = process_subtitle_translation(
-                                            &state_clone,
-                                            &text_to_translate,
-                                        ) {
-                                            error!("❌ Erro ao traduzir legenda: {}", e);
+                                    let label = ocr_result.lines.remove(0);
+                                    ocr_result.rebuild_full_text();
+                                    ocr_result.full_text =
+                                        format!("{}: {}", label.text.trim(), ocr_result.full_text);
+                                }
+
+                                let full_text = corrector.clean(&ocr_result.full_text);
+
+                                if let Some(capture) = history_capture {
+                                    // Caixas nas coordenadas da captura (desfaz o upscale;
+                                    // o multi-variante já devolve na escala original)
+                                    let mut lines = ocr_result.lines.clone();
+                                    if preprocessed {
+                                        let factor = preprocess_config.upscale_factor() as f64;
+                                        if factor != 1.0 {
+                                            for line in &mut lines {
+                                                line.unscale(factor);
+                                            }
                                         }
-                                    });
+                                    }
+
+                                    state.history.record(
+                                        &history_config,
+                                        history::CaptureEntry {
+                                            mode: "legenda",
+                                            origin: subtitle_origin,
+                                            capture,
+                                            preprocessed: preprocessed
+                                                .then(|| processed_image.clone()),
+                                            ocr_text: full_text.clone(),
+                                            lines,
+                                        },
+                                    );
+                                }
+
+                                handle_subtitle_text(&state, &full_text);
+                                if subtitle::is_long_enough(&full_text, &subtitle_config) {
+                                    empty_reads = 0;
+                                } else {
+                                    empty_reads += 1;
                                 }
+                                last_frame = Some((signature, full_text));
                             }
                             Err(e) => {
                                 // OCR falhou silenciosamente (pode ser região sem texto)
                                 trace!("OCR falhou: {}", e);
+                                empty_reads += 1;
+                                last_frame = Some((signature, String::new()));
                             }
                         }
                     }
                     Err(e) => {
-                        error!("❌ Erro ao capturar região de legenda: {}", e);
+                        // Mesmo erro a cada captura (ex: jogo minimizado):
+                        // avisa só quando muda
+                        let message = e.to_string();
+                        if last_capture_error.as_ref() != Some(&message) {
+                            error!("❌ Erro ao capturar região de legenda: {}", message);
+                            last_capture_error = Some(message);
+                        }
                     }
                 }
 
@@ -2305,12 +3391,64 @@ fn start_subtitle_thread(state: AppState) {
     });
 }
 
+/// Passa o texto lido da região de legenda pelo debounce e, se ele foi
+/// confirmado, traduz em outra thread
+fn handle_subtitle_text(state: &AppState, full_text: &str) {
+    let subtitle_config = state.config.lock().unwrap().app_config.subtitle.clone();
+
+    // Se detectou texto, atualiza o tempo
+    if subtitle::is_long_enough(full_text, &subtitle_config) {
+        state.subtitle_state.update_detection_time();
+    }
+
+    // Processa o texto detectado
+    if let Some(text_to_translate) = state
+        .subtitle_state
+        .process_detected_text(full_text, &subtitle_config)
+    {
+        // Texto mudou! Traduz
+        let state_clone = state.clone();
+
+        thread::spawn(move || {
+            if let Err(e) = process_subtitle_translation(&state_clone, &text_to_translate) {
+                error!("❌ Erro ao traduzir legenda: {}", e);
+            }
+        });
+    }
+}
+
+/// Procura a legenda de novo na janela do jogo (ou no monitor da região)
+///
+/// # Retorna
+/// * `Some(região)` da legenda mais provável, pronta para o config
+fn track_subtitle_region(state: &AppState) -> Result<Option<config::RegionConfig>> {
+    let (window, monitor, detect_config) = {
+        let config = state.config.lock().unwrap();
+        (
+            config.app_config.game_window.clone(),
+            config.app_config.subtitle.region.monitor,
+            config.app_config.text_detect.clone(),
+        )
+    };
+
+    let detection = {
+        let mut source = state.capture_source.lock().unwrap();
+        text_regions::detect_area(source.as_mut(), &window, monitor, &detect_config)?
+    };
+
+    Ok(
+        text_regions::best(&detection.regions, text_regions::TextRegionKind::Subtitle)
+            .map(|region| detection.to_config(region)),
+    )
+}
+
 /// Processa a tradução de uma legenda
 fn process_subtitle_translation(state: &AppState, text: &str) -> anyhow::Result<()> {
     info!("📺 Traduzindo legenda: \"{}\"", text);
+    let ocr_text = text;
 
     // Pega configurações de tradução (do app_config pra ter hot reload)
-    let (api_key, provider, source_lang, target_lang, libre_url) = {
+    let (api_key, provider, source_lang, target_lang, libre_url, speaker_config) = {
         let config = state.config.lock().unwrap();
         (
             config.app_config.translation.deepl_api_key.clone(),
@@ -2318,16 +3456,33 @@ fn process_subtitle_translation(state: &AppState, text: &str) -> anyhow::Result<
             config.app_config.translation.source_language.clone(),
             config.app_config.translation.target_language.clone(),
             config.app_config.translation.libretranslate_url.clone(),
+            config.app_config.speaker.clone(),
         )
     };
 
+    // Separa o nome do falante: só a fala é traduzida
+    let (speaker, dialogue) = if speaker_config.enabled {
+        speaker::split_prefix(text, &speaker_config)
+    } else {
+        (None, text.to_string())
+    };
+    let speaker = speaker.map(|name| speaker::display_name(&name, &speaker_config));
+    let text = dialogue.as_str();
+
+    if let Some(speaker) = &speaker {
+        info!("   🗣️  Falante: {}", speaker);
+    }
+
     // Verifica cache primeiro
     if let Some(cached) = state
         .translation_cache
         .get(&provider, &source_lang, &target_lang, text)
     {
         info!("   📦 Cache hit!");
-        state.subtitle_state.add_translated_subtitle(cached);
+        state.history.set_translation_for_text(ocr_text, &cached);
+        state
+            .subtitle_state
+            .add_translated_subtitle(cached, speaker);
         return Ok(());
     }
 
@@ -2354,13 +3509,130 @@ fn process_subtitle_translation(state: &AppState, text: &str) -> anyhow::Result<
             .set(&provider, &source_lang, &target_lang, text, translated_text);
 
         // Adiciona ao histórico de legendas
+        state
+            .history
+            .set_translation_for_text(ocr_text, translated_text);
         state
             .subtitle_state
-            .add_translated_subtitle(translated_text.clone());
+            .add_translated_subtitle(translated_text.clone(), speaker);
+    }
+
+    Ok(())
+}
+// ============================================================================
+// REPLAY HEADLESS (--replay)
+// ============================================================================
+
+/// Roda o modo legenda ou as hotkeys sobre uma gravação e imprime as
+/// traduções (não precisa de monitor: nada de overlay, hotkeys ou tela)
+fn run_replay(state: AppState, options: &capture::ReplayOptions) -> Result<()> {
+    *state.capture_source.lock().unwrap() = capture::open_recording(&options.path)?;
+
+    // Gravações não têm janela: regiões presas à janela valem direto
+    // sobre o frame (grave a área cliente do jogo)
+    {
+        let mut config = state.config.lock().unwrap();
+        if config.app_config.game_window.enabled {
+            info!("🪟 Janela do jogo ignorada no replay: regiões relativas ao frame");
+            config.app_config.game_window.enabled = false;
+            config.app_config.region.window_size = None;
+            config.app_config.subtitle.region.window_size = None;
+        }
+    }
+    let finished = || state.capture_source.lock().unwrap().is_finished();
+
+    match options.mode {
+        capture::ReplayMode::Subtitle => {
+            *state.subtitle_mode_active.lock().unwrap() = true;
+            start_subtitle_thread(state.clone());
+
+            // Imprime as legendas novas conforme aparecem no histórico
+            let mut printed: Vec<String> = Vec::new();
+            let mut print_new = || {
+                let history: Vec<String> = state
+                    .subtitle_state
+                    .get_subtitle_history()
+                    .iter()
+                    .map(|entry| entry.display_text())
+                    .collect();
+
+                // O histórico é limitado: acha onde o último impresso está
+                let start = printed
+                    .last()
+                    .and_then(|last| history.iter().rposition(|text| text == last))
+                    .map(|index| index + 1)
+                    .unwrap_or(0);
+                for text in &history[start.min(history.len())..] {
+                    println!("📺 {}", text);
+                    printed.push(text.clone());
+                }
+            };
+
+            while !finished() {
+                print_new();
+                thread::sleep(Duration::from_millis(100));
+            }
+
+            // O último frame continua na tela: a legenda dele só é confirmada
+            // depois de stable_count leituras. Espera essas leituras (e mais
+            // um pouco se ainda houver candidata pendente) antes de parar
+            let (stable_count, interval_ms) = {
+                let config = state.config.lock().unwrap();
+                (
+                    config.app_config.subtitle.stable_count.max(1) as u64,
+                    config.app_config.subtitle.capture_interval_ms,
+                )
+            };
+            let settle = Duration::from_millis(interval_ms * (stable_count + 1));
+            let settle_started = std::time::Instant::now();
+            while settle_started.elapsed() < settle
+                || (state.subtitle_state.has_pending_candidate()
+                    && settle_started.elapsed() < settle * 2)
+            {
+                print_new();
+                thread::sleep(Duration::from_millis(100));
+            }
+
+            // Dá tempo para as últimas traduções (rodam em outras threads)
+            *state.subtitle_mode_active.lock().unwrap() = false;
+            thread::sleep(Duration::from_secs(3));
+            print_new();
+        }
+        capture::ReplayMode::Region | capture::ReplayMode::FullScreen => {
+            let action = if options.mode == capture::ReplayMode::Region {
+                hotkey::HotkeyAction::TranslateRegion
+            } else {
+                hotkey::HotkeyAction::TranslateFullScreen
+            };
+
+            // Uma "tecla" por captura até a gravação acabar
+            let mut frame = 0;
+            while !finished() {
+                frame += 1;
+                state.clear_translations();
+
+                if let Err(e) = process_translation_blocking(&state, action) {
+                    error!("❌ Erro no frame {}: {}", frame, e);
+                    continue;
+                }
+
+                if let Some((items, _, _, _)) = state.get_translations() {
+                    for item in items {
+                        println!(
+                            "🖼️  [{}] ({:.0}, {:.0}) {} → {}",
+                            frame, item.screen_x, item.screen_y, item.original, item.translated
+                        );
+                    }
+                }
+            }
+        }
     }
 
+    let _ = state.translation_cache.save_to_disk();
+    info!("✅ Replay concluído!");
     Ok(())
 }
+
 // ============================================================================
 // FUNÇÃO PRINCIPAL
 // ============================================================================
@@ -2369,6 +3641,7 @@ fn main() -> Result<()> {
     // Declara que o programa é DPI-aware (Per-Monitor V2)
     // Sem isso, o Windows "mente" e diz que o DPI é 96 (100%)
     // mesmo quando o usuário tem 125%, 150%, etc.
+    #[cfg(windows)]
     unsafe {
         winapi::um::shellscalingapi::SetProcessDpiAwareness(2); // 2 = Per-Monitor DPI Aware
     }
@@ -2380,6 +3653,36 @@ fn main() -> Result<()> {
     info!("🎮 ============================================");
     info!("");
 
+    // Lê argumentos da linha de comando
+    let cli_args = cli::parse_args()?;
+
+    // Modo headless: avalia o OCR sobre um corpus e sai (sem overlay)
+    if let Some(options) = cli_args.ocr_score {
+        let app_config = config::AppConfig::load()?;
+        let passed = corpus::run(&options, &app_config)?;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    // Modo headless: procura o melhor pré-processamento para o corpus e sai
+    if let Some(options) = cli_args.autotune {
+        let mut app_config = config::AppConfig::load()?;
+        autotune::run(&options, &mut app_config)?;
+        return Ok(());
+    }
+
+    // Modo headless: mede a captura das regiões (display e legendas) e sai
+    if let Some(iterations) = cli_args.capture_bench {
+        let app_config = config::AppConfig::load()?;
+        screenshot::run_capture_benchmark(
+            &[
+                ("display", &app_config.region),
+                ("legendas", &app_config.subtitle.region),
+            ],
+            iterations,
+        )?;
+        return Ok(());
+    }
+
     // Carrega configurações
     let config = Config::load()?;
 
@@ -2387,7 +3690,10 @@ fn main() -> Result<()> {
     let (command_sender, command_receiver) = unbounded::<AppCommand>();
 
     // Cria estado compartilhado
+    #[cfg(windows)]
     let dpi = unsafe { winapi::um::winuser::GetDpiForSystem() };
+    #[cfg(not(windows))]
+    let dpi = 96;
     let dpi_scale = dpi as f32 / 96.0;
     info!(
         "📐 DPI do sistema: {} (escala: {}%)",
@@ -2397,11 +3703,25 @@ fn main() -> Result<()> {
 
     let state = AppState::new(config, command_sender, dpi_scale);
 
+    // Modo headless: roda o pipeline sobre uma gravação e sai (sem overlay)
+    if let Some(options) = cli_args.replay {
+        return run_replay(state, &options);
+    }
+
     // Inicia threads
     start_hotkey_thread(state.clone());
     start_config_watcher(state.clone());
     start_subtitle_thread(state.clone());
 
+    // Pré-aquece o cache em background (se pedido via --prewarm)
+    if let Some(options) = cli_args.prewarm {
+        prewarm::start_prewarm_thread(
+            state.config.clone(),
+            state.translation_cache.clone(),
+            options,
+        );
+    }
+
     info!("✅ Sistema pronto!");
     info!("   Numpad - = Tela inteira");
     info!("   Numpad + = Região customizada");
@@ -2529,6 +3849,7 @@ fn make_window_click_through() {
     }
 }
 
+#[cfg(windows)]
 fn remove_window_click_through() {
     use winapi::um::winuser::{
         FindWindowW, GetWindowLongW, SetWindowLongW, GWL_EXSTYLE, WS_EX_LAYERED, WS_EX_TRANSPARENT,
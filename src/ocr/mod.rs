@@ -0,0 +1,362 @@
+// game-translator/src/ocr/mod.rs
+
+// ============================================================================
+// MÓDULO OCR - Extração de texto com backends plugáveis
+// ============================================================================
+//
+// O pipeline não depende de um motor de OCR específico. Cada motor
+// implementa o trait `OcrBackend` e devolve `OcrResultWithPositions`.
+//
+// Backends disponíveis:
+// - Windows OCR nativo (Windows 10/11)        → config "windows"
+// - Tesseract (Linux/CI, --features tesseract) → config "tesseract"
+//
+// ============================================================================
+
+#[cfg(feature = "tesseract")]
+mod tesseract_backend;
+mod voting;
+#[cfg(windows)]
+mod windows_backend;
+
+pub use voting::recognize_variants;
+
+use crate::cjk;
+use crate::config::OcrConfig;
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use std::path::Path;
+
+// ============================================================================
+// ESTRUTURAS DE DADOS
+// ============================================================================
+
+/// Razão altura/largura a partir da qual uma linha é considerada vertical
+/// (só usada pelos backends; sem nenhum compilado, fica sem uso)
+#[cfg_attr(not(any(windows, feature = "tesseract")), allow(dead_code))]
+const VERTICAL_ASPECT_RATIO: f64 = 1.5;
+
+/// Representa uma palavra detectada com sua posição na imagem
+#[derive(Debug, Clone)]
+pub struct DetectedWord {
+    /// A palavra detectada
+    #[allow(dead_code)]
+    pub text: String,
+    /// Posição X (pixels a partir da esquerda da imagem)
+    pub x: f64,
+    /// Posição Y (pixels a partir do topo da imagem)
+    pub y: f64,
+    /// Largura da palavra
+    pub width: f64,
+    /// Altura da palavra
+    pub height: f64,
+    /// Confiança do OCR (0.0 a 1.0), se o backend informar
+    #[cfg_attr(not(any(windows, feature = "tesseract")), allow(dead_code))]
+    pub confidence: Option<f32>,
+}
+
+/// Representa um bloco de texto detectado com sua posição na imagem
+#[derive(Debug, Clone)]
+pub struct DetectedText {
+    /// O texto detectado
+    pub text: String,
+    /// Posição X (pixels a partir da esquerda da imagem)
+    pub x: f64,
+    /// Posição Y (pixels a partir do topo da imagem)
+    pub y: f64,
+    /// Largura do bloco de texto
+    pub width: f64,
+    /// Altura do bloco de texto
+    pub height: f64,
+    /// Palavras que formam a linha (com posição individual)
+    pub words: Vec<DetectedWord>,
+    /// Confiança média da linha (0.0 a 1.0), se o backend informar
+    pub confidence: Option<f32>,
+    /// Texto vertical (coluna de japonês/chinês, lida de cima para baixo)
+    pub vertical: bool,
+}
+
+impl DetectedText {
+    /// Monta uma linha a partir das suas palavras
+    ///
+    /// O retângulo da linha é a união dos retângulos das palavras
+    /// (cobre letras altas/baixas de qualquer palavra, não só da primeira)
+    /// e a confiança é a média das palavras que têm confiança.
+    ///
+    /// # Retorna
+    /// * `None` se não houver palavras
+    #[cfg_attr(not(any(windows, feature = "tesseract")), allow(dead_code))]
+    pub fn from_words(text: String, words: Vec<DetectedWord>) -> Option<DetectedText> {
+        let first = words.first()?;
+
+        let mut left = first.x;
+        let mut top = first.y;
+        let mut right = first.x + first.width;
+        let mut bottom = first.y + first.height;
+
+        for word in &words[1..] {
+            left = left.min(word.x);
+            top = top.min(word.y);
+            right = right.max(word.x + word.width);
+            bottom = bottom.max(word.y + word.height);
+        }
+
+        let confidences: Vec<f32> = words.iter().filter_map(|w| w.confidence).collect();
+        let confidence = if confidences.is_empty() {
+            None
+        } else {
+            Some(confidences.iter().sum::<f32>() / confidences.len() as f32)
+        };
+
+        let width = right - left;
+        let height = bottom - top;
+
+        // Coluna alta e estreita com vários caracteres = texto vertical
+        let vertical = height > width * VERTICAL_ASPECT_RATIO && text.chars().count() >= 2;
+
+        Some(DetectedText {
+            text,
+            x: left,
+            y: top,
+            width,
+            height,
+            words,
+            confidence,
+            vertical,
+        })
+    }
+
+    /// Divide todas as coordenadas (linha e palavras) por um fator
+    ///
+    /// Usado para desfazer o upscale do pré-processamento.
+    pub fn unscale(&mut self, factor: f64) {
+        self.x /= factor;
+        self.y /= factor;
+        self.width /= factor;
+        self.height /= factor;
+
+        for word in &mut self.words {
+            word.x /= factor;
+            word.y /= factor;
+            word.width /= factor;
+            word.height /= factor;
+        }
+    }
+}
+
+/// Representa um texto traduzido com sua posição na TELA (coordenadas absolutas)
+#[derive(Debug, Clone)]
+pub struct TranslatedText {
+    /// Texto original (inglês)
+    pub original: String,
+    /// Texto traduzido (português)
+    pub translated: String,
+    /// Posição X na tela (coordenadas absolutas do monitor)
+    pub screen_x: f64,
+    /// Posição Y na tela (coordenadas absolutas do monitor)
+    pub screen_y: f64,
+    /// Largura do bloco original
+    pub width: f64,
+    /// Altura do bloco original
+    pub height: f64,
+    /// Bloco original era vertical (coluna de japonês/chinês)
+    pub vertical: bool,
+    /// Nome de quem está falando (não traduzido)
+    pub speaker: Option<String>,
+}
+
+impl TranslatedText {
+    /// Texto exibido no overlay ("Nome: tradução" quando há falante)
+    pub fn display_text(&self) -> String {
+        match &self.speaker {
+            Some(speaker) => format!("{}: {}", speaker, self.translated),
+            None => self.translated.clone(),
+        }
+    }
+}
+
+/// Resultado completo do OCR com posições
+#[derive(Debug, Clone)]
+pub struct OcrResultWithPositions {
+    /// Texto completo (todas as linhas juntas)
+    pub full_text: String,
+    /// Lista de linhas detectadas com suas posições
+    pub lines: Vec<DetectedText>,
+}
+
+impl OcrResultWithPositions {
+    /// Remove linhas com confiança abaixo do mínimo (lixo do OCR)
+    ///
+    /// Linhas sem confiança (backend não informa) são mantidas.
+    /// Se alguma linha for removida, o `full_text` é remontado.
+    pub fn retain_confident(&mut self, min_confidence: f32) {
+        if min_confidence <= 0.0 {
+            return;
+        }
+
+        let before = self.lines.len();
+        self.lines
+            .retain(|line| !matches!(line.confidence, Some(c) if c < min_confidence));
+
+        let removed = before - self.lines.len();
+        if removed > 0 {
+            info!(
+                "   🗑️  {} linha(s) descartada(s) por baixa confiança (< {:.2})",
+                removed, min_confidence
+            );
+            self.rebuild_full_text();
+        }
+    }
+
+    /// Aplica a orientação do texto configurada
+    ///
+    /// * `"auto"` - mantém o que foi detectado pelo formato das linhas
+    /// * `"horizontal"` - força todas as linhas como horizontais
+    /// * `"vertical"` - força todas as linhas como verticais
+    ///
+    /// Se houver colunas verticais, elas são reordenadas da direita para a
+    /// esquerda (ordem de leitura do japonês/chinês vertical).
+    pub fn apply_orientation(&mut self, orientation: &str) {
+        match orientation.to_lowercase().as_str() {
+            "horizontal" => self.lines.iter_mut().for_each(|l| l.vertical = false),
+            "vertical" => self.lines.iter_mut().for_each(|l| l.vertical = true),
+            _ => {}
+        }
+
+        if !self.lines.iter().any(|l| l.vertical) {
+            return;
+        }
+
+        info!("   ↕️  Texto vertical detectado");
+
+        // Horizontais primeiro (de cima para baixo), depois as colunas
+        // verticais da direita para a esquerda
+        self.lines.sort_by(|a, b| {
+            a.vertical.cmp(&b.vertical).then_with(|| {
+                if a.vertical {
+                    (b.x + b.width).total_cmp(&(a.x + a.width))
+                } else {
+                    a.y.total_cmp(&b.y)
+                }
+            })
+        });
+
+        self.rebuild_full_text();
+    }
+
+    /// Remonta o `full_text` a partir das linhas (sem espaço entre CJK)
+    pub fn rebuild_full_text(&mut self) {
+        let mut full_text = String::new();
+        for line in &self.lines {
+            if !full_text.is_empty() && !cjk::joins_without_space(&full_text, &line.text) {
+                full_text.push(' ');
+            }
+            full_text.push_str(line.text.trim());
+        }
+        self.full_text = full_text;
+    }
+}
+
+// ============================================================================
+// TRAIT DO BACKEND DE OCR
+// ============================================================================
+
+/// Motor de OCR capaz de extrair texto com posições de uma imagem
+pub trait OcrBackend: Send + Sync {
+    /// Nome do backend (para logs)
+    fn name(&self) -> &'static str;
+
+    /// Extrai texto COM posições de uma imagem em memória
+    fn recognize(&self, image: &DynamicImage) -> Result<OcrResultWithPositions>;
+
+    /// Extrai texto COM posições de um arquivo de imagem em disco
+    ///
+    /// Implementação padrão: carrega o arquivo e chama `recognize`.
+    fn recognize_file(&self, image_path: &Path) -> Result<OcrResultWithPositions> {
+        let image = image::open(image_path)
+            .with_context(|| format!("Falha ao abrir imagem: {:?}", image_path))?;
+        self.recognize(&image)
+    }
+}
+
+/// Cria o backend de OCR escolhido no config
+///
+/// # Argumentos
+/// * `config` - Configuração de OCR (backend e opções)
+/// * `language` - Idioma do texto a reconhecer (ex: "EN", "ja", "pt-BR").
+///   "auto" ou vazio = idiomas padrão do backend
+///
+/// # Retorna
+/// * `Err` se o backend não existe, não está disponível nesta plataforma/build,
+///   ou se o pacote do idioma não está instalado
+pub fn create_backend(config: &OcrConfig, language: &str) -> Result<Box<dyn OcrBackend>> {
+    let language = normalize_language_tag(language);
+
+    match config.backend.to_lowercase().as_str() {
+        "windows" => {
+            #[cfg(windows)]
+            {
+                Ok(Box::new(windows_backend::WindowsOcrBackend::new(
+                    language.as_deref(),
+                )?))
+            }
+            #[cfg(not(windows))]
+            {
+                anyhow::bail!("O backend 'windows' só está disponível no Windows (use 'tesseract')")
+            }
+        }
+        "tesseract" => {
+            #[cfg(feature = "tesseract")]
+            {
+                Ok(Box::new(tesseract_backend::TesseractBackend::new(
+                    &config.tesseract,
+                    language.as_deref(),
+                    config.orientation.eq_ignore_ascii_case("vertical"),
+                )?))
+            }
+            #[cfg(not(feature = "tesseract"))]
+            {
+                let _ = language;
+                anyhow::bail!(
+                    "O backend 'tesseract' não foi compilado (use: cargo build --features tesseract)"
+                )
+            }
+        }
+        other => anyhow::bail!("Backend de OCR desconhecido: '{}'", other),
+    }
+}
+
+/// Normaliza um código de idioma para tag BCP-47
+///
+/// Exemplos: "EN" → "en", "PT-BR" → "pt-BR", "zh_hans" → "zh-Hans"
+///
+/// # Retorna
+/// * `None` para "auto" ou vazio (usar o padrão do backend)
+pub fn normalize_language_tag(language: &str) -> Option<String> {
+    let language = language.trim();
+    if language.is_empty() || language.eq_ignore_ascii_case("auto") {
+        return None;
+    }
+
+    let parts: Vec<String> = language
+        .split(['-', '_'])
+        .enumerate()
+        .map(|(i, part)| match (i, part.len()) {
+            (0, _) => part.to_lowercase(),
+            // Região ("BR", "US")
+            (_, 2) => part.to_uppercase(),
+            // Script ("Hans", "Hant")
+            (_, 4) => {
+                let lower = part.to_lowercase();
+                let mut chars = lower.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => lower,
+                }
+            }
+            _ => part.to_string(),
+        })
+        .collect();
+
+    Some(parts.join("-"))
+}
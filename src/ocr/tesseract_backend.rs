@@ -0,0 +1,287 @@
+// game-translator/src/ocr/tesseract_backend.rs
+
+// ============================================================================
+// BACKEND TESSERACT - OCR multiplataforma (via leptess)
+// ============================================================================
+//
+// Permite rodar o pipeline fora do Windows (Linux, CI). Precisa do
+// Tesseract e da Leptonica instalados, além dos pacotes de idioma
+// (arquivos .traineddata) na pasta tessdata.
+//
+// Compilar com: cargo build --features tesseract
+//
+// ============================================================================
+
+use super::{DetectedText, DetectedWord, OcrBackend, OcrResultWithPositions};
+use crate::config::TesseractConfig;
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use leptess::{LepTess, Variable};
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Backend que usa o Tesseract
+///
+/// A instância do Tesseract é cara de criar (carrega os modelos de idioma),
+/// então ela fica guardada e é reutilizada entre chamadas.
+pub struct TesseractBackend {
+    tess: Mutex<LepTess>,
+}
+
+impl TesseractBackend {
+    /// Inicializa o Tesseract com os idiomas e o modo de segmentação do config
+    ///
+    /// # Argumentos
+    /// * `config` - Configuração do Tesseract
+    /// * `language` - Tag BCP-47 do idioma do OCR (usada se `config.languages` estiver vazio)
+    /// * `vertical` - Texto vertical: usa os modelos `_vert` e PSM 5 (bloco vertical)
+    pub fn new(config: &TesseractConfig, language: Option<&str>, vertical: bool) -> Result<Self> {
+        let datapath = if config.datapath.is_empty() {
+            None
+        } else {
+            Some(config.datapath.as_str())
+        };
+
+        let languages = if config.languages.trim().is_empty() {
+            language
+                .map(|tag| tesseract_language_code(tag, vertical))
+                .unwrap_or_else(|| "eng".to_string())
+        } else {
+            config.languages.clone()
+        };
+
+        check_traineddata(datapath, &languages)?;
+
+        let mut tess = LepTess::new(datapath, &languages).with_context(|| {
+            format!(
+                "Falha ao iniciar Tesseract com idioma(s) '{}' (tessdata: {}). \
+                 Verifique se os arquivos .traineddata estão instalados",
+                languages,
+                datapath.unwrap_or("padrão do sistema")
+            )
+        })?;
+
+        // Page segmentation mode (ex: 6 = bloco uniforme, 7 = linha única,
+        // 5 = bloco vertical)
+        let psm = if vertical { 5 } else { config.psm };
+        tess.set_variable(Variable::TesseditPagesegMode, &psm.to_string())
+            .map_err(|_| anyhow::anyhow!("PSM inválido para o Tesseract: {}", psm))?;
+
+        info!(
+            "✅ Tesseract iniciado (idiomas: {}, psm: {})",
+            languages, psm
+        );
+
+        Ok(TesseractBackend {
+            tess: Mutex::new(tess),
+        })
+    }
+}
+
+/// Converte uma tag BCP-47 para o código de idioma do Tesseract
+///
+/// Exemplos: "en" → "eng", "ja" → "jpn", "zh-Hant" → "chi_tra"
+///
+/// Para texto vertical, japonês e chinês usam os modelos `_vert`.
+fn tesseract_language_code(tag: &str, vertical: bool) -> String {
+    let primary = tag.split('-').next().unwrap_or(tag);
+
+    let code = match primary {
+        "en" => "eng",
+        "ja" => "jpn",
+        "ko" => "kor",
+        "pt" => "por",
+        "es" => "spa",
+        "fr" => "fra",
+        "de" => "deu",
+        "it" => "ita",
+        "ru" => "rus",
+        "pl" => "pol",
+        "nl" => "nld",
+        "tr" => "tur",
+        "uk" => "ukr",
+        "zh" if tag.contains("Hant") || tag.ends_with("TW") || tag.ends_with("HK") => "chi_tra",
+        "zh" => "chi_sim",
+        // Já é um código do Tesseract (ex: "jpn_vert") ou desconhecido
+        _ => tag,
+    };
+
+    if vertical && matches!(code, "jpn" | "chi_sim" | "chi_tra") {
+        format!("{}_vert", code)
+    } else {
+        code.to_string()
+    }
+}
+
+/// Verifica se os arquivos .traineddata de cada idioma existem
+///
+/// Só dá para checar quando a pasta tessdata é conhecida (config ou
+/// TESSDATA_PREFIX). Caso contrário, o próprio Tesseract acusa o erro.
+fn check_traineddata(datapath: Option<&str>, languages: &str) -> Result<()> {
+    let tessdata = match datapath
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("TESSDATA_PREFIX").map(PathBuf::from))
+    {
+        Some(dir) => dir,
+        None => return Ok(()),
+    };
+
+    let missing: Vec<&str> = languages
+        .split('+')
+        .filter(|lang| !tessdata.join(format!("{}.traineddata", lang)).exists())
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let installed: Vec<String> = std::fs::read_dir(&tessdata)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    e.file_name()
+                        .to_string_lossy()
+                        .strip_suffix(".traineddata")
+                        .map(|s| s.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    anyhow::bail!(
+        "Pacote de idioma do Tesseract não encontrado: {} (pasta: {:?}, instalados: {}). \
+         Baixe os arquivos .traineddata em https://github.com/tesseract-ocr/tessdata",
+        missing.join(", "),
+        tessdata,
+        installed.join(", ")
+    )
+}
+
+impl OcrBackend for TesseractBackend {
+    fn name(&self) -> &'static str {
+        "Tesseract"
+    }
+
+    fn recognize(&self, image: &DynamicImage) -> Result<OcrResultWithPositions> {
+        info!("🔍 Executando Tesseract OCR (memória)...");
+        let started = Instant::now();
+
+        // A Leptonica no Windows só lê TIFF da memória, então usamos TIFF
+        // em todas as plataformas para manter o mesmo comportamento
+        let mut tiff_bytes: Vec<u8> = Vec::new();
+        DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_to(&mut Cursor::new(&mut tiff_bytes), image::ImageFormat::Tiff)
+            .context("Falha ao converter imagem para TIFF")?;
+
+        let mut tess = self.tess.lock().unwrap();
+
+        tess.set_image_from_mem(&tiff_bytes)
+            .context("Falha ao carregar imagem no Tesseract")?;
+
+        // Screenshots não têm DPI; sem isso o Tesseract reclama e chuta 70
+        tess.set_source_resolution(300);
+
+        let converted = Instant::now();
+
+        let tsv = tess
+            .get_tsv_text(0)
+            .context("Falha ao obter resultado do Tesseract")?;
+        let recognized = Instant::now();
+
+        info!(
+            "   ⏱️  OCR: imagem {} ms + reconhecimento {} ms = {} ms ({}x{})",
+            (converted - started).as_millis(),
+            (recognized - converted).as_millis(),
+            (recognized - started).as_millis(),
+            image.width(),
+            image.height()
+        );
+
+        parse_tsv(&tsv)
+    }
+}
+
+// ============================================================================
+// FUNÇÃO AUXILIAR - Converte o TSV do Tesseract em linhas
+// ============================================================================
+
+/// Converte a saída TSV do Tesseract em linhas com posição
+///
+/// Colunas do TSV:
+/// level, page_num, block_num, par_num, line_num, word_num,
+/// left, top, width, height, conf, text
+///
+/// As palavras (level 5) são agrupadas pela chave (block, par, line).
+/// A confiança do Tesseract vai de 0 a 100 (-1 = sem valor).
+fn parse_tsv(tsv: &str) -> Result<OcrResultWithPositions> {
+    // Palavras agrupadas por linha, na ordem em que aparecem
+    let mut grouped: Vec<((u32, u32, u32), Vec<DetectedWord>)> = Vec::new();
+
+    for row in tsv.lines() {
+        let cols: Vec<&str> = row.split('\t').collect();
+        if cols.len() < 12 || cols[0] != "5" {
+            continue;
+        }
+
+        let word_text = cols[11].trim();
+        if word_text.is_empty() {
+            continue;
+        }
+
+        let num = |i: usize| cols[i].parse::<f64>().unwrap_or(0.0);
+        let key = (num(2) as u32, num(3) as u32, num(4) as u32);
+        let conf = num(10);
+
+        let word = DetectedWord {
+            text: word_text.to_string(),
+            x: num(6),
+            y: num(7),
+            width: num(8),
+            height: num(9),
+            confidence: (conf >= 0.0).then_some((conf / 100.0) as f32),
+        };
+
+        match grouped.last_mut() {
+            Some((last_key, words)) if *last_key == key => words.push(word),
+            _ => grouped.push((key, vec![word])),
+        }
+    }
+
+    let lines: Vec<DetectedText> = grouped
+        .into_iter()
+        .filter_map(|(_, words)| {
+            let text = words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            DetectedText::from_words(text, words)
+        })
+        .collect();
+
+    for (i, line) in lines.iter().enumerate() {
+        info!(
+            "   📍 Linha {}: \"{}\" em ({:.0}, {:.0}) {}x{} (confiança: {:.2})",
+            i,
+            line.text,
+            line.x,
+            line.y,
+            line.width as i32,
+            line.height as i32,
+            line.confidence.unwrap_or(0.0)
+        );
+    }
+
+    info!("✅ OCR completo: {} linhas detectadas", lines.len());
+
+    let full_text = lines
+        .iter()
+        .map(|l| l.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    Ok(OcrResultWithPositions { full_text, lines })
+}
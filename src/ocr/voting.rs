@@ -0,0 +1,383 @@
+// game-translator/src/ocr/voting.rs
+
+// ============================================================================
+// OCR MULTI-VARIANTE - Várias versões da mesma captura + votação
+// ============================================================================
+//
+// Um único pré-processamento raramente funciona em todas as cenas do jogo
+// (texto claro em fundo escuro, texto com contorno, fundo colorido...).
+//
+// Aqui a mesma captura passa por várias variantes de pré-processamento
+// (declaradas no config), o OCR roda em todas EM PARALELO e as linhas
+// são combinadas:
+//
+// 1. Linhas de variantes diferentes na mesma posição (IoU alto) formam
+//    um grupo
+// 2. Grupos encontrados por poucas variantes são descartados (a não ser
+//    que a confiança seja muito alta)
+// 3. Em cada grupo vence o texto lido pelo maior número de variantes;
+//    empate é decidido pela confiança, depois pelo tamanho do texto e,
+//    por fim, pela ordem das variantes no config (o resultado não pode
+//    mudar de uma captura para outra, senão o modo legenda re-traduz)
+//
+// O pré-processamento das variantes sempre roda em paralelo, mas o OCR
+// só é paralelo se o backend aceitar chamadas simultâneas: o Tesseract
+// guarda uma única instância atrás de um Mutex, então as variantes
+// esperam a vez dele (uma depois da outra).
+//
+// ============================================================================
+
+use super::{DetectedText, OcrBackend, OcrResultWithPositions};
+use crate::config::OcrVariantConfig;
+use crate::screenshot;
+use anyhow::Result;
+use image::DynamicImage;
+use std::thread;
+
+/// Sobreposição mínima (IoU) para duas linhas serem "a mesma linha"
+const IOU_THRESHOLD: f64 = 0.5;
+
+/// Confiança a partir da qual uma linha é aceita mesmo com um só voto
+const HIGH_CONFIDENCE: f32 = 0.85;
+
+/// Linha encontrada por uma variante
+struct Candidate {
+    variant: usize,
+    line: DetectedText,
+}
+
+/// Roda o OCR em todas as variantes e combina os resultados por votação
+///
+/// As coordenadas devolvidas já estão na escala da imagem ORIGINAL
+/// (o upscale de cada variante é desfeito antes da votação).
+///
+/// # Argumentos
+/// * `backend` - Backend de OCR (compartilhado entre as threads)
+/// * `image` - Captura original (sem pré-processamento)
+/// * `variants` - Variantes de pré-processamento
+/// * `min_votes` - Quantas variantes precisam achar a linha
+/// * `allow_upscale` - false = ignora o upscale das variantes (tela cheia)
+pub fn recognize_variants(
+    backend: &dyn OcrBackend,
+    image: &DynamicImage,
+    variants: &[OcrVariantConfig],
+    min_votes: usize,
+    allow_upscale: bool,
+) -> Result<OcrResultWithPositions> {
+    if variants.is_empty() {
+        return backend.recognize(image);
+    }
+
+    info!(
+        "🗳️  OCR multi-variante: {} variantes em paralelo",
+        variants.len()
+    );
+
+    // Pré-processa e reconhece cada variante em sua própria thread
+    let results: Vec<Result<OcrResultWithPositions>> = thread::scope(|scope| {
+        let handles: Vec<_> = variants
+            .iter()
+            .map(|variant| {
+                scope.spawn(move || recognize_variant(backend, image, variant, allow_upscale))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Thread do OCR entrou em pânico")))
+            })
+            .collect()
+    });
+
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut successful_variants = 0;
+
+    for (index, (variant, result)) in variants.iter().zip(results).enumerate() {
+        match result {
+            Ok(result) => {
+                info!(
+                    "   🗳️  Variante '{}': {} linhas",
+                    variant.name,
+                    result.lines.len()
+                );
+                successful_variants += 1;
+                candidates.extend(result.lines.into_iter().map(|line| Candidate {
+                    variant: index,
+                    line,
+                }));
+            }
+            Err(e) => warn!("⚠️  Variante '{}' falhou: {}", variant.name, e),
+        }
+    }
+
+    if successful_variants == 0 {
+        anyhow::bail!("Todas as variantes de OCR falharam");
+    }
+
+    // Com uma variante só não tem como exigir mais de um voto
+    let min_votes = min_votes.clamp(1, successful_variants);
+    let lines = vote(candidates, min_votes);
+
+    info!("   🗳️  Resultado da votação: {} linhas", lines.len());
+
+    let mut result = OcrResultWithPositions {
+        full_text: String::new(),
+        lines,
+    };
+    result.rebuild_full_text();
+
+    Ok(result)
+}
+
+/// Pré-processa a captura com uma variante e roda o OCR
+fn recognize_variant(
+    backend: &dyn OcrBackend,
+    image: &DynamicImage,
+    variant: &OcrVariantConfig,
+    allow_upscale: bool,
+) -> Result<OcrResultWithPositions> {
+    let preprocess = &variant.preprocess;
+
+    if !preprocess.enabled {
+        return backend.recognize(image);
+    }
+
+    let upscale = if allow_upscale {
+        preprocess.upscale_factor()
+    } else {
+        1.0
+    };
+
+    let processed = screenshot::preprocess_image(image, preprocess, allow_upscale);
+
+    let mut result = backend.recognize(&processed)?;
+
+    // Volta as coordenadas para a escala da captura original
+    if upscale != 1.0 {
+        for line in &mut result.lines {
+            line.unscale(upscale as f64);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Agrupa as linhas por posição e escolhe o texto vencedor de cada grupo
+fn vote(candidates: Vec<Candidate>, min_votes: usize) -> Vec<DetectedText> {
+    // 1. Agrupa linhas sobrepostas de variantes diferentes
+    let mut clusters: Vec<Vec<Candidate>> = Vec::new();
+
+    for candidate in candidates {
+        let best = clusters
+            .iter()
+            .enumerate()
+            .filter(|(_, cluster)| cluster.iter().all(|c| c.variant != candidate.variant))
+            .map(|(index, cluster)| (index, iou(&cluster[0].line, &candidate.line)))
+            .filter(|(_, overlap)| *overlap >= IOU_THRESHOLD)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        match best {
+            Some((index, _)) => clusters[index].push(candidate),
+            None => clusters.push(vec![candidate]),
+        }
+    }
+
+    // 2. Escolhe o vencedor de cada grupo
+    let mut lines: Vec<DetectedText> = clusters
+        .into_iter()
+        .filter_map(|cluster| pick_winner(cluster, min_votes))
+        .collect();
+
+    // Ordem de leitura: de cima para baixo, da esquerda para a direita
+    lines.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    lines
+}
+
+/// Escolhe o texto mais votado de um grupo (ou descarta o grupo)
+fn pick_winner(cluster: Vec<Candidate>, min_votes: usize) -> Option<DetectedText> {
+    let votes = cluster.len();
+    let best_confidence = cluster
+        .iter()
+        .filter_map(|c| c.line.confidence)
+        .fold(0.0f32, f32::max);
+
+    if votes < min_votes && best_confidence < HIGH_CONFIDENCE {
+        trace!(
+            "🗳️  Linha descartada ({} voto(s)): \"{}\"",
+            votes,
+            cluster[0].line.text
+        );
+        return None;
+    }
+
+    // Quantas variantes leram cada texto (ignorando maiúsculas/pontuação)
+    let keys: Vec<String> = cluster.iter().map(|c| vote_key(&c.line.text)).collect();
+    let mut distinct: Vec<&String> = Vec::new();
+    for key in &keys {
+        if !distinct.contains(&key) {
+            distinct.push(key);
+        }
+    }
+
+    distinct
+        .into_iter()
+        .map(|key| {
+            let members: Vec<&Candidate> = cluster
+                .iter()
+                .zip(&keys)
+                .filter(|(_, k)| *k == key)
+                .map(|(c, _)| c)
+                .collect();
+            let confidence = members
+                .iter()
+                .map(|c| c.line.confidence.unwrap_or(0.0))
+                .fold(0.0f32, f32::max);
+            let first_variant = members.iter().map(|c| c.variant).min().unwrap_or(0);
+            (members, confidence, first_variant)
+        })
+        .max_by(|(a, conf_a, first_a), (b, conf_b, first_b)| {
+            a.len()
+                .cmp(&b.len())
+                .then(conf_a.total_cmp(conf_b))
+                // Empate: prefere o texto mais longo (menos cortado) e,
+                // por último, o da variante que vem antes no config
+                .then(a[0].line.text.len().cmp(&b[0].line.text.len()))
+                .then(first_b.cmp(first_a))
+        })
+        .and_then(|(members, _, _)| {
+            members
+                .into_iter()
+                .max_by(|a, b| {
+                    a.line
+                        .confidence
+                        .unwrap_or(0.0)
+                        .total_cmp(&b.line.confidence.unwrap_or(0.0))
+                        .then(b.variant.cmp(&a.variant))
+                })
+                .map(|c| c.line.clone())
+        })
+}
+
+/// Chave de comparação de texto entre variantes
+fn vote_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Intersection over Union entre os retângulos de duas linhas
+fn iou(a: &DetectedText, b: &DetectedText) -> f64 {
+    let left = a.x.max(b.x);
+    let top = a.y.max(b.y);
+    let right = (a.x + a.width).min(b.x + b.width);
+    let bottom = (a.y + a.height).min(b.y + b.height);
+
+    if right <= left || bottom <= top {
+        return 0.0;
+    }
+
+    let intersection = (right - left) * (bottom - top);
+    let union = a.width * a.height + b.width * b.height - intersection;
+
+    if union <= 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(variant: usize, text: &str, y: f64, confidence: Option<f32>) -> Candidate {
+        Candidate {
+            variant,
+            line: DetectedText {
+                text: text.to_string(),
+                // Cada variante acha a linha um pouco deslocada
+                x: 100.0 + variant as f64 * 3.0,
+                y: y + variant as f64 * 2.0,
+                width: 400.0,
+                height: 30.0,
+                words: Vec::new(),
+                confidence,
+                vertical: false,
+            },
+        }
+    }
+
+    fn texts(lines: &[DetectedText]) -> Vec<&str> {
+        lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn clusters_by_position_and_drops_lines_with_few_votes() {
+        let candidates = vec![
+            candidate(0, "Where is the key?", 100.0, None),
+            candidate(0, "In the cellar.", 200.0, None),
+            candidate(1, "In the ceIlar.", 200.0, None),
+            candidate(1, "Where is the key?", 100.0, None),
+            candidate(2, "Where is the kev?", 100.0, None),
+            // Lixo achado só por uma variante
+            candidate(2, "~~~", 400.0, None),
+            // Um voto só, mas confiança alta: fica
+            candidate(2, "Press A", 600.0, Some(0.95)),
+        ];
+
+        let lines = vote(candidates, 2);
+
+        assert_eq!(
+            texts(&lines),
+            ["Where is the key?", "In the cellar.", "Press A"]
+        );
+    }
+
+    #[test]
+    fn same_variant_never_votes_twice_in_a_cluster() {
+        // Duas linhas quase sobrepostas da mesma variante continuam separadas
+        let candidates = vec![
+            candidate(0, "First", 100.0, None),
+            candidate(0, "Second", 105.0, None),
+        ];
+
+        assert_eq!(texts(&vote(candidates, 1)), ["First", "Second"]);
+    }
+
+    #[test]
+    fn ties_are_decided_by_confidence_then_variant_order() {
+        // Sem confiança (backend do Windows) e textos do mesmo tamanho:
+        // vence sempre a primeira variante, em qualquer ordem de chegada
+        let tie = |first: &str, second: &str| {
+            vote(
+                vec![
+                    candidate(1, second, 100.0, None),
+                    candidate(0, first, 100.0, None),
+                ],
+                1,
+            )
+        };
+        for _ in 0..20 {
+            assert_eq!(texts(&tie("Hallo there", "Hello there")), ["Hallo there"]);
+            assert_eq!(texts(&tie("Hello there", "Hallo there")), ["Hello there"]);
+        }
+
+        // Com confiança, a maior vence o empate de votos
+        let lines = vote(
+            vec![
+                candidate(0, "Hallo there", 100.0, Some(0.6)),
+                candidate(1, "Hello there", 100.0, Some(0.9)),
+            ],
+            1,
+        );
+        assert_eq!(texts(&lines), ["Hello there"]);
+    }
+}
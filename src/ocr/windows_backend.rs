@@ -0,0 +1,361 @@
+// game-translator/src/ocr/windows_backend.rs
+
+// ============================================================================
+// BACKEND WINDOWS OCR - API nativa do Windows 10/11
+// ============================================================================
+//
+// Suporta dois modos:
+//
+// 1. MODO MEMÓRIA (rápido) - Pixels da RAM direto num SoftwareBitmap
+//    (sem codificar/decodificar PNG)
+// 2. MODO ARQUIVO (debug) - Lê imagem de arquivo em disco
+//
+// As engines do OCR ficam em cache (uma por idioma) durante toda a
+// execução: criar uma OcrEngine a cada captura era caro no modo legenda.
+//
+// ============================================================================
+
+use super::{DetectedText, DetectedWord, OcrBackend, OcrResultWithPositions};
+use anyhow::{Context, Result};
+use image::DynamicImage;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+use windows::{
+    core::HSTRING,
+    Globalization::Language,
+    Graphics::Imaging::{BitmapDecoder, BitmapPixelFormat, SoftwareBitmap},
+    Media::Ocr::OcrEngine,
+    Storage::Streams::DataWriter,
+    Storage::{FileAccessMode, StorageFile},
+};
+
+/// Engines já criadas, por idioma ("" = idiomas do perfil do usuário)
+static ENGINE_CACHE: Mutex<Option<HashMap<String, OcrEngine>>> = Mutex::new(None);
+
+/// Backend que usa `Windows.Media.Ocr`
+pub struct WindowsOcrBackend {
+    /// Engine já criada para o idioma escolhido
+    engine: OcrEngine,
+}
+
+impl WindowsOcrBackend {
+    /// Cria a engine para o idioma informado
+    ///
+    /// # Argumentos
+    /// * `language` - Tag BCP-47 (ex: "en", "ja", "pt-BR").
+    ///   `None` = idiomas do perfil do usuário (comportamento antigo)
+    ///
+    /// # Retorna
+    /// * `Err` se o pacote de OCR do idioma não estiver instalado
+    pub fn new(language: Option<&str>) -> Result<Self> {
+        let cache_key = language.unwrap_or("").to_string();
+
+        let mut cache = ENGINE_CACHE.lock().unwrap();
+        let engines = cache.get_or_insert_with(HashMap::new);
+
+        // Reaproveita a engine se o idioma já foi usado antes
+        if let Some(engine) = engines.get(&cache_key) {
+            return Ok(WindowsOcrBackend {
+                engine: engine.clone(),
+            });
+        }
+
+        let started = Instant::now();
+        let engine = match language {
+            None => {
+                info!("🌍 OCR usando os idiomas do perfil do Windows");
+                OcrEngine::TryCreateFromUserProfileLanguages()
+                    .context("Falha ao criar engine OCR")?
+            }
+            Some(tag) => {
+                let recognizer_language = find_recognizer_language(tag)?;
+                info!(
+                    "🌍 OCR usando o idioma: {}",
+                    recognizer_language.LanguageTag()?.to_string_lossy()
+                );
+                OcrEngine::TryCreateFromLanguage(&recognizer_language)
+                    .with_context(|| format!("Falha ao criar engine OCR para o idioma '{}'", tag))?
+            }
+        };
+
+        info!(
+            "   ⏱️  Engine OCR criada em {} ms (fica em cache)",
+            started.elapsed().as_millis()
+        );
+
+        engines.insert(cache_key, engine.clone());
+        Ok(WindowsOcrBackend { engine })
+    }
+}
+
+impl OcrBackend for WindowsOcrBackend {
+    fn name(&self) -> &'static str {
+        "Windows OCR"
+    }
+
+    fn recognize(&self, image: &DynamicImage) -> Result<OcrResultWithPositions> {
+        extract_text_from_memory(&self.engine, image)
+    }
+
+    fn recognize_file(&self, image_path: &Path) -> Result<OcrResultWithPositions> {
+        extract_text_with_positions(&self.engine, image_path)
+    }
+}
+
+/// Procura, entre os pacotes de OCR instalados, o que atende o idioma
+///
+/// Primeiro tenta a tag exata ("pt-BR"), depois só o idioma ("pt" → "pt-PT").
+fn find_recognizer_language(tag: &str) -> Result<Language> {
+    let available =
+        OcrEngine::AvailableRecognizerLanguages().context("Falha ao listar idiomas de OCR")?;
+
+    let mut installed: Vec<(String, Language)> = Vec::new();
+    for i in 0..available.Size()? {
+        let language = available.GetAt(i)?;
+        installed.push((language.LanguageTag()?.to_string_lossy(), language));
+    }
+
+    let primary = |t: &str| t.split('-').next().unwrap_or("").to_lowercase();
+
+    let found = installed
+        .iter()
+        .find(|(installed_tag, _)| installed_tag.eq_ignore_ascii_case(tag))
+        .or_else(|| {
+            installed
+                .iter()
+                .find(|(installed_tag, _)| primary(installed_tag) == primary(tag))
+        });
+
+    match found {
+        Some((_, language)) => Ok(language.clone()),
+        None => {
+            let installed_tags: Vec<&str> = installed.iter().map(|(t, _)| t.as_str()).collect();
+            anyhow::bail!(
+                "Pacote de OCR do idioma '{}' não está instalado no Windows (instalados: {}). \
+                 Instale em: Configurações > Hora e idioma > Idioma e região > Adicionar idioma",
+                tag,
+                installed_tags.join(", ")
+            )
+        }
+    }
+}
+
+// ============================================================================
+// OCR DA MEMÓRIA (MODO RÁPIDO)
+// ============================================================================
+
+/// Extrai texto COM posições de uma imagem em memória (não usa disco)
+///
+/// Este é o modo mais rápido: os pixels vão direto para um SoftwareBitmap,
+/// sem codificar PNG, sem stream e sem BitmapDecoder.
+///
+/// # Argumentos
+/// * `engine` - Engine do Windows OCR (já configurada com o idioma)
+/// * `image` - Imagem em memória (DynamicImage)
+///
+/// # Retorna
+/// * `Result<OcrResultWithPositions>` - Texto extraído com posições
+fn extract_text_from_memory(
+    engine: &OcrEngine,
+    image: &DynamicImage,
+) -> Result<OcrResultWithPositions> {
+    info!("🔍 Executando Windows OCR (memória)...");
+    let started = Instant::now();
+
+    // ========================================================================
+    // PASSO 1: Converter pixels para BGRA8 (formato nativo do SoftwareBitmap)
+    // ========================================================================
+    let bitmap = image_to_software_bitmap(image)?;
+    let converted = Instant::now();
+
+    // ========================================================================
+    // PASSO 2: Executar OCR
+    // ========================================================================
+    let result = engine
+        .RecognizeAsync(&bitmap)
+        .context("Falha ao executar OCR")?
+        .get()
+        .context("Falha ao obter resultado OCR")?;
+    let recognized = Instant::now();
+
+    info!(
+        "   ⏱️  OCR: bitmap {} ms + reconhecimento {} ms = {} ms ({}x{})",
+        (converted - started).as_millis(),
+        (recognized - converted).as_millis(),
+        (recognized - started).as_millis(),
+        image.width(),
+        image.height()
+    );
+
+    // ========================================================================
+    // PASSO 3: Extrair texto e posições
+    // ========================================================================
+    extract_lines_from_result(&result)
+}
+
+/// Copia os pixels da imagem para um SoftwareBitmap BGRA8
+fn image_to_software_bitmap(image: &DynamicImage) -> Result<SoftwareBitmap> {
+    let (width, height) = (image.width(), image.height());
+
+    let max_dimension = OcrEngine::MaxImageDimension().unwrap_or(u32::MAX);
+    if width > max_dimension || height > max_dimension {
+        warn!(
+            "⚠️  Imagem {}x{} maior que o limite do Windows OCR ({} px)",
+            width, height, max_dimension
+        );
+    }
+
+    // RGBA → BGRA (troca vermelho e azul)
+    let mut pixels = image.to_rgba8().into_raw();
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+
+    let writer = DataWriter::new().context("Falha ao criar DataWriter")?;
+    writer
+        .WriteBytes(&pixels)
+        .context("Falha ao escrever pixels")?;
+    let buffer = writer.DetachBuffer().context("Falha ao obter buffer")?;
+
+    SoftwareBitmap::CreateCopyFromBuffer(
+        &buffer,
+        BitmapPixelFormat::Bgra8,
+        width as i32,
+        height as i32,
+    )
+    .context("Falha ao criar SoftwareBitmap")
+}
+
+// ============================================================================
+// OCR DE ARQUIVO (MODO DEBUG)
+// ============================================================================
+
+/// Extrai texto COM posições de um arquivo de imagem em disco
+///
+/// Mais lento que o modo memória, mas útil para debug (pode ver o screenshot.png)
+///
+/// # Argumentos
+/// * `engine` - Engine do Windows OCR (já configurada com o idioma)
+/// * `image_path` - Caminho para a imagem
+///
+/// # Retorna
+/// * `Result<OcrResultWithPositions>` - Texto extraído com posições
+fn extract_text_with_positions(
+    engine: &OcrEngine,
+    image_path: &Path,
+) -> Result<OcrResultWithPositions> {
+    info!("🔍 Executando Windows OCR (arquivo): {:?}", image_path);
+
+    // ========================================================================
+    // PASSO 1: Abrir arquivo de imagem
+    // ========================================================================
+    let absolute_path = image_path
+        .canonicalize()
+        .context("Falha ao obter caminho absoluto")?;
+
+    let path_str = absolute_path
+        .to_string_lossy()
+        .to_string()
+        .trim_start_matches(r"\\?\")
+        .to_string();
+
+    let path_hstring = HSTRING::from(&path_str);
+
+    let file = StorageFile::GetFileFromPathAsync(&path_hstring)
+        .context("Falha ao abrir arquivo")?
+        .get()
+        .context("Falha ao obter arquivo")?;
+
+    let stream = file
+        .OpenAsync(FileAccessMode::Read)
+        .context("Falha ao abrir stream")?
+        .get()
+        .context("Falha ao obter stream")?;
+
+    // ========================================================================
+    // PASSO 2: Decodificar e executar OCR
+    // ========================================================================
+    let decoder = BitmapDecoder::CreateAsync(&stream)
+        .context("Falha ao criar decoder")?
+        .get()
+        .context("Falha ao decodificar")?;
+
+    let bitmap = decoder
+        .GetSoftwareBitmapAsync()
+        .context("Falha ao criar bitmap")?
+        .get()
+        .context("Falha ao obter bitmap")?;
+
+    let result = engine
+        .RecognizeAsync(&bitmap)
+        .context("Falha ao executar OCR")?
+        .get()
+        .context("Falha ao obter resultado")?;
+
+    // ========================================================================
+    // PASSO 3: Extrair texto e posições
+    // ========================================================================
+    extract_lines_from_result(&result)
+}
+
+// ============================================================================
+// FUNÇÃO AUXILIAR - Extrai linhas do resultado do OCR
+// ============================================================================
+
+/// Extrai as linhas de texto e suas posições do resultado do Windows OCR
+fn extract_lines_from_result(
+    result: &windows::Media::Ocr::OcrResult,
+) -> Result<OcrResultWithPositions> {
+    let full_text = result
+        .Text()
+        .context("Falha ao obter texto")?
+        .to_string_lossy();
+
+    let ocr_lines = result.Lines().context("Falha ao obter linhas")?;
+
+    let mut lines: Vec<DetectedText> = Vec::new();
+
+    for i in 0..ocr_lines.Size()? {
+        let line = ocr_lines.GetAt(i)?;
+        let line_text = line.Text()?.to_string_lossy();
+        let words = line.Words()?;
+
+        // Coleta cada palavra com seu próprio retângulo
+        // (Windows OCR não informa confiança)
+        let mut detected_words: Vec<DetectedWord> = Vec::new();
+        for j in 0..words.Size()? {
+            let word = words.GetAt(j)?;
+            let rect = word.BoundingRect()?;
+
+            detected_words.push(DetectedWord {
+                text: word.Text()?.to_string_lossy(),
+                x: rect.X as f64,
+                y: rect.Y as f64,
+                width: rect.Width as f64,
+                height: rect.Height as f64,
+                confidence: None,
+            });
+        }
+
+        // Bounding box da linha = união das palavras
+        if let Some(detected) = DetectedText::from_words(line_text, detected_words) {
+            info!(
+                "   📍 Linha {}: \"{}\" em ({:.0}, {:.0}) {}x{}",
+                i,
+                detected.text,
+                detected.x,
+                detected.y,
+                detected.width as i32,
+                detected.height as i32
+            );
+
+            lines.push(detected);
+        }
+    }
+
+    info!("✅ OCR completo: {} linhas detectadas", lines.len());
+
+    Ok(OcrResultWithPositions { full_text, lines })
+}
@@ -0,0 +1,492 @@
+// game-translator/src/prewarm.rs
+
+// ============================================================================
+// MÓDULO PREWARM - Pré-aquece o cache a partir de dumps de texto do jogo
+// ============================================================================
+//
+// Muitos jogos guardam os diálogos em arquivos de texto, JSON ou CSV.
+// Este módulo lê esses arquivos, extrai as linhas únicas e traduz tudo
+// em background, salvando no TranslationCache. Assim, quando o texto
+// aparece no jogo, a tradução já está no cache (instantânea e offline).
+//
+// Características:
+// - Rate limiting: pausa entre lotes para não estourar limites da API
+// - Retomável: linhas que já estão no cache são puladas, e o cache é
+//   salvo em disco a cada lote. Se o processo parar no meio, basta rodar
+//   de novo que ele continua de onde parou.
+//
+// ============================================================================
+
+use crate::cache::TranslationCache;
+use crate::config::{Config, SpeakerConfig};
+use crate::correction::{self, CorrectionEngine};
+use crate::speaker;
+use crate::translator;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Número de tentativas por lote antes de desistir
+const MAX_RETRIES: u32 = 3;
+
+/// Base mínima da espera entre tentativas (com --prewarm-delay 0 a espera
+/// seria zero e as novas tentativas martelariam a API no rate limit)
+const MIN_RETRY_DELAY_MS: u64 = 500;
+
+/// Opções do pré-aquecimento (vindas da linha de comando)
+#[derive(Debug, Clone)]
+pub struct PrewarmOptions {
+    /// Arquivo com o dump de texto (.txt, .json ou .csv)
+    pub path: PathBuf,
+    /// Quantidade de linhas enviadas por requisição
+    pub batch_size: usize,
+    /// Pausa entre lotes em milissegundos (rate limiting)
+    pub delay_ms: u64,
+    /// Coluna do CSV a usar (None = todas as colunas com texto)
+    pub csv_column: Option<usize>,
+    /// Campo do JSON a usar, em qualquer nível (None = todos os valores)
+    pub json_field: Option<String>,
+}
+
+impl PrewarmOptions {
+    /// Cria opções com valores padrão para o arquivo informado
+    pub fn new(path: PathBuf) -> Self {
+        PrewarmOptions {
+            path,
+            batch_size: 20,
+            delay_ms: 1000,
+            csv_column: None,
+            json_field: None,
+        }
+    }
+}
+
+// ============================================================================
+// EXTRAÇÃO DE LINHAS
+// ============================================================================
+
+/// Lê o arquivo de dump e retorna as linhas únicas, já normalizadas
+///
+/// O formato é detectado pela extensão:
+/// * `.json` - valores string (recursivamente, ignora chaves), todos
+///   ou só os do campo `json_field`
+/// * `.csv`  - campos do CSV (todas as colunas ou só `csv_column`)
+/// * outros  - texto puro, uma linha por entrada
+///
+/// IDs e caminhos guardados como valor ("npc_intro_01", "sfx/door.wav")
+/// são descartados, para não gastar a cota da API com eles.
+/// As linhas passam pelo mesmo corretor do OCR e pela mesma separação do
+/// falante ("NOME: fala" → "fala") para gerar a mesma chave de cache.
+pub fn extract_lines(
+    path: &Path,
+    options: &PrewarmOptions,
+    corrector: &CorrectionEngine,
+    speaker_config: &SpeakerConfig,
+) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Falha ao ler dump de texto: {:?}", path))?;
+
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let raw_lines: Vec<String> = match extension.as_str() {
+        "json" => {
+            let json: serde_json::Value =
+                serde_json::from_str(&contents).context("Falha ao parsear dump JSON")?;
+            let mut strings = Vec::new();
+            collect_json_strings(&json, options.json_field.as_deref(), &mut strings);
+            strings
+        }
+        "csv" => csv_fields(&contents, options.csv_column),
+        _ => contents.lines().map(|l| l.to_string()).collect(),
+    };
+
+    // Normaliza como o pipeline do jogo faz (mesma chave de cache)
+    // e remove duplicadas mantendo a ordem original
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    for raw in raw_lines {
+        let collapsed = raw.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let mut cleaned = corrector.clean(&collapsed);
+
+        // No jogo só a fala é traduzida (o nome segue à parte)
+        if speaker_config.enabled {
+            cleaned = speaker::split_prefix(&cleaned, speaker_config).1;
+        }
+
+        // Ignora IDs, números e linhas vazias
+        if !cleaned.chars().any(|c| c.is_alphabetic()) || looks_like_identifier(&cleaned) {
+            continue;
+        }
+
+        if seen.insert(cleaned.clone()) {
+            lines.push(cleaned);
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Texto que parece ID, chave ou caminho de arquivo, e não uma fala
+///
+/// Uma "palavra" só, em um destes formatos:
+/// - snake_case: "npc_intro_01", "QUEST_START"
+/// - caminho com 2+ separadores: "audio/sfx/door.wav"
+/// - chave com pontos, em minúsculas: "menu.title", "ui.button.ok"
+///
+/// Fala de uma palavra continua passando: "and/or", "U.S.A.", "Wait...".
+fn looks_like_identifier(text: &str) -> bool {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        return false;
+    }
+
+    let snake_case =
+        text.contains('_') && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    let path = text.matches(['/', '\\']).count() >= 2;
+
+    let dotted_key = text.contains('.')
+        && text.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+
+    snake_case || path || dotted_key
+}
+
+/// Espera antes da tentativa seguinte: dobra a cada falha, a partir de
+/// `delay_ms` (com um mínimo de MIN_RETRY_DELAY_MS)
+fn retry_delay(delay_ms: u64, attempt: u32) -> Duration {
+    Duration::from_millis(delay_ms.max(MIN_RETRY_DELAY_MS) * 2u64.pow(attempt))
+}
+
+/// Coleta recursivamente os valores string de um JSON
+///
+/// Com `field`, só entram os valores (e arrays de valores) das chaves
+/// com esse nome, em qualquer nível: `{"id": "x", "text": "Olá"}`.
+fn collect_json_strings(value: &serde_json::Value, field: Option<&str>, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) if field.is_none() => out.push(s.clone()),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_json_strings(item, field, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map {
+                if field == Some(key.as_str()) {
+                    collect_json_strings(item, None, out);
+                } else {
+                    collect_json_strings(item, field, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Campos do CSV em ordem (todas as colunas ou só `column`)
+fn csv_fields(contents: &str, column: Option<usize>) -> Vec<String> {
+    parse_csv(contents)
+        .into_iter()
+        .flat_map(|record| match column {
+            Some(column) => record.into_iter().skip(column).take(1).collect(),
+            None => record,
+        })
+        .collect()
+}
+
+/// Parser simples de CSV (aceita campos entre aspas, aspas escapadas ""
+/// e quebras de linha dentro de campos entre aspas)
+fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+// ============================================================================
+// TRADUÇÃO EM BACKGROUND
+// ============================================================================
+
+/// Inicia o pré-aquecimento do cache em uma thread separada
+///
+/// Usa o mesmo cache do app, então as traduções ficam disponíveis
+/// imediatamente para o modo legenda e para as hotkeys.
+pub fn start_prewarm_thread(
+    config: Arc<Mutex<Config>>,
+    cache: TranslationCache,
+    options: PrewarmOptions,
+) {
+    thread::spawn(move || {
+        info!("🔥 Thread de pré-aquecimento iniciada: {:?}", options.path);
+
+        if let Err(e) = prewarm_cache(&config, &cache, &options) {
+            error!("❌ Erro no pré-aquecimento do cache: {}", e);
+        }
+    });
+}
+
+/// Traduz todas as linhas do dump que ainda não estão no cache
+fn prewarm_cache(
+    config: &Arc<Mutex<Config>>,
+    cache: &TranslationCache,
+    options: &PrewarmOptions,
+) -> Result<()> {
+    let (corrector, speaker_config) = {
+        let config = config.lock().unwrap();
+        (
+            correction::engine_for(&config.app_config),
+            config.app_config.speaker.clone(),
+        )
+    };
+    let lines = extract_lines(&options.path, options, &corrector, &speaker_config)?;
+    let batch_size = options.batch_size.max(1);
+
+    info!("🔥 {} linhas únicas encontradas no dump", lines.len());
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut translated_count = 0;
+    let mut skipped_count = 0;
+
+    for (batch_index, batch) in lines.chunks(batch_size).enumerate() {
+        // Pega do app_config a cada lote pra ter hot reload
+        let (api_key, provider, source_lang, target_lang, libre_url) = {
+            let config = config.lock().unwrap();
+            (
+                config.app_config.translation.deepl_api_key.clone(),
+                config.app_config.translation.provider.clone(),
+                config.app_config.translation.source_language.clone(),
+                config.app_config.translation.target_language.clone(),
+                config.app_config.translation.libretranslate_url.clone(),
+            )
+        };
+
+        // Pula o que já está no cache (é isso que torna o processo retomável)
+        let (cached, not_cached) = cache.get_batch(&provider, &source_lang, &target_lang, batch);
+        skipped_count += cached.len();
+
+        if not_cached.is_empty() {
+            continue;
+        }
+
+        let texts_to_api: Vec<String> = not_cached.into_iter().map(|(_, t)| t).collect();
+
+        // Tenta algumas vezes com espera crescente antes de desistir
+        let mut attempt = 0;
+        let translations = loop {
+            attempt += 1;
+            let result = runtime.block_on(async {
+                translator::translate_batch_with_provider(
+                    &texts_to_api,
+                    &provider,
+                    &api_key,
+                    &source_lang,
+                    &target_lang,
+                    Some(&libre_url),
+                )
+                .await
+            });
+
+            match result {
+                Ok(translations) => break translations,
+                Err(e) if attempt < MAX_RETRIES => {
+                    warn!(
+                        "⚠️  Lote {} falhou (tentativa {}/{}): {}",
+                        batch_index + 1,
+                        attempt,
+                        MAX_RETRIES,
+                        e
+                    );
+                    thread::sleep(retry_delay(options.delay_ms, attempt));
+                }
+                Err(e) => {
+                    // Salva o progresso antes de sair, para retomar depois
+                    let _ = cache.save_to_disk();
+                    return Err(e.context(format!(
+                        "Pré-aquecimento interrompido no lote {} ({} linhas traduzidas)",
+                        batch_index + 1,
+                        translated_count
+                    )));
+                }
+            }
+        };
+
+        let pairs: Vec<(String, String)> = texts_to_api
+            .into_iter()
+            .zip(translations)
+            .filter(|(_, translated)| !translated.is_empty())
+            .collect();
+
+        translated_count += pairs.len();
+        cache.set_batch(&provider, &source_lang, &target_lang, &pairs);
+
+        // Salva a cada lote para poder retomar se o programa fechar
+        let _ = cache.save_to_disk();
+
+        info!(
+            "🔥 Pré-aquecimento: {}/{} linhas ({} já estavam no cache)",
+            translated_count + skipped_count,
+            lines.len(),
+            skipped_count
+        );
+
+        // Rate limiting entre lotes
+        thread::sleep(Duration::from_millis(options.delay_ms));
+    }
+
+    info!(
+        "✅ Pré-aquecimento concluído: {} traduzidas, {} já estavam no cache",
+        translated_count, skipped_count
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quoting_and_column_extraction() {
+        let contents =
+            "id,text\r\nnpc_01,\"Hello, \"\"friend\"\"\"\nnpc_02,\"Two\nlines\"\nnpc_03,";
+
+        assert_eq!(
+            parse_csv(contents),
+            vec![
+                vec!["id", "text"],
+                vec!["npc_01", "Hello, \"friend\""],
+                vec!["npc_02", "Two\nlines"],
+                vec!["npc_03", ""],
+            ]
+        );
+        assert_eq!(
+            csv_fields(contents, Some(1)),
+            vec!["text", "Hello, \"friend\"", "Two\nlines", ""]
+        );
+        assert_eq!(csv_fields(contents, Some(5)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn json_skips_identifiers_and_honours_field() {
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{"lines": [
+                {"id": "npc_intro_01", "sound": "audio/sfx/door.wav", "text": "The door is locked."},
+                {"id": "menu.title", "text": ["Wait...", "Run!"], "speaker": "Guard"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let mut all = Vec::new();
+        collect_json_strings(&json, None, &mut all);
+        let mut kept: Vec<&String> = all.iter().filter(|s| !looks_like_identifier(s)).collect();
+        kept.sort();
+        assert_eq!(kept, ["Guard", "Run!", "The door is locked.", "Wait..."]);
+
+        let mut text_only = Vec::new();
+        collect_json_strings(&json, Some("text"), &mut text_only);
+        text_only.sort();
+        assert_eq!(text_only, ["Run!", "The door is locked.", "Wait..."]);
+
+        // Fala de uma palavra com barra ou pontos não é identificador
+        for speech in [
+            "and/or", "U.S.A.", "e.g.", "Yes/No?", "Mr.Smith", "...", "3:00",
+        ] {
+            assert!(!looks_like_identifier(speech), "{}", speech);
+        }
+        for id in ["QUEST_START", "ui.button.ok", "data\\text\\intro.txt"] {
+            assert!(looks_like_identifier(id), "{}", id);
+        }
+    }
+
+    #[test]
+    fn retries_back_off_even_without_delay() {
+        assert_eq!(
+            retry_delay(0, 1),
+            Duration::from_millis(2 * MIN_RETRY_DELAY_MS)
+        );
+        assert_eq!(
+            retry_delay(0, 2),
+            Duration::from_millis(4 * MIN_RETRY_DELAY_MS)
+        );
+        assert_eq!(retry_delay(2000, 1), Duration::from_millis(4000));
+    }
+
+    #[test]
+    fn speaker_prefix_is_split_like_the_game_does() {
+        let path = std::env::temp_dir().join(format!(
+            "game-translator-prewarm-{}.txt",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            "ALICE: Where are you going?\nObjective: Find the key\n",
+        )
+        .unwrap();
+
+        let corrector = CorrectionEngine::new(&Default::default(), "en");
+        let options = PrewarmOptions::new(path.clone());
+        let speaker_config = SpeakerConfig {
+            enabled: true,
+            ..SpeakerConfig::default()
+        };
+
+        assert_eq!(
+            extract_lines(&path, &options, &corrector, &speaker_config).unwrap(),
+            ["Where are you going?", "Objective: Find the key"]
+        );
+        assert_eq!(
+            extract_lines(&path, &options, &corrector, &SpeakerConfig::default()).unwrap(),
+            ["ALICE: Where are you going?", "Objective: Find the key"]
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -11,6 +11,15 @@
 // de transparência: a área ao redor da seleção fica escurecida,
 // enquanto a área de seleção fica 100% transparente (mostra a tela real).
 //
+// A janela cobre o desktop virtual inteiro (todos os monitores). As
+// coordenadas devolvidas são do desktop virtual e podem ser negativas
+// (monitor à esquerda/acima do principal); quem chama converte para o
+// monitor da região com screenshot::to_monitor_region.
+//
+// Quem chama pode passar caixas candidatas (texto achado pela detecção
+// automática, ver text_regions.rs). Elas aparecem com borda verde e um
+// clique dentro de uma delas já escolhe a caixa, sem precisar arrastar.
+//
 // Tecnologias usadas:
 // - winapi: Criação de janela Win32, mensagens, GDI + bitmap BGRA
 // - UpdateLayeredWindow: transparência per-pixel (cada pixel tem seu alpha)
@@ -62,7 +71,11 @@ use winapi::um::winuser::{
     IDC_CROSS,
     MSG,
     SM_CXSCREEN,
+    SM_CXVIRTUALSCREEN,
     SM_CYSCREEN,
+    SM_CYVIRTUALSCREEN,
+    SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN,
     SW_SHOW,
     ULW_ALPHA, // Flag para usar alpha per-pixel
     VK_ESCAPE,
@@ -104,16 +117,25 @@ const BORDER_COLOR_R: u8 = 0x00;
 /// Espessura da borda em pixels
 const BORDER_WIDTH: i32 = 3;
 
+/// Cor da borda das caixas candidatas em BGR (verde)
+const CANDIDATE_COLOR: [u8; 3] = [0x44, 0xDD, 0x44];
+
+/// Cor da caixa candidata sob o mouse em BGR (amarelo)
+const HOVER_COLOR: [u8; 3] = [0x00, 0xDD, 0xFF];
+
+/// Espessura da borda das caixas candidatas em pixels
+const CANDIDATE_BORDER_WIDTH: i32 = 2;
+
 // ============================================================================
 // ESTRUTURAS DE DADOS
 // ============================================================================
 
-/// Coordenadas da região selecionada (mesma interface da versão anterior)
-/// O main.rs usa essa struct, então mantemos ela igual
+/// Coordenadas da região selecionada, no desktop virtual
+/// (x/y podem ser negativos em monitores à esquerda/acima do principal)
 #[derive(Debug, Clone)]
 pub struct SelectedRegion {
-    pub x: u32,
-    pub y: u32,
+    pub x: i32,
+    pub y: i32,
     pub width: u32,
     pub height: u32,
 }
@@ -131,10 +153,19 @@ struct SelectorState {
     result: Option<SelectedRegion>,
     /// Se o usuário cancelou (ESC)
     cancelled: bool,
+    /// Canto superior esquerdo do desktop virtual (origem da janela)
+    origin_x: i32,
+    origin_y: i32,
     /// Largura da tela em pixels
     screen_width: i32,
     /// Altura da tela em pixels
     screen_height: i32,
+    /// Modo conta-gotas: um clique escolhe um ponto (sem arrastar)
+    pick_mode: bool,
+    /// Caixas candidatas em coordenadas da janela: (x1, y1, x2, y2)
+    candidates: Vec<(i32, i32, i32, i32)>,
+    /// Índice da caixa candidata sob o mouse
+    hovered: Option<usize>,
 }
 
 // ============================================================================
@@ -147,6 +178,9 @@ static SELECTOR_RESULT: Mutex<Option<Option<SelectedRegion>>> = Mutex::new(None)
 /// Título exibido no topo da tela durante a seleção
 static SELECTOR_TITLE: Mutex<Option<String>> = Mutex::new(None);
 
+/// Caixas candidatas da seleção atual (no desktop virtual)
+static SELECTOR_CANDIDATES: Mutex<Vec<SelectedRegion>> = Mutex::new(Vec::new());
+
 // ============================================================================
 // FUNÇÃO PÚBLICA - PONTO DE ENTRADA
 // ============================================================================
@@ -154,26 +188,35 @@ static SELECTOR_TITLE: Mutex<Option<String>> = Mutex::new(None);
 /// Abre a interface de seleção de região e retorna a região selecionada
 ///
 /// Cria um overlay transparente sobre toda a tela. O usuário clica e
-/// arrasta para selecionar uma região. ESC cancela.
+/// arrasta para selecionar uma região, ou clica numa caixa candidata.
+/// ESC cancela.
 ///
 /// # Parâmetros
 /// * `title` - Texto opcional exibido no topo da tela (ex: "SELEÇÃO ÁREA DE LEGENDA")
+/// * `candidates` - Caixas sugeridas, no desktop virtual (pode ser vazio)
 ///
 /// # Retorna
 /// * `Ok(Some(SelectedRegion))` - Região selecionada com sucesso
 /// * `Ok(None)` - Usuário cancelou (ESC)
 /// * `Err(...)` - Erro ao criar janela
-pub fn select_region(title: Option<&str>) -> Result<Option<SelectedRegion>> {
+pub fn select_region(
+    title: Option<&str>,
+    candidates: &[SelectedRegion],
+) -> Result<Option<SelectedRegion>> {
     info!("🎯 Iniciando seletor de região (overlay transparente)...");
+    if !candidates.is_empty() {
+        info!("   🟩 {} caixa(s) de texto sugerida(s)", candidates.len());
+    }
 
     // Limpa resultado anterior
     *SELECTOR_RESULT.lock().unwrap() = None;
     *SELECTOR_TITLE.lock().unwrap() = title.map(|s| s.to_string());
+    *SELECTOR_CANDIDATES.lock().unwrap() = candidates.to_vec();
 
     // Cria e executa a janela do seletor
     // Essa função bloqueia até o usuário selecionar ou cancelar
     unsafe {
-        create_selector_window()?;
+        create_selector_window(false)?;
     }
 
     // Pega o resultado
@@ -194,12 +237,89 @@ pub fn select_region(title: Option<&str>) -> Result<Option<SelectedRegion>> {
     Ok(result)
 }
 
+/// Conta-gotas: o usuário clica num pixel da tela e recebe a cor dele
+///
+/// A tela é capturada ANTES do overlay abrir (o escurecimento não entra
+/// na cor). A cor devolvida é a média 3x3 ao redor do clique, para não
+/// pegar um pixel de antialiasing da borda da letra por azar.
+///
+/// # Retorna
+/// * `Ok(Some([r, g, b]))` - Cor escolhida
+/// * `Ok(None)` - Usuário cancelou (ESC)
+pub fn pick_color(title: Option<&str>) -> Result<Option<[u8; 3]>> {
+    info!("🎨 Iniciando conta-gotas...");
+
+    // Uma captura por monitor (o clique pode ser em qualquer um)
+    let captures = crate::screenshot::monitors()?
+        .into_iter()
+        .map(|monitor| {
+            let image = crate::screenshot::capture_screen_to_memory(Some(monitor.index))?;
+            Ok((monitor, image.to_rgb8()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    *SELECTOR_RESULT.lock().unwrap() = None;
+    *SELECTOR_TITLE.lock().unwrap() = title.map(|s| s.to_string());
+    SELECTOR_CANDIDATES.lock().unwrap().clear();
+
+    unsafe {
+        create_selector_window(true)?;
+    }
+
+    let point = SELECTOR_RESULT.lock().unwrap().take().unwrap_or(None);
+    let Some(point) = point else {
+        info!("❌ Conta-gotas cancelado");
+        return Ok(None);
+    };
+
+    let Some((monitor, screen)) = captures.iter().find(|(m, _)| m.contains(point.x, point.y))
+    else {
+        anyhow::bail!("Ponto ({}, {}) fora dos monitores", point.x, point.y);
+    };
+
+    // Ponto relativo à captura do monitor
+    let (px, py) = (point.x - monitor.x, point.y - monitor.y);
+
+    let mut sum = [0u32; 3];
+    let mut count = 0u32;
+    for y in py - 1..=py + 1 {
+        for x in px - 1..=px + 1 {
+            if x >= 0 && y >= 0 && (x as u32) < screen.width() && (y as u32) < screen.height() {
+                let pixel = screen.get_pixel(x as u32, y as u32);
+                for c in 0..3 {
+                    sum[c] += pixel[c] as u32;
+                }
+                count += 1;
+            }
+        }
+    }
+
+    if count == 0 {
+        anyhow::bail!("Ponto ({}, {}) fora da captura da tela", point.x, point.y);
+    }
+
+    let color = [
+        (sum[0] / count) as u8,
+        (sum[1] / count) as u8,
+        (sum[2] / count) as u8,
+    ];
+    info!(
+        "✅ Cor escolhida: #{:02X}{:02X}{:02X} em ({}, {})",
+        color[0], color[1], color[2], point.x, point.y
+    );
+
+    Ok(Some(color))
+}
+
 // ============================================================================
 // CRIAÇÃO DA JANELA WIN32
 // ============================================================================
 
 /// Cria a janela overlay transparente e inicia o loop de mensagens
-unsafe fn create_selector_window() -> Result<()> {
+///
+/// Com `pick_mode` o primeiro clique já fecha o seletor, devolvendo uma
+/// região 1x1 no ponto clicado.
+unsafe fn create_selector_window(pick_mode: bool) -> Result<()> {
     // ========================================================================
     // PASSO 1: Registrar a classe da janela
     // ========================================================================
@@ -224,12 +344,17 @@ unsafe fn create_selector_window() -> Result<()> {
     RegisterClassExW(&wc);
 
     // ========================================================================
-    // PASSO 2: Pegar tamanho da tela
+    // PASSO 2: Pegar tamanho do desktop virtual (todos os monitores)
     // ========================================================================
-    let screen_width = GetSystemMetrics(SM_CXSCREEN);
-    let screen_height = GetSystemMetrics(SM_CYSCREEN);
-
-    info!("   📐 Tela: {}x{}", screen_width, screen_height);
+    let origin_x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+    let origin_y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+    let screen_width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+
+    info!(
+        "   📐 Desktop virtual: {}x{} em ({}, {})",
+        screen_width, screen_height, origin_x, origin_y
+    );
 
     // ========================================================================
     // PASSO 3: Criar a janela
@@ -249,8 +374,8 @@ unsafe fn create_selector_window() -> Result<()> {
         class_name.as_ptr(),
         wide_string("Seletor de Região").as_ptr(),
         WS_POPUP | WS_VISIBLE,
-        0,
-        0,
+        origin_x,
+        origin_y,
         screen_width,
         screen_height,
         ptr::null_mut(),
@@ -266,14 +391,31 @@ unsafe fn create_selector_window() -> Result<()> {
     // ========================================================================
     // PASSO 4: Criar estado e associar à janela
     // ========================================================================
+    // Caixas candidatas passam para coordenadas da janela
+    let candidates = SELECTOR_CANDIDATES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|region| {
+            let x1 = region.x - origin_x;
+            let y1 = region.y - origin_y;
+            (x1, y1, x1 + region.width as i32, y1 + region.height as i32)
+        })
+        .collect();
+
     let state = Box::new(SelectorState {
         start_point: None,
         current_point: POINT { x: 0, y: 0 },
         is_dragging: false,
         result: None,
         cancelled: false,
+        origin_x,
+        origin_y,
         screen_width,
         screen_height,
+        pick_mode,
+        candidates,
+        hovered: None,
     });
 
     SetWindowLongPtrW(hwnd, GWLP_USERDATA, Box::into_raw(state) as isize);
@@ -454,13 +596,58 @@ unsafe fn render_overlay(hwnd: HWND) {
         }
     }
 
+    // Caixas candidatas (só enquanto não está arrastando): interior
+    // transparente, borda verde e o número da caixa em cima
+    if selection.is_none() {
+        for (i, &rect) in state.candidates.iter().enumerate() {
+            let color = if state.hovered == Some(i) {
+                HOVER_COLOR
+            } else {
+                CANDIDATE_COLOR
+            };
+            paint_candidate(pixels, w, h, rect, color);
+            draw_text_on_bitmap(
+                pixels,
+                w,
+                h,
+                &(i + 1).to_string(),
+                rect.0,
+                rect.1 - 12,
+                false,
+            );
+        }
+    }
+
     // ========================================================================
     // PASSO 3: Desenhar texto no bitmap (título e dimensões)
     // ========================================================================
 
+    // Centralizado no monitor principal, que começa em (0, 0) no desktop
+    let primary_center = GetSystemMetrics(SM_CXSCREEN) / 2 - state.origin_x;
+
     // Desenha título centralizado (se houver)
     if let Some(ref title) = *SELECTOR_TITLE.lock().unwrap() {
-        draw_text_on_bitmap(pixels, w, h, title, w / 2, 50, true);
+        draw_text_on_bitmap(
+            pixels,
+            w,
+            h,
+            title,
+            primary_center,
+            50 - state.origin_y,
+            true,
+        );
+    }
+
+    if !state.candidates.is_empty() {
+        draw_text_on_bitmap(
+            pixels,
+            w,
+            h,
+            "CLIQUE EM UMA CAIXA VERDE OU ARRASTE",
+            primary_center,
+            70 - state.origin_y,
+            true,
+        );
     }
 
     // Desenha dimensões da seleção acima do retângulo
@@ -482,6 +669,10 @@ unsafe fn render_overlay(hwnd: HWND) {
     // - pblend: configuração de blending (AC_SRC_ALPHA para per-pixel)
     //
     let pt_zero = POINT { x: 0, y: 0 };
+    let pt_window = POINT {
+        x: state.origin_x,
+        y: state.origin_y,
+    };
     let size = SIZE { cx: w, cy: h };
 
     // BLENDFUNCTION configura como o bitmap é misturado com a tela
@@ -494,14 +685,14 @@ unsafe fn render_overlay(hwnd: HWND) {
 
     UpdateLayeredWindow(
         hwnd,
-        hdc_screen,                             // DC destino (tela)
-        &pt_zero as *const POINT as *mut POINT, // Posição da janela na tela
-        &size as *const SIZE as *mut SIZE,      // Tamanho
-        hdc_mem,                                // DC fonte (nosso bitmap)
-        &pt_zero as *const POINT as *mut POINT, // Posição no bitmap
-        0,                                      // Cor de transparência (não usada com ULW_ALPHA)
-        &blend as *const _ as *mut _,           // Configuração de blending
-        ULW_ALPHA,                              // Usar alpha per-pixel
+        hdc_screen,                               // DC destino (tela)
+        &pt_window as *const POINT as *mut POINT, // Posição da janela na tela
+        &size as *const SIZE as *mut SIZE,        // Tamanho
+        hdc_mem,                                  // DC fonte (nosso bitmap)
+        &pt_zero as *const POINT as *mut POINT,   // Posição no bitmap
+        0,                                        // Cor de transparência (não usada com ULW_ALPHA)
+        &blend as *const _ as *mut _,             // Configuração de blending
+        ULW_ALPHA,                                // Usar alpha per-pixel
     );
 
     // ========================================================================
@@ -513,6 +704,42 @@ unsafe fn render_overlay(hwnd: HWND) {
     winapi::um::winuser::ReleaseDC(ptr::null_mut(), hdc_screen);
 }
 
+/// Pinta uma caixa candidata: interior transparente e borda opaca
+///
+/// `rect` = (x1, y1, x2, y2) em coordenadas da janela (x2/y2 exclusivos).
+fn paint_candidate(
+    pixels: &mut [u8],
+    bw: i32,
+    bh: i32,
+    rect: (i32, i32, i32, i32),
+    color: [u8; 3],
+) {
+    let (x1, y1, x2, y2) = rect;
+    let border = CANDIDATE_BORDER_WIDTH;
+
+    for y in (y1 - border).max(0)..(y2 + border).min(bh) {
+        for x in (x1 - border).max(0)..(x2 + border).min(bw) {
+            let idx = ((y * bw + x) as usize) * 4;
+            let inside = x >= x1 && x < x2 && y >= y1 && y < y2;
+            if inside {
+                pixels[idx..idx + 4].copy_from_slice(&[0, 0, 0, 0]);
+            } else {
+                pixels[idx..idx + 4].copy_from_slice(&[color[0], color[1], color[2], 255]);
+            }
+        }
+    }
+}
+
+/// A menor caixa candidata que contém o ponto (caixas podem se sobrepor)
+fn candidate_at(candidates: &[(i32, i32, i32, i32)], x: i32, y: i32) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, &(x1, y1, x2, y2))| x >= x1 && x < x2 && y >= y1 && y < y2)
+        .min_by_key(|(_, &(x1, y1, x2, y2))| (x2 - x1) as i64 * (y2 - y1) as i64)
+        .map(|(i, _)| i)
+}
+
 // ============================================================================
 // DESENHO DE TEXTO NO BITMAP
 // ============================================================================
@@ -773,6 +1000,18 @@ unsafe extern "system" fn wnd_proc(
                 let x = (lparam & 0xFFFF) as i16 as i32;
                 let y = ((lparam >> 16) & 0xFFFF) as i16 as i32;
 
+                // Conta-gotas: o clique já é o resultado
+                if state.pick_mode {
+                    *SELECTOR_RESULT.lock().unwrap() = Some(Some(SelectedRegion {
+                        x: x + state.origin_x,
+                        y: y + state.origin_y,
+                        width: 1,
+                        height: 1,
+                    }));
+                    DestroyWindow(hwnd);
+                    return 0;
+                }
+
                 state.start_point = Some(POINT { x, y });
                 state.current_point = POINT { x, y };
                 state.is_dragging = true;
@@ -789,14 +1028,22 @@ unsafe extern "system" fn wnd_proc(
         WM_MOUSEMOVE => {
             let state = get_state(hwnd);
             if let Some(state) = state {
-                if state.is_dragging {
-                    let x = (lparam & 0xFFFF) as i16 as i32;
-                    let y = ((lparam >> 16) & 0xFFFF) as i16 as i32;
+                let x = (lparam & 0xFFFF) as i16 as i32;
+                let y = ((lparam >> 16) & 0xFFFF) as i16 as i32;
 
+                if state.is_dragging {
                     state.current_point = POINT { x, y };
 
                     // Redesenha o overlay com a nova posição
                     render_overlay(hwnd);
+                } else {
+                    // Destaca a caixa candidata sob o mouse (só redesenha
+                    // quando ela muda: a tela inteira é repintada)
+                    let hovered = candidate_at(&state.candidates, x, y);
+                    if hovered != state.hovered {
+                        state.hovered = hovered;
+                        render_overlay(hwnd);
+                    }
                 }
             }
             0
@@ -826,12 +1073,24 @@ unsafe extern "system" fn wnd_proc(
 
                         if width > 5 && height > 5 {
                             let region = SelectedRegion {
-                                x: x1 as u32,
-                                y: y1 as u32,
+                                x: x1 + state.origin_x,
+                                y: y1 + state.origin_y,
                                 width: width as u32,
                                 height: height as u32,
                             };
 
+                            state.result = Some(region.clone());
+                            *SELECTOR_RESULT.lock().unwrap() = Some(Some(region));
+                        } else if let Some(i) = candidate_at(&state.candidates, x, y) {
+                            // Clique (sem arrastar) numa caixa candidata
+                            let (cx1, cy1, cx2, cy2) = state.candidates[i];
+                            let region = SelectedRegion {
+                                x: cx1 + state.origin_x,
+                                y: cy1 + state.origin_y,
+                                width: (cx2 - cx1) as u32,
+                                height: (cy2 - cy1) as u32,
+                            };
+
                             state.result = Some(region.clone());
                             *SELECTOR_RESULT.lock().unwrap() = Some(Some(region));
                         }
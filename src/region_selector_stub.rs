@@ -0,0 +1,35 @@
+// game-translator/src/region_selector_stub.rs
+
+// ============================================================================
+// SELETOR DE REGIÃO - Fora do Windows
+// ============================================================================
+//
+// O seletor de verdade (region_selector.rs) é uma janela Win32. Fora do
+// Windows o programa compila (para o modo headless --ocr-score e o CI),
+// mas a seleção de região não está disponível.
+//
+// ============================================================================
+
+use anyhow::Result;
+
+/// Coordenadas da região selecionada (mesma interface do seletor Win32)
+#[derive(Debug, Clone)]
+pub struct SelectedRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Sempre falha: o seletor só existe no Windows
+pub fn select_region(
+    _title: Option<&str>,
+    _candidates: &[SelectedRegion],
+) -> Result<Option<SelectedRegion>> {
+    anyhow::bail!("Seletor de região só está disponível no Windows")
+}
+
+/// Sempre falha: o conta-gotas usa a janela do seletor
+pub fn pick_color(_title: Option<&str>) -> Result<Option<[u8; 3]>> {
+    anyhow::bail!("Conta-gotas só está disponível no Windows")
+}
@@ -4,147 +4,392 @@
 // MÓDULO SCREENSHOT - Captura de tela
 // ============================================================================
 
+use crate::config::{PreprocessConfig, PreprocessStep, RegionConfig};
+use crate::filters;
 use anyhow::{Context, Result};
 use image::{DynamicImage, ImageBuffer, Rgba};
 use screenshots::Screen;
-use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 // ============================================================================
-// CAPTURA DE TELA INTEIRA
+// MONITORES
 // ============================================================================
+//
+// Com mais de um monitor, o Windows monta um "desktop virtual": o monitor
+// principal começa em (0, 0) e os outros ficam ao redor, podendo ter
+// coordenadas NEGATIVAS (monitor à esquerda ou acima do principal).
+//
+// As regiões do config guardam o índice do monitor (`monitor`) e x/y
+// RELATIVOS ao canto do monitor. Assim a região continua certa mesmo se
+// o arranjo dos monitores mudar. Sem `monitor` = monitor principal
+// (comportamento antigo).
+//
+// O processo é Per-Monitor DPI Aware, então tudo aqui é em pixels físicos,
+// mesmo com monitores de escalas diferentes.
+
+/// Um monitor do desktop virtual (pixels físicos)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorInfo {
+    /// Índice usado no config (0 = principal, depois da esquerda para a direita)
+    pub index: u32,
+    /// Identificador do monitor no sistema
+    id: u32,
+    /// Canto superior esquerdo no desktop virtual (pode ser negativo)
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Escala do monitor (1.0 = 96 DPI, 1.5 = 144 DPI...)
+    pub scale: f32,
+    pub is_primary: bool,
+}
 
-/// Captura a tela inteira do monitor principal e salva em um arquivo
-///
-/// # Argumentos
-/// * `output_path` - Caminho onde a imagem será salva
-///
-/// # Retorna
-/// * `Result<DynamicImage>` - Imagem capturada ou erro
-pub fn capture_screen(output_path: &Path) -> Result<DynamicImage> {
-    info!("📸 Capturando tela inteira...");
-
-    // Pega a lista de todos os monitores
-    let screens = Screen::all().context("Falha ao listar monitores")?;
+impl MonitorInfo {
+    /// Converte uma posição relativa ao monitor para o desktop virtual
+    pub fn to_virtual(self, x: u32, y: u32) -> (i32, i32) {
+        (self.x + x as i32, self.y + y as i32)
+    }
 
-    // Pega o monitor principal (índice 0)
-    let screen = screens.get(0).context("Nenhum monitor encontrado")?;
+    /// Verifica se um ponto do desktop virtual está neste monitor
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x < self.x + self.width as i32
+            && y < self.y + self.height as i32
+    }
 
-    info!(
-        "   Monitor: {}x{}",
-        screen.display_info.width, screen.display_info.height
-    );
+    /// Nome para a interface ("1: 2560x1440 em (-2560, 0)")
+    pub fn label(&self) -> String {
+        format!(
+            "{}: {}x{} em ({}, {}){}",
+            self.index + 1,
+            self.width,
+            self.height,
+            self.x,
+            self.y,
+            if self.is_primary { " - principal" } else { "" }
+        )
+    }
+}
 
-    // Captura a imagem do monitor
-    let buffer = screen.capture().context("Falha ao capturar tela")?;
+/// Tempo que a lista de monitores fica em cache
+///
+/// O overlay consulta o monitor a cada frame; monitores quase nunca mudam.
+const MONITOR_CACHE_TTL: Duration = Duration::from_secs(2);
+
+static MONITOR_CACHE: Mutex<Option<(Instant, Vec<MonitorInfo>)>> = Mutex::new(None);
+
+/// Lista os monitores: o principal primeiro, depois da esquerda para a
+/// direita e de cima para baixo
+pub fn monitors() -> Result<Vec<MonitorInfo>> {
+    let mut cache = MONITOR_CACHE.lock().unwrap();
+    if let Some((time, monitors)) = cache.as_ref() {
+        if time.elapsed() < MONITOR_CACHE_TTL {
+            return Ok(monitors.clone());
+        }
+    }
 
-    // Converte o buffer para DynamicImage (formato padrão do crate image)
-    let img = buffer_to_image(&buffer);
+    let mut screens = Screen::all().context("Falha ao listar monitores")?;
+    if screens.is_empty() {
+        anyhow::bail!("Nenhum monitor encontrado");
+    }
+    screens.sort_by_key(|s| {
+        let info = s.display_info;
+        (!info.is_primary, info.x, info.y)
+    });
+
+    let monitors: Vec<MonitorInfo> = screens
+        .iter()
+        .enumerate()
+        .map(|(index, screen)| {
+            let info = screen.display_info;
+            MonitorInfo {
+                index: index as u32,
+                id: info.id,
+                x: info.x,
+                y: info.y,
+                width: info.width,
+                height: info.height,
+                scale: monitor_scale(&info),
+                is_primary: info.is_primary,
+            }
+        })
+        .collect();
 
-    // Salva a imagem em disco
-    img.save(output_path)
-        .context("Falha ao salvar screenshot")?;
+    *cache = Some((Instant::now(), monitors.clone()));
+    Ok(monitors)
+}
 
-    info!("✅ Screenshot salva em: {:?}", output_path);
+/// Monitor de uma região do config (`None` ou índice inexistente = principal)
+pub fn monitor(index: Option<u32>) -> Result<MonitorInfo> {
+    let monitors = monitors()?;
+
+    if let Some(index) = index {
+        match monitors.iter().find(|m| m.index == index) {
+            Some(monitor) => return Ok(*monitor),
+            None => warn!(
+                "⚠️  Monitor {} não encontrado, usando o principal",
+                index + 1
+            ),
+        }
+    }
 
-    Ok(img)
+    Ok(monitors[0])
 }
 
-// ============================================================================
-// CAPTURA DE REGIÃO ESPECÍFICA (NOVO!)
-// ============================================================================
+/// Monitor que contém o ponto do desktop virtual (o mais próximo, se nenhum)
+pub fn monitor_at(x: i32, y: i32) -> Result<MonitorInfo> {
+    let monitors = monitors()?;
 
-/// Captura apenas uma região específica da tela
-///
-/// # Argumentos
-/// * `output_path` - Caminho onde a imagem será salva
-/// * `x` - Posição X do canto superior esquerdo (em pixels)
-/// * `y` - Posição Y do canto superior esquerdo (em pixels)
-/// * `width` - Largura da região (em pixels)
-/// * `height` - Altura da região (em pixels)
+    let nearest = monitors
+        .iter()
+        .min_by_key(|m| {
+            if m.contains(x, y) {
+                return 0;
+            }
+            let dx = (m.x - x).max(x - (m.x + m.width as i32 - 1)).max(0) as i64;
+            let dy = (m.y - y).max(y - (m.y + m.height as i32 - 1)).max(0) as i64;
+            dx * dx + dy * dy
+        })
+        .copied();
+
+    nearest.context("Nenhum monitor encontrado")
+}
+
+/// Converte um retângulo do desktop virtual (ex: vindo do seletor) para
+/// o monitor onde está o centro dele, cortando o que sobrar para fora
 ///
 /// # Retorna
-/// * `Result<DynamicImage>` - Imagem capturada (apenas a região) ou erro
-pub fn capture_region(
-    output_path: &Path,
-    x: u32,
-    y: u32,
+/// * `(monitor, x, y, largura, altura)` com x/y relativos ao monitor
+pub fn to_monitor_region(
+    x: i32,
+    y: i32,
     width: u32,
     height: u32,
-) -> Result<DynamicImage> {
-    info!("📸 Capturando região da tela...");
-    info!("   Posição: ({}, {})", x, y);
-    info!("   Tamanho: {}x{}", width, height);
+) -> Result<(MonitorInfo, u32, u32, u32, u32)> {
+    let monitor = monitor_at(x + width as i32 / 2, y + height as i32 / 2)?;
+
+    let left = x.clamp(monitor.x, monitor.x + monitor.width as i32);
+    let top = y.clamp(monitor.y, monitor.y + monitor.height as i32);
+    let right = (x + width as i32).clamp(left, monitor.x + monitor.width as i32);
+    let bottom = (y + height as i32).clamp(top, monitor.y + monitor.height as i32);
+
+    Ok((
+        monitor,
+        (left - monitor.x) as u32,
+        (top - monitor.y) as u32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    ))
+}
 
-    // ========================================================================
-    // PASSO 1: Capturar a tela inteira primeiro
-    // ========================================================================
-    let screens = Screen::all().context("Falha ao listar monitores")?;
+/// Escala (DPI efetivo / 96) de um monitor
+fn monitor_scale(info: &screenshots::DisplayInfo) -> f32 {
+    #[cfg(windows)]
+    unsafe {
+        use winapi::shared::windef::POINT;
+        use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+        use winapi::um::winuser::{MonitorFromPoint, MONITOR_DEFAULTTONEAREST};
+
+        let point = POINT {
+            x: info.x + 1,
+            y: info.y + 1,
+        };
+        let handle = MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST);
+        let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+        if GetDpiForMonitor(handle, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) == 0 && dpi_x > 0 {
+            return dpi_x as f32 / 96.0;
+        }
+        1.0
+    }
 
-    let screen = screens.get(0).context("Nenhum monitor encontrado")?;
+    #[cfg(not(windows))]
+    {
+        info.scale_factor
+    }
+}
 
-    let buffer = screen.capture().context("Falha ao capturar tela")?;
+/// Screen (crate screenshots) correspondente ao monitor
+fn screen_for(monitor: &MonitorInfo) -> Result<Screen> {
+    Screen::all()
+        .context("Falha ao listar monitores")?
+        .into_iter()
+        .find(|s| s.display_info.id == monitor.id)
+        .with_context(|| format!("Monitor {} não está mais conectado", monitor.index + 1))
+}
 
-    let full_img = buffer_to_image(&buffer);
+/// Captura o monitor inteiro
+fn capture_monitor(monitor: &MonitorInfo) -> Result<DynamicImage> {
+    let screen = screen_for(monitor)?;
 
-    // ========================================================================
-    // PASSO 2: Validar se a região está dentro da tela
-    // ========================================================================
-    let screen_width = full_img.width();
-    let screen_height = full_img.height();
+    info!(
+        "   Monitor {}: {}x{} em ({}, {})",
+        monitor.index + 1,
+        monitor.width,
+        monitor.height,
+        monitor.x,
+        monitor.y
+    );
+
+    let buffer = screen.capture().context("Falha ao capturar tela")?;
+    Ok(buffer_to_image(buffer))
+}
 
-    // Verifica se a região está completamente dentro da tela
-    if x + width > screen_width || y + height > screen_height {
+/// Captura só a região do monitor (x/y relativos ao monitor)
+///
+/// Usa a captura de área do backend: no modo legenda, ler uma faixa de
+/// 1200x100 não precisa mais copiar o monitor inteiro (um frame 4K por
+/// segundo) para depois recortar.
+fn capture_monitor_region(
+    monitor: &MonitorInfo,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<DynamicImage> {
+    // Valida se a região está dentro do monitor
+    if x + width > monitor.width || y + height > monitor.height {
         anyhow::bail!(
-            "Região ({},{} {}x{}) está fora dos limites da tela ({}x{})",
+            "Região ({},{} {}x{}) está fora dos limites do monitor {} ({}x{})",
             x,
             y,
             width,
             height,
-            screen_width,
-            screen_height
+            monitor.index + 1,
+            monitor.width,
+            monitor.height
         );
     }
 
-    // ========================================================================
-    // PASSO 3: Recortar apenas a região desejada
-    // ========================================================================
-    // O método crop() cria uma "view" da região sem copiar dados
-    // Mas precisamos converter para DynamicImage para salvar
-    let cropped = full_img.crop_imm(x, y, width, height);
+    let screen = screen_for(monitor)?;
 
-    // ========================================================================
-    // PASSO 4: Salvar a imagem recortada
-    // ========================================================================
-    cropped
-        .save(output_path)
-        .context("Falha ao salvar screenshot da região")?;
+    // O crate screenshots multiplica a área pela escala do monitor; como
+    // as regiões já estão em pixels físicos, divide antes (no Windows com
+    // Per-Monitor DPI Aware a escala dele é 1.0 e nada muda)
+    let factor = screen.display_info.scale_factor;
+    let logical = |value: u32| -> u32 {
+        if factor > 0.0 && factor != 1.0 {
+            (value as f32 / factor).round() as u32
+        } else {
+            value
+        }
+    };
+
+    let buffer = screen
+        .capture_area(
+            logical(x) as i32,
+            logical(y) as i32,
+            logical(width),
+            logical(height),
+        )
+        .context("Falha ao capturar região")?;
+    let image = buffer_to_image(buffer);
+
+    // Arredondamento da escala pode sobrar 1 pixel
+    if image.width() > width || image.height() > height {
+        return Ok(image.crop_imm(0, 0, width, height));
+    }
 
-    info!("✅ Screenshot da região salva em: {:?}", output_path);
+    Ok(image)
+}
 
-    Ok(cropped)
+/// Captura o monitor inteiro e recorta (o jeito antigo, só para comparar
+/// no --capture-bench)
+fn capture_monitor_region_by_crop(
+    monitor: &MonitorInfo,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<DynamicImage> {
+    let buffer = screen_for(monitor)?
+        .capture()
+        .context("Falha ao capturar tela")?;
+
+    // Cópia extra que o buffer_to_image antigo fazia
+    let rgba = buffer.rgba().to_vec();
+    let full_img = DynamicImage::ImageRgba8(
+        ImageBuffer::from_raw(buffer.width(), buffer.height(), rgba)
+            .context("Falha ao criar ImageBuffer")?,
+    );
+
+    Ok(full_img.crop_imm(x, y, width, height))
+}
+
+/// Mede a captura das regiões do config: antes (monitor inteiro + recorte)
+/// e depois (captura de área), e imprime a comparação
+///
+/// A tabela traz tudo que precisa ir junto com os números publicados:
+/// resolução e escala do monitor, tamanho da região e as duas medianas.
+///
+/// # Argumentos
+/// * `regions` - (nome, região) a medir
+/// * `iterations` - Capturas por método (o relatório usa a mediana)
+pub fn run_capture_benchmark(regions: &[(&str, &RegionConfig)], iterations: usize) -> Result<()> {
+    let iterations = iterations.max(1);
+
+    println!("Captura: {} iterações por método (mediana)", iterations);
+    println!();
+    println!(
+        "{:<10} {:>24} {:>12} {:>12} {:>12} {:>10}",
+        "região", "monitor", "tamanho", "antes", "depois", "ganho"
+    );
+
+    for (name, region) in regions {
+        let monitor = monitor(region.monitor)?;
+
+        let measure = |capture: fn(&MonitorInfo, u32, u32, u32, u32) -> Result<DynamicImage>| {
+            let mut times: Vec<f64> = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                capture(&monitor, region.x, region.y, region.width, region.height)?;
+                times.push(start.elapsed().as_secs_f64() * 1000.0);
+            }
+            times.sort_by(f64::total_cmp);
+            Ok::<f64, anyhow::Error>(times[times.len() / 2])
+        };
+
+        let before = measure(capture_monitor_region_by_crop)?;
+        let after = measure(capture_monitor_region)?;
+
+        println!(
+            "{:<10} {:>24} {:>12} {:>10.2}ms {:>10.2}ms {:>9.1}x",
+            name,
+            format!(
+                "{}: {}x{} @ {:.0}%",
+                monitor.index + 1,
+                monitor.width,
+                monitor.height,
+                monitor.scale * 100.0
+            ),
+            format!("{}x{}", region.width, region.height),
+            before,
+            after,
+            before / after.max(f64::EPSILON)
+        );
+    }
+
+    println!();
+    Ok(())
 }
 
 // ============================================================================
 // CAPTURA EM MEMÓRIA (SEM SALVAR EM DISCO) - MAIS RÁPIDO!
 // ============================================================================
 
-/// Captura a tela inteira e retorna a imagem em memória (não salva em disco)
+/// Captura a tela inteira de um monitor e retorna a imagem em memória
+/// (não salva em disco)
+///
+/// # Argumentos
+/// * `monitor` - Índice do monitor (None = principal)
 ///
 /// # Retorna
 /// * `Result<DynamicImage>` - Imagem capturada em memória
-pub fn capture_screen_to_memory() -> Result<DynamicImage> {
+pub fn capture_screen_to_memory(monitor: Option<u32>) -> Result<DynamicImage> {
     info!("📸 Capturando tela inteira (memória)...");
 
-    let screens = Screen::all().context("Falha ao listar monitores")?;
-    let screen = screens.get(0).context("Nenhum monitor encontrado")?;
-
-    info!(
-        "   Monitor: {}x{}",
-        screen.display_info.width, screen.display_info.height
-    );
-
-    let buffer = screen.capture().context("Falha ao capturar tela")?;
-    let img = buffer_to_image(&buffer);
+    let img = capture_monitor(&self::monitor(monitor)?)?;
 
     info!("✅ Screenshot capturada em memória!");
 
@@ -154,286 +399,753 @@ pub fn capture_screen_to_memory() -> Result<DynamicImage> {
 /// Captura uma região específica e retorna a imagem em memória (não salva em disco)
 ///
 /// # Argumentos
-/// * `x` - Posição X do canto superior esquerdo
-/// * `y` - Posição Y do canto superior esquerdo
+/// * `monitor` - Índice do monitor da região (None = principal)
+/// * `x` - Posição X do canto superior esquerdo (relativa ao monitor)
+/// * `y` - Posição Y do canto superior esquerdo (relativa ao monitor)
 /// * `width` - Largura da região
 /// * `height` - Altura da região
 ///
 /// # Retorna
 /// * `Result<DynamicImage>` - Imagem capturada em memória
-pub fn capture_region_to_memory(x: u32, y: u32, width: u32, height: u32) -> Result<DynamicImage> {
+pub fn capture_region_to_memory(
+    monitor: Option<u32>,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<DynamicImage> {
     info!("📸 Capturando região (memória)...");
     info!("   Posição: ({}, {})", x, y);
     info!("   Tamanho: {}x{}", width, height);
 
-    let screens = Screen::all().context("Falha ao listar monitores")?;
-    let screen = screens.get(0).context("Nenhum monitor encontrado")?;
-
-    let buffer = screen.capture().context("Falha ao capturar tela")?;
-    let full_img = buffer_to_image(&buffer);
-
-    // Valida se a região está dentro da tela
-    let screen_width = full_img.width();
-    let screen_height = full_img.height();
-
-    if x + width > screen_width || y + height > screen_height {
-        anyhow::bail!(
-            "Região ({},{} {}x{}) está fora dos limites da tela ({}x{})",
-            x,
-            y,
-            width,
-            height,
-            screen_width,
-            screen_height
-        );
-    }
-
-    // Recorta a região
-    let cropped = full_img.crop_imm(x, y, width, height);
+    let cropped = capture_monitor_region(&self::monitor(monitor)?, x, y, width, height)?;
 
     info!("✅ Screenshot da região capturada em memória!");
 
     Ok(cropped)
 }
 
+/// Tamanho do monitor principal em pixels físicos (largura, altura)
+pub fn primary_screen_size() -> (f32, f32) {
+    monitor(None)
+        .map(|m| (m.width as f32, m.height as f32))
+        .unwrap_or((1920.0, 1080.0))
+}
+
 // ============================================================================
 // FUNÇÃO AUXILIAR - Converte buffer para imagem
 // ============================================================================
 
 /// Converte o buffer da screenshot para DynamicImage
 /// (função auxiliar interna)
-fn buffer_to_image(buffer: &screenshots::Image) -> DynamicImage {
+fn buffer_to_image(buffer: screenshots::Image) -> DynamicImage {
     let width = buffer.width();
     let height = buffer.height();
-    let rgba = buffer.rgba();
+
+    // Pega o Vec de bytes RGBA do buffer sem copiar
+    let rgba: Vec<u8> = buffer.into();
 
     // Cria um ImageBuffer a partir dos bytes RGBA
     let img_buffer: ImageBuffer<Rgba<u8>, Vec<u8>> =
-        ImageBuffer::from_raw(width as u32, height as u32, rgba.to_vec())
-            .expect("Falha ao criar ImageBuffer");
+        ImageBuffer::from_raw(width, height, rgba).expect("Falha ao criar ImageBuffer");
 
     DynamicImage::ImageRgba8(img_buffer)
 }
 
+// ============================================================================
+// DETECÇÃO DE MUDANÇA - Pula o OCR quando a região não mudou
+// ============================================================================
+//
+// No modo legenda a mesma fala fica parada na tela por vários segundos.
+// Em vez de rodar pré-processamento + OCR a cada captura, a captura é
+// reduzida a uma miniatura em tons de cinza (média de cada bloco) e
+// comparada com a da última captura que passou pelo OCR. A comparação
+// usa o bloco que MAIS mudou: uma palavra trocada numa linha longa muda
+// poucos blocos, mas muda bastante; ruído de compressão muda todos, mas
+// pouco.
+//
+// ============================================================================
+
+/// Largura da miniatura (a altura segue a proporção da região)
+const SIGNATURE_COLUMNS: u32 = 64;
+
+/// Altura máxima da miniatura
+const SIGNATURE_MAX_ROWS: u32 = 64;
+
+/// Miniatura de uma captura, para saber se ela mudou sem rodar o OCR
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameSignature {
+    /// Tamanho da captura original
+    width: u32,
+    height: u32,
+    /// Média de cinza de cada bloco, linha após linha
+    cells: Vec<u8>,
+}
+
+impl FrameSignature {
+    /// Calcula a miniatura de uma captura
+    pub fn of(image: &DynamicImage) -> Self {
+        let gray = image.to_luma8();
+        let (width, height) = gray.dimensions();
+        if width == 0 || height == 0 {
+            return FrameSignature {
+                width,
+                height,
+                cells: Vec::new(),
+            };
+        }
+
+        let columns = SIGNATURE_COLUMNS.min(width);
+        let rows =
+            ((height * columns + width / 2) / width).clamp(1, SIGNATURE_MAX_ROWS.min(height));
+
+        let mut sums = vec![0u32; (columns * rows) as usize];
+        let mut counts = vec![0u32; sums.len()];
+        for (y, row) in gray.rows().enumerate() {
+            let cell_row = (y as u32 * rows / height) * columns;
+            for (x, pixel) in row.enumerate() {
+                let cell = (cell_row + x as u32 * columns / width) as usize;
+                sums[cell] += pixel[0] as u32;
+                counts[cell] += 1;
+            }
+        }
+
+        FrameSignature {
+            width,
+            height,
+            cells: sums
+                .iter()
+                .zip(&counts)
+                .map(|(&sum, &count)| (sum / count.max(1)) as u8)
+                .collect(),
+        }
+    }
+
+    /// Maior diferença entre blocos (None = capturas de tamanhos diferentes)
+    pub fn difference(&self, other: &FrameSignature) -> Option<u8> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+
+        Some(
+            self.cells
+                .iter()
+                .zip(&other.cells)
+                .map(|(a, b)| a.abs_diff(*b))
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    /// A captura mudou em relação a esta? (tamanho diferente = mudou)
+    pub fn changed(&self, other: &FrameSignature, threshold: u8) -> bool {
+        match self.difference(other) {
+            Some(difference) => difference >= threshold,
+            None => true,
+        }
+    }
+}
+
+// ============================================================================
+// PRÉ-PROCESSAMENTO - Pipeline de filtros para melhorar o OCR
+// ============================================================================
+//
+// Os filtros vêm do config como uma lista ordenada (PreprocessConfig::pipeline).
+// Cada filtro é uma função independente; a ordem e as repetições são
+// decididas pelo config, não pelo código.
+
 /// Pré-processa uma imagem para melhorar o OCR
-/// - Converte para escala de cinza
-/// - Aumenta contraste
-/// - Aplica threshold (binarização)
-/// - Inverte cores (texto branco -> texto preto)
+///
+/// # Argumentos
+/// * `image` - Imagem original
+/// * `config` - Pré-processamento (pipeline de filtros + debug)
+/// * `allow_upscale` - false = ignora os filtros de upscale (tela cheia:
+///   a imagem já é grande e o upscale deixaria tudo muito lento)
 pub fn preprocess_image(
-    image: &image::DynamicImage,
-    grayscale: bool,
-    invert: bool,
-    contrast: f32,
-    threshold: u8,
-    save_debug: bool,
-    upscale: f32,
-    blur: f32,
-    dilate: u8,
-    erode: u8,
-    edge_detection: u8,
-) -> image::DynamicImage {
+    image: &DynamicImage,
+    config: &PreprocessConfig,
+    allow_upscale: bool,
+) -> DynamicImage {
+    run_pipeline(image, config, allow_upscale, None)
+}
+
+/// Uma etapa do pré-processamento guardada para debug
+pub struct DebugStage {
+    /// "raw" para a captura, senão o nome do filtro
+    pub name: &'static str,
+    pub image: DynamicImage,
+}
+
+/// Igual a `preprocess_image`, mas devolve também a imagem depois de
+/// cada filtro (a primeira é a captura sem filtro, "raw")
+pub fn preprocess_image_stages(
+    image: &DynamicImage,
+    config: &PreprocessConfig,
+    allow_upscale: bool,
+) -> (DynamicImage, Vec<DebugStage>) {
+    let mut stages = vec![DebugStage {
+        name: "raw",
+        image: image.clone(),
+    }];
+    let processed = run_pipeline(image, config, allow_upscale, Some(&mut stages));
+    (processed, stages)
+}
+
+/// Aplica o pipeline do config, guardando cada etapa em `stages` se pedido
+fn run_pipeline(
+    image: &DynamicImage,
+    config: &PreprocessConfig,
+    allow_upscale: bool,
+    mut stages: Option<&mut Vec<DebugStage>>,
+) -> DynamicImage {
     let mut processed = image.clone();
 
-    // 0. Upscale — redimensiona a imagem ANTES de qualquer processamento
-    // O Windows OCR funciona MUITO melhor com texto grande (>30px).
-    // Se o texto no jogo é pequeno, upscale 2x ou 3x melhora bastante.
-    // Fazemos isso PRIMEIRO porque os outros filtros (threshold, contraste)
-    // funcionam melhor em imagens maiores com mais detalhe nos pixels.
-    if upscale > 1.0 {
-        let (w, h) = (processed.width(), processed.height());
-        let new_w = (w as f32 * upscale) as u32;
-        let new_h = (h as f32 * upscale) as u32;
-
-        // image::imageops::FilterType::Lanczos3 é o melhor filtro para upscale
-        // Ele preserva bordas nítidas (perfeito para texto)
-        // Outros filtros disponíveis:
-        //   Nearest  = mais rápido, mas pixelado (ruim para OCR)
-        //   Triangle = ok, mas borra um pouco
-        //   Lanczos3 = mais lento, mas bordas nítidas (melhor para texto!)
-        processed = processed.resize_exact(new_w, new_h, image::imageops::FilterType::Lanczos3);
-
-        info!(
-            "   🔍 Upscale: {}x{} → {}x{} (fator {:.1}x)",
-            w, h, new_w, new_h, upscale
-        );
+    for step in config.pipeline() {
+        if !allow_upscale && matches!(step, PreprocessStep::Upscale { .. }) {
+            continue;
+        }
+        processed = apply_step(processed, &step);
+
+        if let Some(stages) = stages.as_deref_mut() {
+            stages.push(DebugStage {
+                name: step.name(),
+                image: processed.clone(),
+            });
+        }
+    }
+
+    // Salva imagem de debug se solicitado
+    if config.save_debug_image {
+        if let Err(e) = processed.save("debug_preprocessed.png") {
+            error!("❌ Erro ao salvar imagem de debug: {}", e);
+        } else {
+            trace!("📸 Imagem de debug salva: debug_preprocessed.png");
+        }
+    }
+
+    processed
+}
+
+/// Aplica um filtro do pipeline
+fn apply_step(image: DynamicImage, step: &PreprocessStep) -> DynamicImage {
+    match *step {
+        PreprocessStep::Upscale { factor } => upscale(image, factor),
+        PreprocessStep::Blur { sigma } => {
+            if sigma <= 0.0 {
+                return image;
+            }
+            info!("   🌫️ Blur aplicado: sigma={:.1}", sigma);
+            image.blur(sigma)
+        }
+        PreprocessStep::Grayscale => {
+            // Converte de volta para RGB para manter compatibilidade
+            DynamicImage::ImageRgb8(DynamicImage::ImageLuma8(image.to_luma8()).to_rgb8())
+        }
+        PreprocessStep::Contrast { amount } => {
+            if amount == 1.0 {
+                return image;
+            }
+            image.adjust_contrast(amount)
+        }
+        PreprocessStep::ColorKey {
+            ref colors,
+            tolerance,
+        } => color_key(&image, colors, tolerance),
+        PreprocessStep::Threshold { level } => threshold(&image, level),
+        PreprocessStep::Otsu => otsu(&image),
+        PreprocessStep::Sauvola { window, k } => sauvola(&image, window, k),
+        PreprocessStep::LocalMean { window, offset } => local_mean(&image, window, offset),
+        PreprocessStep::EdgeDetection { threshold } => edge_detection(&image, threshold),
+        PreprocessStep::Erode { radius } => erode(&image, radius),
+        PreprocessStep::Dilate { radius } => dilate(&image, radius),
+        PreprocessStep::Invert => {
+            let mut inverted = image;
+            inverted.invert();
+            inverted
+        }
     }
+}
 
-    // 0.5. Blur gaussiano — suaviza sombras e artefatos visuais
-    // Aplicado ANTES do grayscale/threshold para que a suavização
-    // elimine bordas duras de sombras e efeitos do jogo.
-    // O threshold depois "limpa" o resultado borrado.
-    if blur > 0.0 {
-        let sigma = blur; // sigma controla a intensidade do blur
-        processed = processed.blur(sigma);
-        info!("   🌫️ Blur aplicado: sigma={:.1}", sigma);
+/// Upscale — redimensiona a imagem
+///
+/// O Windows OCR funciona MUITO melhor com texto grande (>30px).
+/// Se o texto no jogo é pequeno, upscale 2x ou 3x melhora bastante.
+/// Os outros filtros (threshold, contraste) também funcionam melhor em
+/// imagens maiores, por isso o upscale costuma ser o primeiro passo.
+fn upscale(image: DynamicImage, factor: f32) -> DynamicImage {
+    if factor <= 0.0 || factor == 1.0 {
+        return image;
     }
 
-    // 1. Converte para escala de cinza
-    if grayscale {
-        processed = image::DynamicImage::ImageLuma8(processed.to_luma8());
-        // Converte de volta para RGB para manter compatibilidade
-        processed = image::DynamicImage::ImageRgb8(processed.to_rgb8());
+    let (w, h) = (image.width(), image.height());
+    let new_w = ((w as f32 * factor) as u32).max(1);
+    let new_h = ((h as f32 * factor) as u32).max(1);
+
+    info!(
+        "   🔍 Upscale: {}x{} → {}x{} (fator {:.1}x)",
+        w, h, new_w, new_h, factor
+    );
+
+    // image::imageops::FilterType::Lanczos3 é o melhor filtro para upscale
+    // Ele preserva bordas nítidas (perfeito para texto)
+    // Outros filtros disponíveis:
+    //   Nearest  = mais rápido, mas pixelado (ruim para OCR)
+    //   Triangle = ok, mas borra um pouco
+    //   Lanczos3 = mais lento, mas bordas nítidas (melhor para texto!)
+    image.resize_exact(new_w, new_h, image::imageops::FilterType::Lanczos3)
+}
+
+/// Threshold (binarização): luminância acima do nível = branco
+fn threshold(image: &DynamicImage, level: u8) -> DynamicImage {
+    if level == 0 {
+        return image.clone();
     }
 
-    // 2. Aumenta contraste
-    if contrast != 1.0 {
-        processed = processed.adjust_contrast(contrast);
+    let rgb = image.to_rgb8();
+    let (width, height) = rgb.dimensions();
+
+    let mut binary = image::RgbImage::new(width, height);
+
+    for (x, y, pixel) in rgb.enumerate_pixels() {
+        // Calcula luminância do pixel
+        let luma =
+            (0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32) as u8;
+
+        // Aplica threshold: acima = branco, abaixo = preto
+        let value = if luma > level { 255 } else { 0 };
+        binary.put_pixel(x, y, image::Rgb([value, value, value]));
     }
 
-    // 2.5. Edge Detection (detecção de bordas) — alternativa ao threshold
-    // Usa filtro Sobel para encontrar transições claro↔escuro.
-    // Perfeito para texto com outline: o contorno escuro ao redor das
-    // letras brancas cria gradientes fortes que o Sobel detecta.
-    // Se ativado, SUBSTITUI o threshold normal.
-    if edge_detection > 0 {
-        let gray = processed.to_luma8();
-        let (width, height) = gray.dimensions();
-        let mut edges = image::GrayImage::new(width, height);
-
-        // Filtro Sobel: calcula gradiente horizontal (Gx) e vertical (Gy)
-        // para cada pixel. Pixels com gradiente alto = borda/contorno.
-        for y in 1..(height - 1) {
-            for x in 1..(width - 1) {
-                // Pega os 9 pixels ao redor (janela 3x3)
-                let p = |dx: i32, dy: i32| -> f32 {
-                    gray.get_pixel((x as i32 + dx) as u32, (y as i32 + dy) as u32)[0] as f32
-                };
-
-                // Kernel Sobel horizontal (detecta bordas verticais)
-                let gx =
-                    -p(-1, -1) - 2.0 * p(-1, 0) - p(-1, 1) + p(1, -1) + 2.0 * p(1, 0) + p(1, 1);
-
-                // Kernel Sobel vertical (detecta bordas horizontais)
-                let gy =
-                    -p(-1, -1) - 2.0 * p(0, -1) - p(1, -1) + p(-1, 1) + 2.0 * p(0, 1) + p(1, 1);
-
-                // Magnitude do gradiente (quanto maior, mais forte a borda)
-                let magnitude = (gx * gx + gy * gy).sqrt().min(255.0) as u8;
-
-                // Aplica threshold no gradiente: acima = borda (branco)
-                let value = if magnitude > edge_detection { 255 } else { 0 };
-                edges.put_pixel(x, y, image::Luma([value]));
-            }
+    DynamicImage::ImageRgb8(binary)
+}
+
+/// Color key — isola o texto pela cor
+///
+/// Muitos jogos desenham a legenda numa cor fixa (branco, amarelo do
+/// narrador...). Pixels a até `tolerance` (distância RGB) de alguma das
+/// cores viram branco, o resto preto — mesma convenção do threshold
+/// (texto claro = branco), então o `invert` padrão deixa texto preto.
+/// Em cenas cheias de detalhe isso separa melhor que a luminância.
+fn color_key(image: &DynamicImage, colors: &[[u8; 3]], tolerance: f32) -> DynamicImage {
+    if colors.is_empty() {
+        return image.clone();
+    }
+
+    let rgb = image.to_rgb8();
+    let (width, height) = rgb.dimensions();
+    let mut keyed = image::RgbImage::new(width, height);
+    let max_distance = tolerance.max(0.0) * tolerance.max(0.0);
+    let mut matched = 0usize;
+
+    for (x, y, pixel) in rgb.enumerate_pixels() {
+        let is_key = colors.iter().any(|color| {
+            let distance: f32 = (0..3)
+                .map(|c| (pixel[c] as f32 - color[c] as f32).powi(2))
+                .sum();
+            distance <= max_distance
+        });
+
+        if is_key {
+            matched += 1;
+            keyed.put_pixel(x, y, image::Rgb([255, 255, 255]));
         }
+    }
 
-        processed =
-            image::DynamicImage::ImageRgb8(image::DynamicImage::ImageLuma8(edges).to_rgb8());
+    info!(
+        "   🎨 Color key: {} cor(es), tolerância={:.0}, {:.1}% dos pixels",
+        colors.len(),
+        tolerance,
+        matched as f64 * 100.0 / (width as f64 * height as f64).max(1.0)
+    );
 
-        info!(
-            "   🔎 Edge detection aplicado: threshold={}",
-            edge_detection
-        );
+    DynamicImage::ImageRgb8(keyed)
+}
 
-        // Pula o threshold normal (edge detection já binarizou)
-        // A dilatação depois vai "preencher" o interior dos contornos
+/// Otsu — threshold global com nível automático
+///
+/// Escolhe o nível que melhor separa o histograma em duas classes
+/// (texto e fundo). Bom quando o fundo é uniforme mas muda de cena
+/// para cena (o nível fixo teria que ser reajustado).
+fn otsu(image: &DynamicImage) -> DynamicImage {
+    let gray = image.to_luma8();
+    let level = otsu_level(&gray);
+
+    info!("   ⚖️ Otsu: nível automático={}", level);
+
+    binarize(&gray, |_, _, luma| luma > level as f32)
+}
+
+/// Nível de Otsu: maximiza a variância entre as duas classes
+fn otsu_level(gray: &image::GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel[0] as usize] += 1;
     }
 
-    // 3. Aplica threshold (binarização) se > 0
-    // Pula se edge_detection está ativo (já fez a binarização)
-    if threshold > 0 && edge_detection == 0 {
-        let rgb = processed.to_rgb8();
-        let (width, height) = rgb.dimensions();
+    let total = (gray.width() as u64 * gray.height() as u64) as f64;
+    let sum_all: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+
+    let mut best_level = 0u8;
+    let mut best_variance = 0.0;
+    let mut weight_background = 0.0;
+    let mut sum_background = 0.0;
+
+    for (level, &count) in histogram.iter().enumerate() {
+        weight_background += count as f64;
+        if weight_background == 0.0 {
+            continue;
+        }
 
-        let mut binary = image::RgbImage::new(width, height);
+        let weight_foreground = total - weight_background;
+        if weight_foreground == 0.0 {
+            break;
+        }
 
-        for (x, y, pixel) in rgb.enumerate_pixels() {
-            // Calcula luminância do pixel
-            let luma =
-                (0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32) as u8;
+        sum_background += level as f64 * count as f64;
+        let mean_background = sum_background / weight_background;
+        let mean_foreground = (sum_all - sum_background) / weight_foreground;
 
-            // Aplica threshold: acima = branco, abaixo = preto
-            let value = if luma > threshold { 255 } else { 0 };
-            binary.put_pixel(x, y, image::Rgb([value, value, value]));
+        let variance =
+            weight_background * weight_foreground * (mean_background - mean_foreground).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best_level = level as u8;
         }
+    }
 
-        processed = image::DynamicImage::ImageRgb8(binary);
+    best_level
+}
+
+/// Sauvola — threshold adaptativo por janela
+///
+/// Cada pixel é comparado com um nível calculado na sua vizinhança:
+/// `média × (1 + k × (desvio / 128 - 1))`. Em regiões lisas (desvio
+/// baixo) o nível fica abaixo da média, e o fundo não vira ruído;
+/// em regiões com texto (desvio alto) fica perto da média.
+fn sauvola(image: &DynamicImage, window: u32, k: f32) -> DynamicImage {
+    let gray = image.to_luma8();
+    let integral = IntegralImage::new(&gray);
+    let radius = window.max(3) / 2;
+
+    info!("   🪟 Sauvola: janela={} k={:.2}", window, k);
+
+    binarize(&gray, |x, y, luma| {
+        let (mean, deviation) = integral.window_stats(x, y, radius);
+        let level = mean * (1.0 + k as f64 * (deviation / 128.0 - 1.0));
+        luma as f64 > level
+    })
+}
+
+/// Média local — threshold adaptativo pela média da janela
+///
+/// Pixel mais claro que a média da vizinhança (menos a margem) = branco.
+/// Simples e rápido; funciona bem com gradientes suaves no fundo.
+fn local_mean(image: &DynamicImage, window: u32, offset: f32) -> DynamicImage {
+    let gray = image.to_luma8();
+    let integral = IntegralImage::new(&gray);
+    let radius = window.max(3) / 2;
+
+    info!("   🪟 Média local: janela={} margem={:.1}", window, offset);
+
+    binarize(&gray, |x, y, luma| {
+        let (mean, _) = integral.window_stats(x, y, radius);
+        luma as f64 > mean - offset as f64
+    })
+}
+
+/// Monta a imagem binária: `is_white(x, y, luma)` decide cada pixel
+fn binarize(gray: &image::GrayImage, is_white: impl Fn(u32, u32, f32) -> bool) -> DynamicImage {
+    let (width, height) = gray.dimensions();
+    let mut binary = image::RgbImage::new(width, height);
+
+    for (x, y, pixel) in gray.enumerate_pixels() {
+        let value = if is_white(x, y, pixel[0] as f32) {
+            255
+        } else {
+            0
+        };
+        binary.put_pixel(x, y, image::Rgb([value, value, value]));
     }
 
-    // 3.5. Erosão — remove pixels das bordas dos caracteres
-    // Aplicada ANTES da dilatação para fazer "opening" (erosão + dilatação)
-    // que remove ruído pequeno sem afetar o texto principal.
-    // Funciona como um filtro de mínimo: cada pixel vira o valor mínimo
-    // dos seus vizinhos dentro do raio.
-    if erode > 0 {
-        let rgb = processed.to_rgb8();
-        let (width, height) = rgb.dimensions();
-        let mut eroded = rgb.clone();
-        let radius = erode as i32;
+    DynamicImage::ImageRgb8(binary)
+}
 
-        for y in 0..height {
-            for x in 0..width {
-                let mut min_val: u8 = 255;
-
-                // Percorre vizinhos dentro do raio
-                for dy in -radius..=radius {
-                    for dx in -radius..=radius {
-                        let nx = x as i32 + dx;
-                        let ny = y as i32 + dy;
-
-                        if nx >= 0 && nx < width as i32 && ny >= 0 && ny < height as i32 {
-                            let pixel = rgb.get_pixel(nx as u32, ny as u32);
-                            min_val = min_val.min(pixel[0]);
-                        }
-                    }
-                }
-
-                eroded.put_pixel(x, y, image::Rgb([min_val, min_val, min_val]));
+/// Imagem integral (somas acumuladas) para média/desvio de qualquer
+/// janela em tempo constante
+struct IntegralImage {
+    width: u32,
+    height: u32,
+    /// Soma dos valores, (largura + 1) × (altura + 1)
+    sum: Vec<u64>,
+    /// Soma dos quadrados, (largura + 1) × (altura + 1)
+    sum_squared: Vec<u64>,
+}
+
+impl IntegralImage {
+    fn new(gray: &image::GrayImage) -> Self {
+        let (width, height) = gray.dimensions();
+        let stride = width as usize + 1;
+        let mut sum = vec![0u64; stride * (height as usize + 1)];
+        let mut sum_squared = vec![0u64; stride * (height as usize + 1)];
+
+        for y in 0..height as usize {
+            let mut row_sum = 0u64;
+            let mut row_sum_squared = 0u64;
+
+            for x in 0..width as usize {
+                let value = gray.get_pixel(x as u32, y as u32)[0] as u64;
+                row_sum += value;
+                row_sum_squared += value * value;
+
+                let index = (y + 1) * stride + x + 1;
+                sum[index] = sum[index - stride] + row_sum;
+                sum_squared[index] = sum_squared[index - stride] + row_sum_squared;
             }
         }
 
-        processed = image::DynamicImage::ImageRgb8(eroded);
-        info!("   🔽 Erosão aplicada: raio={}", erode);
+        IntegralImage {
+            width,
+            height,
+            sum,
+            sum_squared,
+        }
+    }
+
+    /// Média e desvio padrão da janela centrada em (x, y)
+    /// (a janela é cortada nas bordas da imagem)
+    fn window_stats(&self, x: u32, y: u32, radius: u32) -> (f64, f64) {
+        let stride = self.width as usize + 1;
+        let left = x.saturating_sub(radius) as usize;
+        let top = y.saturating_sub(radius) as usize;
+        let right = (x + radius + 1).min(self.width) as usize;
+        let bottom = (y + radius + 1).min(self.height) as usize;
+
+        let area = |table: &[u64]| {
+            table[bottom * stride + right] + table[top * stride + left]
+                - table[top * stride + right]
+                - table[bottom * stride + left]
+        };
+
+        let count = ((right - left) * (bottom - top)) as f64;
+        let mean = area(&self.sum) as f64 / count;
+        let variance = (area(&self.sum_squared) as f64 / count - mean * mean).max(0.0);
+
+        (mean, variance.sqrt())
+    }
+}
+
+/// Edge Detection (detecção de bordas) — alternativa ao threshold
+///
+/// Usa filtro Sobel para encontrar transições claro↔escuro.
+/// Perfeito para texto com outline: o contorno escuro ao redor das
+/// letras brancas cria gradientes fortes que o Sobel detecta.
+/// Uma dilatação depois "preenche" o interior dos contornos.
+fn edge_detection(image: &DynamicImage, threshold: u8) -> DynamicImage {
+    // Filtro Sobel: calcula gradiente horizontal (Gx) e vertical (Gy)
+    // para cada pixel. Pixels com gradiente alto = borda/contorno.
+    let gray = image.to_luma8();
+    let (width, height) = gray.dimensions();
+    let edges = filters::sobel_threshold(gray.as_raw(), width as usize, height as usize, threshold);
+
+    info!("   🔎 Edge detection aplicado: threshold={}", threshold);
+
+    gray_to_rgb(width, height, &edges)
+}
+
+/// Erosão — remove pixels das bordas dos caracteres
+///
+/// Funciona como um filtro de mínimo: cada pixel vira o valor mínimo
+/// dos seus vizinhos dentro do raio. Erosão seguida de dilatação
+/// ("opening") remove ruído pequeno sem afetar o texto principal.
+fn erode(image: &DynamicImage, radius: u8) -> DynamicImage {
+    if radius == 0 {
+        return image.clone();
     }
 
-    // 3.6. Dilatação — expande pixels dos caracteres (engorda letras)
-    // Funciona como um filtro de máximo: cada pixel vira o valor máximo
-    // dos seus vizinhos dentro do raio. Isso "fecha" buracos e engorda
-    // letras finas que o threshold pode ter afinado demais.
-    if dilate > 0 {
-        let rgb = processed.to_rgb8();
-        let (width, height) = rgb.dimensions();
-        let mut dilated = rgb.clone();
-        let radius = dilate as i32;
+    info!("   🔽 Erosão aplicada: raio={}", radius);
+    morphology(image, radius, filters::Morphology::Erode)
+}
+
+/// Dilatação — expande pixels dos caracteres (engorda letras)
+///
+/// Funciona como um filtro de máximo: cada pixel vira o valor máximo
+/// dos seus vizinhos dentro do raio. Isso "fecha" buracos e engorda
+/// letras finas que o threshold pode ter afinado demais.
+fn dilate(image: &DynamicImage, radius: u8) -> DynamicImage {
+    if radius == 0 {
+        return image.clone();
+    }
+
+    info!("   🔼 Dilatação aplicada: raio={}", radius);
+    morphology(image, radius, filters::Morphology::Dilate)
+}
+
+/// Filtro de vizinhança quadrada (mínimo ou máximo) sobre o canal vermelho
+fn morphology(image: &DynamicImage, radius: u8, op: filters::Morphology) -> DynamicImage {
+    let rgb = image.to_rgb8();
+    let (width, height) = rgb.dimensions();
+    let red: Vec<u8> = rgb.as_raw().iter().step_by(3).copied().collect();
+
+    let result = filters::morphology(&red, width as usize, height as usize, radius as usize, op);
+
+    gray_to_rgb(width, height, &result)
+}
+
+/// Monta uma imagem RGB cinza a partir de um byte por pixel
+fn gray_to_rgb(width: u32, height: u32, pixels: &[u8]) -> DynamicImage {
+    let rgb: Vec<u8> = pixels.iter().flat_map(|&v| [v, v, v]).collect();
+    DynamicImage::ImageRgb8(
+        image::RgbImage::from_raw(width, height, rgb).expect("buffer com tamanho da imagem"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fundo em degradê horizontal (escuro → claro) com "letras" em
+    /// faixas verticais 60 níveis mais escuras que o fundo ao redor
+    fn text_over_gradient() -> (image::GrayImage, Vec<bool>) {
+        let (width, height) = (200, 40);
+        let mut gray = image::GrayImage::new(width, height);
+        let mut is_text = Vec::new();
 
         for y in 0..height {
             for x in 0..width {
-                let mut max_val: u8 = 0;
-
-                // Percorre vizinhos dentro do raio
-                for dy in -radius..=radius {
-                    for dx in -radius..=radius {
-                        let nx = x as i32 + dx;
-                        let ny = y as i32 + dy;
-
-                        if nx >= 0 && nx < width as i32 && ny >= 0 && ny < height as i32 {
-                            let pixel = rgb.get_pixel(nx as u32, ny as u32);
-                            max_val = max_val.max(pixel[0]);
-                        }
-                    }
-                }
-
-                dilated.put_pixel(x, y, image::Rgb([max_val, max_val, max_val]));
+                let background = 70 + (x * 170 / width) as u8;
+                let text = (10..30).contains(&y) && x % 20 < 4;
+                let value = if text { background - 60 } else { background };
+                gray.put_pixel(x, y, image::Luma([value]));
+                is_text.push(text);
             }
         }
 
-        processed = image::DynamicImage::ImageRgb8(dilated);
-        info!("   🔼 Dilatação aplicada: raio={}", dilate);
+        (gray, is_text)
     }
 
-    // 4. Inverte cores
-    if invert {
-        processed.invert();
+    /// Fração de pixels classificados certo (texto = preto, fundo = branco)
+    fn accuracy(result: &DynamicImage, is_text: &[bool]) -> f64 {
+        let binary = result.to_luma8();
+        let correct = binary
+            .pixels()
+            .zip(is_text)
+            .filter(|(pixel, &text)| (pixel[0] == 0) == text)
+            .count();
+        correct as f64 / is_text.len() as f64
     }
 
-    // 5. Salva imagem de debug se solicitado
-    if save_debug {
-        if let Err(e) = processed.save("debug_preprocessed.png") {
-            error!("❌ Erro ao salvar imagem de debug: {}", e);
-        } else {
-            trace!("📸 Imagem de debug salva: debug_preprocessed.png");
+    #[test]
+    fn otsu_splits_bimodal_image() {
+        let mut gray = image::GrayImage::new(100, 10);
+        for (x, _, pixel) in gray.enumerate_pixels_mut() {
+            *pixel = image::Luma([if x < 30 { 40 } else { 200 }]);
         }
+
+        let level = otsu_level(&gray);
+        assert!((40..200).contains(&level), "nível {}", level);
+
+        let result = otsu(&DynamicImage::ImageLuma8(gray)).to_luma8();
+        assert_eq!(result.get_pixel(10, 5)[0], 0);
+        assert_eq!(result.get_pixel(80, 5)[0], 255);
     }
 
-    processed
+    #[test]
+    fn adaptive_methods_handle_gradient_background() {
+        let (gray, is_text) = text_over_gradient();
+        let image = DynamicImage::ImageLuma8(gray);
+
+        // Nenhum nível global separa texto e fundo num degradê desses
+        let best_global = (0..=255u8)
+            .map(|level| accuracy(&threshold(&image, level), &is_text))
+            .fold(0.0, f64::max);
+
+        let sauvola = accuracy(&sauvola(&image, 25, 0.2), &is_text);
+        let local_mean = accuracy(&local_mean(&image, 25, 10.0), &is_text);
+
+        assert!(sauvola > 0.97, "sauvola {:.3}", sauvola);
+        assert!(local_mean > 0.97, "média local {:.3}", local_mean);
+        assert!(best_global < sauvola && best_global < local_mean);
+    }
+
+    #[test]
+    fn color_key_keeps_only_key_colors() {
+        let mut rgb = image::RgbImage::new(3, 1);
+        rgb.put_pixel(0, 0, image::Rgb([250, 220, 60])); // amarelo do texto
+        rgb.put_pixel(1, 0, image::Rgb([240, 240, 240])); // fundo claro
+        rgb.put_pixel(2, 0, image::Rgb([30, 30, 30])); // contorno
+
+        let keyed = color_key(&DynamicImage::ImageRgb8(rgb), &[[255, 215, 50]], 30.0).to_luma8();
+
+        assert_eq!(keyed.get_pixel(0, 0)[0], 255);
+        assert_eq!(keyed.get_pixel(1, 0)[0], 0);
+        assert_eq!(keyed.get_pixel(2, 0)[0], 0);
+    }
+
+    #[test]
+    fn window_stats_match_direct_computation() {
+        let (gray, _) = text_over_gradient();
+        let integral = IntegralImage::new(&gray);
+
+        let (x, y, radius) = (57u32, 12u32, 4u32);
+        let values: Vec<f64> = (y - radius..=y + radius)
+            .flat_map(|wy| (x - radius..=x + radius).map(move |wx| (wx, wy)))
+            .map(|(wx, wy)| gray.get_pixel(wx, wy)[0] as f64)
+            .collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let deviation =
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+
+        let (integral_mean, integral_deviation) = integral.window_stats(x, y, radius);
+        assert!((integral_mean - mean).abs() < 1e-9);
+        assert!((integral_deviation - deviation).abs() < 1e-6);
+    }
+
+    #[test]
+    fn legacy_fields_map_to_threshold_mode() {
+        let config = PreprocessConfig {
+            enabled: true,
+            threshold: 128,
+            threshold_mode: crate::config::ThresholdMode::Sauvola,
+            ..PreprocessConfig::default()
+        };
+
+        assert!(config.pipeline().contains(&PreprocessStep::Sauvola {
+            window: config.adaptive_window,
+            k: config.sauvola_k,
+        }));
+        assert!(!config
+            .pipeline()
+            .iter()
+            .any(|step| matches!(step, PreprocessStep::Threshold { .. })));
+    }
+
+    #[test]
+    fn frame_signature_ignores_noise_but_sees_new_text() {
+        // Faixa de legenda 1200x100: fundo escuro com "letras" claras
+        let subtitle = |first_letter: u32, noise: u8| {
+            DynamicImage::ImageLuma8(image::GrayImage::from_fn(1200, 100, |x, y| {
+                let letter = x >= first_letter && (x / 12) % 3 == 0 && (40..60).contains(&y);
+                let base = if letter { 230 } else { 30 };
+                image::Luma([base + ((x * 7 + y * 13) % 3) as u8 * noise])
+            }))
+        };
+
+        let frame = FrameSignature::of(&subtitle(100, 0));
+        assert_eq!(frame.difference(&frame), Some(0));
+
+        // Ruído de compressão: não conta como mudança
+        assert!(!frame.changed(&FrameSignature::of(&subtitle(100, 1)), 8));
+
+        // Fala nova (texto começa em outro lugar): mudou
+        assert!(frame.changed(&FrameSignature::of(&subtitle(400, 0)), 8));
+
+        // Região de outro tamanho: sempre mudou
+        let smaller = DynamicImage::ImageLuma8(image::GrayImage::new(600, 100));
+        assert_eq!(frame.difference(&FrameSignature::of(&smaller)), None);
+        assert!(frame.changed(&FrameSignature::of(&smaller), 8));
+    }
 }
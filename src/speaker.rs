@@ -0,0 +1,414 @@
+// game-translator/src/speaker.rs
+
+// ============================================================================
+// MÓDULO SPEAKER - Detecta o nome de quem está falando no diálogo
+// ============================================================================
+//
+// Os jogos mostram o nome do personagem de dois jeitos principais:
+//
+// 1. Prefixo na própria fala:   "ALICE: Where are you going?"
+//                               "【アリス】どこへ行くの？"
+// 2. Rótulo acima da caixa:     "Alice"
+//                               "Where are you going?"
+//
+// Sem tratamento, o nome é traduzido junto ("Alice" → "Alice" com sorte,
+// "Hope" → "Esperança" sem sorte) ou misturado na frase. Aqui o nome é
+// separado da fala, não é traduzido (ou é trocado pelo glossário) e segue
+// como um campo próprio até o overlay.
+//
+// Ter cara de nome não basta: "Objective: Find the key", "Warning: Low HP"
+// e um "Inventory" acima da lista também começam com maiúscula. Por isso
+// o nome só é separado com alguma evidência a mais:
+// - Está no glossário (qualquer formato)
+// - Está entre colchetes ("【アリス】", "[Alice]")
+// - Prefixo "NOME:" todo em maiúsculas ou CJK
+// - Rótulo todo em maiúsculas ou CJK, bem mais estreito que a fala
+//
+// ============================================================================
+
+use crate::cjk;
+use crate::config::SpeakerConfig;
+use crate::ocr::DetectedText;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Tamanho máximo de um nome (em caracteres)
+const MAX_NAME_CHARS: usize = 24;
+
+/// Número máximo de palavras em um nome ("Captain John Smith")
+const MAX_NAME_WORDS: usize = 3;
+
+/// Distância vertical máxima entre o rótulo e a fala (em alturas de linha)
+const MAX_LABEL_GAP: f64 = 1.5;
+
+/// Largura máxima do rótulo em relação à fala (nome fora do glossário)
+const MAX_LABEL_WIDTH_RATIO: f64 = 0.6;
+
+/// "NOME: fala" ou "NOME：fala"
+fn prefix_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^\s*([^:：]{1,24}?)\s*[:：]\s*(\S.*)$").unwrap())
+}
+
+/// "【NOME】fala", "[NOME] fala" ou "「NOME」fala"
+fn bracket_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^\s*[【\[「『]([^】\]」』]{1,24})[】\]」』]\s*(\S.*)$").unwrap()
+    })
+}
+
+/// Separa o nome do falante do começo do texto
+///
+/// Entre colchetes basta parecer um nome; com dois-pontos o nome precisa
+/// estar no glossário ou ter estilo de nome (maiúsculas/CJK), e a fala
+/// precisa ter letras ("TIME: 12:30" não é fala).
+///
+/// # Retorna
+/// * `(Some(nome), fala)` se o texto começa com um nome
+/// * `(None, texto)` caso contrário
+pub fn split_prefix(text: &str, config: &SpeakerConfig) -> (Option<String>, String) {
+    if let Some(captures) = bracket_regex().captures(text) {
+        let (name, line) = (captures[1].trim(), captures[2].trim());
+        if looks_like_name(name) && !line.is_empty() {
+            return (Some(name.to_string()), line.to_string());
+        }
+    }
+
+    if let Some(captures) = prefix_regex().captures(text) {
+        let (name, line) = (captures[1].trim(), captures[2].trim());
+        if looks_like_name(name)
+            && (in_glossary(name, config) || has_name_style(name))
+            && line.chars().any(|c| c.is_alphabetic())
+        {
+            return (Some(name.to_string()), line.to_string());
+        }
+    }
+
+    (None, text.trim().to_string())
+}
+
+/// Verifica se um texto curto parece um nome de personagem
+///
+/// Nomes são curtos, têm no máximo 3 palavras, começam com maiúscula
+/// (ou são CJK) e não terminam com pontuação de frase.
+pub fn looks_like_name(text: &str) -> bool {
+    let text = text.trim();
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    if text.is_empty()
+        || text.chars().count() > MAX_NAME_CHARS
+        || words.len() > MAX_NAME_WORDS
+        || text.ends_with(['.', '!', '?', ',', '。', '！', '？', '、'])
+        || !text.chars().any(|c| c.is_alphabetic())
+    {
+        return false;
+    }
+
+    if cjk::contains_cjk(text) {
+        return true;
+    }
+
+    // Cada palavra começa com maiúscula ("Old Man", "ALICE", "Dr. Kim")
+    words
+        .iter()
+        .all(|w| w.chars().next().is_some_and(|c| c.is_uppercase()))
+}
+
+/// O nome está no glossário (sem diferenciar maiúsculas)
+fn in_glossary(name: &str, config: &SpeakerConfig) -> bool {
+    config
+        .glossary
+        .keys()
+        .any(|original| original.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Estilo típico de nome em jogo: CJK ou todo em maiúsculas ("ALICE", "OLD MAN")
+fn has_name_style(name: &str) -> bool {
+    if cjk::contains_cjk(name) {
+        return true;
+    }
+
+    let letters: Vec<char> = name.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+}
+
+/// Separa os rótulos de nome das linhas cruas do OCR
+///
+/// Precisa rodar antes do agrupamento em blocos: com as tolerâncias
+/// padrão do layout, um nome logo acima da fala e alinhado com ela
+/// entraria no mesmo bloco e seria traduzido como parte do diálogo.
+///
+/// # Retorna
+/// * `(rótulos, demais linhas)`
+pub fn take_labels(
+    lines: &[DetectedText],
+    config: &SpeakerConfig,
+) -> (Vec<DetectedText>, Vec<DetectedText>) {
+    lines
+        .iter()
+        .cloned()
+        .partition(|line| is_label_line(line, lines, config))
+}
+
+/// A linha é um nome e a linha logo abaixo dela é a fala?
+fn is_label_line(line: &DetectedText, lines: &[DetectedText], config: &SpeakerConfig) -> bool {
+    if line.vertical || !looks_like_name(&line.text) {
+        return false;
+    }
+
+    lines
+        .iter()
+        .filter(|next| next.y > line.y && overlaps_horizontally(line, next))
+        .min_by(|a, b| a.y.total_cmp(&b.y))
+        .is_some_and(|next| is_label_for(line, next, config))
+}
+
+/// Associa cada bloco ao rótulo de nome logo acima dele
+///
+/// # Argumentos
+/// * `labels` - Rótulos separados por `take_labels`
+/// * `blocks` - Blocos montados com as demais linhas
+///
+/// # Retorna
+/// * Um item por bloco: `(nome do rótulo, bloco da fala)`.
+///   Rótulos sem fala embaixo voltam no fim como texto comum.
+pub fn attach_labels(
+    labels: Vec<DetectedText>,
+    blocks: Vec<DetectedText>,
+    config: &SpeakerConfig,
+) -> Vec<(Option<String>, DetectedText)> {
+    let mut labels: Vec<Option<DetectedText>> = labels.into_iter().map(Some).collect();
+    let mut result: Vec<(Option<String>, DetectedText)> = Vec::with_capacity(blocks.len());
+
+    for block in blocks {
+        // O rótulo mais próximo acima do bloco
+        let nearest = labels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, label)| {
+                let label = label.as_ref()?;
+                is_label_for(label, &block, config)
+                    .then_some((index, block.y - (label.y + label.height)))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index);
+
+        let name = nearest
+            .and_then(|index| labels[index].take())
+            .map(|label| label.text.trim().to_string());
+        result.push((name, block));
+    }
+
+    result.extend(labels.into_iter().flatten().map(|label| (None, label)));
+    result
+}
+
+/// Verifica se o rótulo está logo acima do bloco (e alinhado com ele)
+///
+/// Fora do glossário, o rótulo precisa ter estilo de nome e ser bem
+/// mais estreito que a fala (um título de menu ocupa a largura toda).
+fn is_label_for(label: &DetectedText, block: &DetectedText, config: &SpeakerConfig) -> bool {
+    let gap = block.y - (label.y + label.height);
+
+    if gap < -label.height * 0.5
+        || gap > label.height * MAX_LABEL_GAP
+        || !overlaps_horizontally(label, block)
+    {
+        return false;
+    }
+
+    in_glossary(&label.text, config)
+        || (has_name_style(&label.text) && label.width <= block.width * MAX_LABEL_WIDTH_RATIO)
+}
+
+fn overlaps_horizontally(a: &DetectedText, b: &DetectedText) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width
+}
+
+/// Verifica se a primeira linha de um resultado de OCR é um rótulo de nome
+///
+/// Usado no modo legenda, que trabalha com as linhas cruas da região.
+pub fn first_line_is_label(lines: &[DetectedText], config: &SpeakerConfig) -> bool {
+    match lines {
+        [label, next, ..] => {
+            !label.vertical && looks_like_name(&label.text) && is_label_for(label, next, config)
+        }
+        _ => false,
+    }
+}
+
+/// Nome a exibir: o do glossário, se existir, senão o original
+pub fn display_name(name: &str, config: &SpeakerConfig) -> String {
+    config
+        .glossary
+        .iter()
+        .find(|(original, _)| original.eq_ignore_ascii_case(name))
+        .map(|(_, mapped)| mapped.clone())
+        .unwrap_or_else(|| name.to_string())
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LayoutConfig;
+
+    fn block(text: &str, x: f64, y: f64, width: f64, height: f64) -> DetectedText {
+        DetectedText {
+            text: text.to_string(),
+            x,
+            y,
+            width,
+            height,
+            words: text
+                .split_whitespace()
+                .map(|w| crate::ocr::DetectedWord {
+                    text: w.to_string(),
+                    x,
+                    y,
+                    width: 10.0,
+                    height,
+                    confidence: None,
+                })
+                .collect(),
+            confidence: None,
+            vertical: false,
+        }
+    }
+
+    fn glossary(names: &[&str]) -> SpeakerConfig {
+        SpeakerConfig {
+            glossary: names
+                .iter()
+                .map(|n| (n.to_string(), n.to_string()))
+                .collect(),
+            ..SpeakerConfig::default()
+        }
+    }
+
+    #[test]
+    fn prefix_needs_name_style_glossary_or_brackets() {
+        let config = glossary(&["Alice"]);
+        let split = |text: &str| split_prefix(text, &config);
+
+        assert_eq!(
+            split("ALICE: Where are you going?"),
+            (
+                Some("ALICE".to_string()),
+                "Where are you going?".to_string()
+            )
+        );
+        assert_eq!(
+            split("alice: Over here!"),
+            (None, "alice: Over here!".to_string())
+        );
+        assert_eq!(
+            split("Alice: Over here!"),
+            (Some("Alice".to_string()), "Over here!".to_string())
+        );
+        assert_eq!(
+            split("【アリス】どこへ行くの？"),
+            (Some("アリス".to_string()), "どこへ行くの？".to_string())
+        );
+        assert_eq!(
+            split("[Old Man] Go away."),
+            (Some("Old Man".to_string()), "Go away.".to_string())
+        );
+
+        // Texto de interface com cara de nome não é falante
+        for text in [
+            "Objective: Find the key",
+            "Warning: Low HP",
+            "Time: 12:30",
+            "TIME: 12:30",
+            "Press A: Jump",
+        ] {
+            assert_eq!(split(text), (None, text.to_string()), "{}", text);
+        }
+    }
+
+    /// Mesmo caminho do modo hotkey: rótulos, layout, associação
+    fn labelled(lines: &[DetectedText], config: &SpeakerConfig) -> Vec<(Option<String>, String)> {
+        let (labels, rest) = take_labels(lines, config);
+        let blocks = crate::layout::group_into_blocks(&rest, &LayoutConfig::default());
+        attach_labels(labels, blocks, config)
+            .into_iter()
+            .map(|(label, block)| (label, block.text))
+            .collect()
+    }
+
+    #[test]
+    fn nameplate_survives_layout_grouping() {
+        let lines = [
+            block("ALICE", 660.0, 460.0, 120.0, 24.0),
+            block("Where are you going", 660.0, 500.0, 600.0, 24.0),
+            block("at this hour?", 660.0, 530.0, 300.0, 24.0),
+        ];
+
+        // Sozinho, o layout junta o nome com a fala
+        let merged = crate::layout::group_into_blocks(&lines, &LayoutConfig::default());
+        assert_eq!(merged.len(), 1);
+
+        assert_eq!(
+            labelled(&lines, &SpeakerConfig::default()),
+            [(
+                Some("ALICE".to_string()),
+                "Where are you going at this hour?".to_string()
+            )]
+        );
+
+        // Nome do glossário em qualquer formato
+        let mut lines = lines;
+        lines[0].text = "Alice".to_string();
+        assert_eq!(
+            labelled(&lines, &glossary(&["alice"]))[0].0.as_deref(),
+            Some("Alice")
+        );
+        assert!(labelled(&lines, &SpeakerConfig::default())
+            .iter()
+            .all(|(label, _)| label.is_none()));
+    }
+
+    #[test]
+    fn labels_need_evidence_and_narrow_box() {
+        let speech = block(
+            "Where are you going at this hour?",
+            100.0,
+            540.0,
+            700.0,
+            30.0,
+        );
+        let none = SpeakerConfig::default();
+
+        // Nome em maiúsculas, estreito, logo acima da fala
+        let labels = labelled(
+            &[block("ALICE", 100.0, 500.0, 120.0, 30.0), speech.clone()],
+            &none,
+        );
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].0.as_deref(), Some("ALICE"));
+
+        // Título comum ou de menu (largura da lista) acima de um bloco:
+        // continua sendo texto
+        for (title, width) in [("Inventory", 150.0), ("Hello", 150.0), ("SETTINGS", 600.0)] {
+            let blocks = labelled(
+                &[block(title, 100.0, 500.0, width, 30.0), speech.clone()],
+                &none,
+            );
+            assert!(blocks.iter().all(|(label, _)| label.is_none()), "{}", title);
+        }
+
+        // Nome do glossário vale em qualquer formato
+        let lines = [block("Alice", 100.0, 500.0, 100.0, 30.0), speech.clone()];
+        assert!(first_line_is_label(&lines, &glossary(&["alice"])));
+        assert!(!first_line_is_label(&lines, &none));
+
+        // Longe demais da fala
+        let far = [block("ALICE", 100.0, 300.0, 120.0, 30.0), speech];
+        assert!(!first_line_is_label(&far, &none));
+    }
+}
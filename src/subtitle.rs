@@ -8,11 +8,15 @@
 // o texto muda e mantendo um histórico de traduções para exibição.
 //
 // Melhorias implementadas:
-// - Levenshtein Distance: comparação mais precisa entre textos
+// - Similaridade configurável: Levenshtein, conjunto de caracteres ou
+//   Jaccard por palavra (`similarity_metric` no SubtitleConfig)
 // - Debounce/Estabilização: só aceita legenda se permanecer estável
+//   por `stable_count` leituras
 //
 // ============================================================================
 
+use crate::cjk;
+use crate::config::{SimilarityMetric, SubtitleConfig};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -20,18 +24,23 @@ use std::time::Instant;
 /// Número máximo de legendas no histórico
 const MAX_SUBTITLE_HISTORY: usize = 10;
 
-/// Threshold de similaridade Levenshtein (0.0 a 1.0)
-/// Textos com similaridade acima deste valor são considerados "iguais"
-const LEVENSHTEIN_SIMILARITY_THRESHOLD: f64 = 0.75;
-
-/// Número mínimo de caracteres para considerar um texto válido
-const MIN_TEXT_LENGTH: usize = 3;
-
 /// Representa uma legenda traduzida no histórico
 #[derive(Debug, Clone)]
 pub struct SubtitleEntry {
     /// Texto traduzido
     pub translated: String,
+    /// Nome de quem está falando (não traduzido)
+    pub speaker: Option<String>,
+}
+
+impl SubtitleEntry {
+    /// Texto exibido ("Nome: tradução" quando há falante)
+    pub fn display_text(&self) -> String {
+        match &self.speaker {
+            Some(speaker) => format!("{}: {}", speaker, self.translated),
+            None => self.translated.clone(),
+        }
+    }
 }
 
 /// Estado do candidato a legenda (para debounce)
@@ -52,8 +61,6 @@ pub struct SubtitleState {
     current_candidate: Arc<Mutex<Option<SubtitleCandidate>>>,
     /// Histórico de legendas traduzidas
     subtitle_history: Arc<Mutex<Vec<SubtitleEntry>>>,
-    /// Número de vezes que o texto precisa ser visto para confirmar (debounce)
-    required_stable_count: u32,
     /// Momento da última detecção de texto (para timeout)
     last_detection_time: Arc<Mutex<Instant>>,
 }
@@ -69,7 +76,6 @@ impl SubtitleState {
             last_confirmed_text: Arc::new(Mutex::new(String::new())),
             current_candidate: Arc::new(Mutex::new(None)),
             subtitle_history: Arc::new(Mutex::new(Vec::new())),
-            required_stable_count: 2,
             last_detection_time: Arc::new(Mutex::new(Instant::now())),
         }
     }
@@ -78,16 +84,23 @@ impl SubtitleState {
     ///
     /// Usa sistema de debounce: só retorna texto para tradução quando
     /// o mesmo texto é detectado múltiplas vezes consecutivas.
+    /// Métrica, threshold e contagens vêm do `config` (lido a cada
+    /// chamada, então mudanças na aba Legendas valem na hora).
     ///
     /// # Retorna
     /// * `Some(texto)` - Se o texto foi confirmado e precisa ser traduzido
     /// * `None` - Se o texto é igual ao anterior, muito curto, ou ainda não estabilizou
-    pub fn process_detected_text(&self, new_text: &str) -> Option<String> {
+    pub fn process_detected_text(&self, new_text: &str, config: &SubtitleConfig) -> Option<String> {
+        let similar = |a: &str, b: &str| {
+            texts_are_similar(config.similarity_metric, config.similarity_threshold, a, b)
+        };
+        let required_stable_count = config.stable_count.max(1);
+
         // Normaliza o texto
         let normalized_new = normalize_text(new_text);
 
         // Se o texto está vazio ou muito curto, ignora
-        if normalized_new.len() < MIN_TEXT_LENGTH {
+        if !is_long_enough(new_text, config) {
             return None;
         }
 
@@ -95,69 +108,63 @@ impl SubtitleState {
         let last_confirmed = self.last_confirmed_text.lock().unwrap();
         let normalized_last = normalize_text(&last_confirmed);
 
-        if texts_are_similar_levenshtein(&normalized_new, &normalized_last) {
+        if similar(&normalized_new, &normalized_last) {
             // Texto é igual ou muito similar ao último confirmado, ignora
             return None;
         }
         drop(last_confirmed); // Libera o lock
 
-        // Sistema de debounce: verifica candidato atual
+        // Sistema de debounce: o mesmo texto do candidato conta mais uma
+        // leitura; um texto diferente vira o novo candidato
         let mut candidate = self.current_candidate.lock().unwrap();
-
-        match &mut *candidate {
-            Some(current) => {
-                // Já temos um candidato, verifica se é o mesmo texto
-                let normalized_candidate = normalize_text(&current.text);
-
-                if texts_are_similar_levenshtein(&normalized_new, &normalized_candidate) {
-                    // Mesmo texto! Incrementa contador
-                    current.seen_count += 1;
-
-                    if current.seen_count >= self.required_stable_count {
-                        // Texto estabilizou! Confirma e retorna para tradução
-                        let confirmed_text = current.text.clone();
-
-                        // Atualiza último texto confirmado
-                        *self.last_confirmed_text.lock().unwrap() = confirmed_text.clone();
-
-                        // Limpa candidato
-                        *candidate = None;
-
-                        info!(
-                            "📺 Legenda confirmada após {} detecções: \"{}\"",
-                            self.required_stable_count, confirmed_text
-                        );
-
-                        return Some(confirmed_text);
-                    } else {
-                        // Ainda não estabilizou
-                        trace!(
-                            "📺 Candidato visto {}/{} vezes",
-                            current.seen_count,
-                            self.required_stable_count
-                        );
-                        return None;
-                    }
-                } else {
-                    // Texto diferente! Substitui candidato
+        let same_candidate = candidate
+            .as_ref()
+            .is_some_and(|current| similar(&normalized_new, &normalize_text(&current.text)));
+
+        let seen_count = match &mut *candidate {
+            Some(current) if same_candidate => {
+                current.seen_count += 1;
+                current.seen_count
+            }
+            _ => {
+                if candidate.is_some() {
                     info!("📺 Novo candidato detectado: \"{}\"", new_text.trim());
-                    *candidate = Some(SubtitleCandidate {
-                        text: new_text.trim().to_string(),
-                        seen_count: 1,
-                    });
-                    return None;
+                } else {
+                    info!("📺 Primeiro candidato detectado: \"{}\"", new_text.trim());
                 }
-            }
-            None => {
-                // Não temos candidato, cria um novo
-                info!("📺 Primeiro candidato detectado: \"{}\"", new_text.trim());
                 *candidate = Some(SubtitleCandidate {
                     text: new_text.trim().to_string(),
                     seen_count: 1,
                 });
-                return None;
+                1
             }
+        };
+
+        if seen_count < required_stable_count {
+            // Ainda não estabilizou
+            trace!(
+                "📺 Candidato visto {}/{} vezes",
+                seen_count,
+                required_stable_count
+            );
+            return None;
         }
+
+        // Texto estabilizou! Confirma, limpa o candidato e retorna para tradução
+        let confirmed_text = candidate.take().map(|c| c.text).unwrap_or_default();
+        *self.last_confirmed_text.lock().unwrap() = confirmed_text.clone();
+
+        info!(
+            "📺 Legenda confirmada após {} detecções: \"{}\"",
+            required_stable_count, confirmed_text
+        );
+
+        Some(confirmed_text)
+    }
+
+    /// Há um candidato esperando ser visto de novo para confirmar?
+    pub fn has_pending_candidate(&self) -> bool {
+        self.current_candidate.lock().unwrap().is_some()
     }
 
     /// Atualiza o tempo da última detecção de texto
@@ -181,11 +188,14 @@ impl SubtitleState {
     }
 
     /// Adiciona uma legenda traduzida ao histórico
-    pub fn add_translated_subtitle(&self, translated: String) {
+    pub fn add_translated_subtitle(&self, translated: String, speaker: Option<String>) {
         let mut history = self.subtitle_history.lock().unwrap();
 
         // Adiciona a nova legenda
-        history.push(SubtitleEntry { translated });
+        history.push(SubtitleEntry {
+            translated,
+            speaker,
+        });
 
         // Remove legendas antigas se exceder o limite
         while history.len() > MAX_SUBTITLE_HISTORY {
@@ -214,20 +224,39 @@ impl SubtitleState {
 
 /// Normaliza texto para comparação
 /// Remove espaços extras, converte para minúsculas, remove caracteres especiais
+///
+/// Em chinês/japonês os espaços inseridos pelo OCR são removidos, então a
+/// comparação fica baseada em caracteres (não em palavras).
 fn normalize_text(text: &str) -> String {
-    text.trim()
+    let collapsed = text
+        .trim()
         .to_lowercase()
         .split_whitespace()
         .collect::<Vec<&str>>()
-        .join(" ")
-        .replace(|c: char| !c.is_alphanumeric() && c != ' ', "")
+        .join(" ");
+
+    cjk::remove_cjk_spaces(&collapsed).replace(|c: char| !c.is_alphanumeric() && c != ' ', "")
+}
+
+/// O texto tem o tamanho mínimo de uma legenda?
+///
+/// Conta caracteres (não bytes) do texto normalizado, com mínimo próprio
+/// para chinês/japonês, onde uma fala inteira cabe em 2-3 caracteres.
+pub fn is_long_enough(text: &str, config: &SubtitleConfig) -> bool {
+    let normalized = normalize_text(text);
+    let min_length = if cjk::contains_cjk(&normalized) {
+        config.min_cjk_text_length
+    } else {
+        config.min_text_length
+    };
+    normalized.chars().count() >= min_length
 }
 
 /// Calcula a distância de Levenshtein entre duas strings
 ///
 /// A distância de Levenshtein é o número mínimo de edições (inserções,
 /// remoções ou substituições) necessárias para transformar uma string em outra.
-fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let s1_chars: Vec<char> = s1.chars().collect();
     let s2_chars: Vec<char> = s2.chars().collect();
 
@@ -272,7 +301,8 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 /// # Retorna
 /// Valor entre 0.0 (totalmente diferentes) e 1.0 (idênticas)
 fn levenshtein_similarity(s1: &str, s2: &str) -> f64 {
-    let max_len = s1.len().max(s2.len());
+    // Conta caracteres (não bytes): um ideograma ocupa 3 bytes em UTF-8
+    let max_len = s1.chars().count().max(s2.chars().count());
 
     if max_len == 0 {
         return 1.0; // Ambas vazias = idênticas
@@ -282,8 +312,20 @@ fn levenshtein_similarity(s1: &str, s2: &str) -> f64 {
     1.0 - (distance as f64 / max_len as f64)
 }
 
-/// Verifica se dois textos são similares usando Levenshtein Distance
-fn texts_are_similar_levenshtein(text1: &str, text2: &str) -> bool {
+/// Similaridade entre dois textos normalizados pela métrica escolhida
+///
+/// # Retorna
+/// Valor entre 0.0 (totalmente diferentes) e 1.0 (idênticas)
+pub fn similarity(metric: SimilarityMetric, s1: &str, s2: &str) -> f64 {
+    match metric {
+        SimilarityMetric::Levenshtein => levenshtein_similarity(s1, s2),
+        SimilarityMetric::Charset => charset_similarity(s1, s2),
+        SimilarityMetric::Jaccard => jaccard_similarity(s1, s2),
+    }
+}
+
+/// Verifica se dois textos são similares (similaridade >= threshold)
+fn texts_are_similar(metric: SimilarityMetric, threshold: f64, text1: &str, text2: &str) -> bool {
     // Se um está vazio e outro não, são diferentes
     if text1.is_empty() != text2.is_empty() {
         return false;
@@ -294,10 +336,10 @@ fn texts_are_similar_levenshtein(text1: &str, text2: &str) -> bool {
         return true;
     }
 
-    let similarity = levenshtein_similarity(text1, text2);
+    let similarity = similarity(metric, text1, text2);
 
     // Log para debug
-    if similarity > 0.5 && similarity < LEVENSHTEIN_SIMILARITY_THRESHOLD {
+    if similarity > 0.5 && similarity < threshold {
         trace!(
             "📊 Similaridade: {:.2}% entre \"{}\" e \"{}\"",
             similarity * 100.0,
@@ -306,38 +348,115 @@ fn texts_are_similar_levenshtein(text1: &str, text2: &str) -> bool {
         );
     }
 
-    similarity >= LEVENSHTEIN_SIMILARITY_THRESHOLD
+    similarity >= threshold
 }
 
-/// Verifica se dois textos são similares (método legado com HashSet)
-/// Mantido para referência, mas não usado
-#[allow(dead_code)]
-fn texts_are_similar_charset(text1: &str, text2: &str) -> bool {
-    if text1.is_empty() != text2.is_empty() {
-        return false;
-    }
-
-    if text1.is_empty() && text2.is_empty() {
-        return true;
-    }
-
-    let len1 = text1.len();
-    let len2 = text2.len();
-    let len_diff = (len1 as i32 - len2 as i32).abs() as usize;
+/// Similaridade pelo conjunto de caracteres (método antigo com HashSet)
+///
+/// Ignora a ordem, então aguenta letras trocadas de lugar, mas textos
+/// com tamanhos muito diferentes (mais de 20%) contam como diferentes.
+fn charset_similarity(s1: &str, s2: &str) -> f64 {
+    let len1 = s1.chars().count();
+    let len2 = s2.chars().count();
     let max_len = len1.max(len2);
 
-    if max_len > 0 && len_diff > max_len / 5 {
-        return false;
+    if max_len == 0 {
+        return 1.0;
+    }
+    if len1.abs_diff(len2) > max_len / 5 {
+        return 0.0;
     }
 
-    let chars1: HashSet<char> = text1.chars().collect();
-    let chars2: HashSet<char> = text2.chars().collect();
+    let chars1: HashSet<char> = s1.chars().collect();
+    let chars2: HashSet<char> = s2.chars().collect();
     let common = chars1.intersection(&chars2).count();
     let total = chars1.union(&chars2).count();
 
+    common as f64 / total as f64
+}
+
+/// Similaridade de Jaccard entre as palavras dos dois textos
+///
+/// Em chinês/japonês não há espaços: cada ideograma conta como palavra.
+fn jaccard_similarity(s1: &str, s2: &str) -> f64 {
+    let tokens = |text: &str| -> HashSet<String> {
+        if cjk::contains_cjk(text) {
+            text.chars()
+                .filter(|c| !c.is_whitespace())
+                .map(String::from)
+                .collect()
+        } else {
+            text.split_whitespace().map(String::from).collect()
+        }
+    };
+
+    let tokens1 = tokens(s1);
+    let tokens2 = tokens(s2);
+    let total = tokens1.union(&tokens2).count();
+
     if total == 0 {
-        return true;
+        return 1.0;
     }
 
-    (common as f64 / total as f64) > 0.85
+    tokens1.intersection(&tokens2).count() as f64 / total as f64
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_and_stable_count_follow_config() {
+        let a = normalize_text("The gate is locked, find the key.");
+        let b = normalize_text("The gate is Iocked, find the key.");
+        let c = normalize_text("Nobody has been here for years.");
+        for metric in [
+            SimilarityMetric::Levenshtein,
+            SimilarityMetric::Charset,
+            SimilarityMetric::Jaccard,
+        ] {
+            let threshold = metric.default_threshold();
+            assert!(texts_are_similar(metric, threshold, &a, &b), "{:?}", metric);
+            assert!(
+                !texts_are_similar(metric, threshold, &a, &c),
+                "{:?}",
+                metric
+            );
+        }
+        assert!((jaccard_similarity("a b c d", "a b c e") - 0.6).abs() < 1e-9);
+        assert!(jaccard_similarity("門が閉まっている", "門が開いている") > 0.5);
+
+        let mut config = SubtitleConfig {
+            stable_count: 3,
+            ..SubtitleConfig::default()
+        };
+        let state = SubtitleState::new(0, 0);
+        assert_eq!(state.process_detected_text("Hello there", &config), None);
+        assert_eq!(state.process_detected_text("Hello there", &config), None);
+        assert_eq!(
+            state.process_detected_text("Hello there", &config),
+            Some("Hello there".to_string())
+        );
+        assert_eq!(state.process_detected_text("Hello there", &config), None);
+
+        config.stable_count = 1;
+        config.min_text_length = 20;
+        assert_eq!(state.process_detected_text("Go away", &config), None);
+        config.min_text_length = 3;
+        assert_eq!(
+            state.process_detected_text("Go away", &config),
+            Some("Go away".to_string())
+        );
+
+        // Conta caracteres, não bytes: "はい" tem 6 bytes mas 2 caracteres
+        config.min_cjk_text_length = 2;
+        assert!(is_long_enough("はい", &config));
+        assert!(!is_long_enough("は", &config));
+        assert!(!is_long_enough("é!", &config));
+        assert!(!is_long_enough(" .. ", &config));
+    }
 }
@@ -0,0 +1,534 @@
+// game-translator/src/text_regions.rs
+
+// ============================================================================
+// MÓDULO TEXT_REGIONS - Detecção automática de caixas de texto
+// ============================================================================
+//
+// Acha onde há texto na tela sem OCR, só olhando a forma dos pixels:
+//
+// 1. Bordas (Sobel binarizado, o mesmo de filters.rs)
+// 2. Componentes conectados das bordas → candidatos a letra (filtrados
+//    por altura, largura e densidade: linhas finas, molduras enormes e
+//    pontinhos soltos ficam de fora)
+// 3. Letras vizinhas e da mesma altura → linhas de texto
+// 4. Linhas empilhadas e alinhadas → blocos (caixas de texto)
+// 5. Cada bloco é classificado pela posição:
+//    - Legenda: até 3 linhas, centralizada, na metade de baixo
+//    - Diálogo: metade de baixo, várias linhas ou bem larga
+//    - Outro: resto (HUD, menus, placas)
+//
+// O resultado alimenta o seletor de região (caixas verdes clicáveis) e
+// os modos de rastreamento automático (ver main.rs). `detect_area`
+// captura a área certa (janela do jogo ou monitor) e converte as caixas
+// para regiões do config e para o desktop virtual.
+//
+// A detecção roda na imagem reduzida para no máximo 1920 de largura:
+// uma captura 4K leva o mesmo tempo que uma 1080p.
+//
+// ============================================================================
+
+use crate::capture::CaptureSource;
+use crate::config::{GameWindowConfig, RegionConfig, TextDetectConfig};
+use crate::filters;
+use crate::game_window::{self, ClientArea};
+use crate::screenshot;
+use anyhow::Result;
+use image::{imageops::FilterType, DynamicImage, GrayImage};
+
+/// Largura máxima da imagem usada na detecção
+const MAX_DETECT_WIDTH: u32 = 1920;
+
+/// Quantos candidatos são devolvidos (os de maior pontuação)
+pub const MAX_CANDIDATES: usize = 8;
+
+// ============================================================================
+// TIPOS
+// ============================================================================
+
+/// Tipo provável de uma caixa de texto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextRegionKind {
+    /// Legenda: poucas linhas, centralizada, embaixo
+    Subtitle,
+    /// Caixa de diálogo: embaixo, várias linhas ou larga
+    Dialogue,
+    /// Qualquer outro texto (HUD, menu, placa)
+    Other,
+}
+
+/// Uma caixa de texto encontrada, em coordenadas da imagem analisada
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Quantas linhas de texto o bloco tem
+    pub lines: u32,
+    /// Pontuação (mais letras = mais provável ser texto de verdade)
+    pub score: f32,
+    pub kind: TextRegionKind,
+}
+
+/// Retângulo com bordas inclusivas (x0..=x1, y0..=y1)
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x0: u32,
+    y0: u32,
+    x1: u32,
+    y1: u32,
+}
+
+impl Rect {
+    fn width(&self) -> u32 {
+        self.x1 - self.x0 + 1
+    }
+
+    fn height(&self) -> u32 {
+        self.y1 - self.y0 + 1
+    }
+
+    fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            x0: self.x0.min(other.x0),
+            y0: self.y0.min(other.y0),
+            x1: self.x1.max(other.x1),
+            y1: self.y1.max(other.y1),
+        }
+    }
+
+    /// Pixels de sobreposição vertical (0 se não se tocam)
+    fn vertical_overlap(&self, other: &Rect) -> u32 {
+        let top = self.y0.max(other.y0);
+        let bottom = self.y1.min(other.y1);
+        if bottom >= top {
+            bottom - top + 1
+        } else {
+            0
+        }
+    }
+
+    fn overlaps_horizontally(&self, other: &Rect) -> bool {
+        self.x0 <= other.x1 && other.x0 <= self.x1
+    }
+}
+
+/// Linha de texto (ou bloco, quando várias linhas se juntam)
+#[derive(Debug, Clone)]
+struct TextLine {
+    rect: Rect,
+    /// Altura das letras (a maior da linha / média das linhas no bloco)
+    char_height: f32,
+    glyphs: u32,
+    lines: u32,
+}
+
+// ============================================================================
+// DETECÇÃO
+// ============================================================================
+
+/// Procura caixas de texto na imagem
+///
+/// Devolve no máximo `MAX_CANDIDATES` regiões, da mais para a menos
+/// provável, já com uma margem em volta do texto.
+pub fn detect(image: &DynamicImage, config: &TextDetectConfig) -> Vec<TextRegion> {
+    let (orig_width, orig_height) = (image.width(), image.height());
+    if orig_width < 3 || orig_height < 3 {
+        return Vec::new();
+    }
+
+    // Reduz imagens grandes (a escala vale também para as alturas do config)
+    let scale = if orig_width > MAX_DETECT_WIDTH {
+        MAX_DETECT_WIDTH as f32 / orig_width as f32
+    } else {
+        1.0
+    };
+    let gray: GrayImage = if scale < 1.0 {
+        let height = ((orig_height as f32 * scale).round() as u32).max(3);
+        image
+            .resize_exact(MAX_DETECT_WIDTH, height, FilterType::Triangle)
+            .to_luma8()
+    } else {
+        image.to_luma8()
+    };
+
+    let (width, height) = (gray.width(), gray.height());
+    let edges = filters::sobel_threshold(
+        gray.as_raw(),
+        width as usize,
+        height as usize,
+        config.edge_threshold,
+    );
+
+    let min_char = (config.min_char_height as f32 * scale).max(3.0);
+    let max_char = (config.max_char_height as f32 * scale).max(min_char);
+
+    let glyphs: Vec<Rect> = connected_components(&edges, width, height)
+        .into_iter()
+        .filter(|(rect, pixels)| is_glyph(rect, *pixels, min_char, max_char))
+        .map(|(rect, _)| rect)
+        .collect();
+
+    let blocks = group_blocks(group_lines(glyphs));
+
+    let mut regions: Vec<TextRegion> = blocks
+        .iter()
+        .map(|block| {
+            let kind = classify(&block.rect, block.lines, width, height);
+            let bonus = if kind == TextRegionKind::Other {
+                1.0
+            } else {
+                1.5
+            };
+
+            // Margem em volta do texto (o OCR erra letras coladas na borda)
+            let pad = (block.char_height * 0.6).round() as u32;
+            let x0 = block.rect.x0.saturating_sub(pad);
+            let y0 = block.rect.y0.saturating_sub(pad);
+            let x1 = (block.rect.x1 + pad).min(width - 1);
+            let y1 = (block.rect.y1 + pad).min(height - 1);
+
+            // De volta para a resolução original
+            let x = ((x0 as f32 / scale) as u32).min(orig_width - 1);
+            let y = ((y0 as f32 / scale) as u32).min(orig_height - 1);
+            let right = (((x1 + 1) as f32 / scale).ceil() as u32).min(orig_width);
+            let bottom = (((y1 + 1) as f32 / scale).ceil() as u32).min(orig_height);
+
+            TextRegion {
+                x,
+                y,
+                width: right - x,
+                height: bottom - y,
+                lines: block.lines,
+                score: block.glyphs as f32 * bonus,
+                kind,
+            }
+        })
+        .collect();
+
+    regions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    regions.truncate(MAX_CANDIDATES);
+    regions
+}
+
+/// A região mais provável de um tipo (a lista já vem ordenada)
+pub fn best(regions: &[TextRegion], kind: TextRegionKind) -> Option<&TextRegion> {
+    regions.iter().find(|region| region.kind == kind)
+}
+
+/// Componentes 8-conectados dos pixels acesos: (retângulo, nº de pixels)
+fn connected_components(edges: &[u8], width: u32, height: u32) -> Vec<(Rect, u32)> {
+    let (w, h) = (width as usize, height as usize);
+    let mut visited = vec![false; w * h];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut components = Vec::new();
+
+    for start in 0..w * h {
+        if edges[start] == 0 || visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        stack.push(start);
+        let (sx, sy) = ((start % w) as u32, (start / w) as u32);
+        let mut rect = Rect {
+            x0: sx,
+            y0: sy,
+            x1: sx,
+            y1: sy,
+        };
+        let mut pixels = 0u32;
+
+        while let Some(index) = stack.pop() {
+            pixels += 1;
+            let (x, y) = (index % w, index / w);
+            rect.x0 = rect.x0.min(x as u32);
+            rect.x1 = rect.x1.max(x as u32);
+            rect.y0 = rect.y0.min(y as u32);
+            rect.y1 = rect.y1.max(y as u32);
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(h - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(w - 1) {
+                    let neighbor = ny * w + nx;
+                    if edges[neighbor] != 0 && !visited[neighbor] {
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        components.push((rect, pixels));
+    }
+
+    components
+}
+
+/// O componente tem cara de letra (ou de palavra com letras coladas)?
+fn is_glyph(rect: &Rect, pixels: u32, min_char: f32, max_char: f32) -> bool {
+    let (w, h) = (rect.width() as f32, rect.height() as f32);
+    if h < min_char || h > max_char || w > h * 15.0 {
+        return false;
+    }
+    pixels as f32 / (w * h) >= 0.08
+}
+
+/// Junta letras vizinhas da mesma altura em linhas
+fn group_lines(mut glyphs: Vec<Rect>) -> Vec<TextLine> {
+    glyphs.sort_by_key(|rect| rect.x0);
+
+    let mut lines: Vec<TextLine> = Vec::new();
+    for glyph in glyphs {
+        let glyph_height = glyph.height() as f32;
+
+        let target = lines.iter_mut().find(|line| {
+            let ratio = line.char_height.max(glyph_height) / line.char_height.min(glyph_height);
+            let overlap = line.rect.vertical_overlap(&glyph) as f32;
+            let gap = glyph.x0 as f32 - line.rect.x1 as f32;
+            ratio <= 2.0
+                && overlap >= 0.5 * line.char_height.min(glyph_height)
+                && gap <= 1.5 * line.char_height
+        });
+
+        match target {
+            Some(line) => {
+                line.rect = line.rect.union(&glyph);
+                line.char_height = line.char_height.max(glyph_height);
+                line.glyphs += 1;
+            }
+            None => lines.push(TextLine {
+                rect: glyph,
+                char_height: glyph_height,
+                glyphs: 1,
+                lines: 1,
+            }),
+        }
+    }
+
+    // Letra sozinha ou linha curta demais é ruído (ícone, pedaço de HUD)
+    lines.retain(|line| line.glyphs >= 2 && line.rect.width() as f32 >= 3.0 * line.char_height);
+    lines
+}
+
+/// Junta linhas empilhadas (mesma altura de letra, alinhadas) em blocos
+fn group_blocks(mut lines: Vec<TextLine>) -> Vec<TextLine> {
+    lines.sort_by_key(|line| line.rect.y0);
+
+    let mut blocks: Vec<TextLine> = Vec::new();
+    for line in lines {
+        let target = blocks.iter_mut().find(|block| {
+            let ratio =
+                block.char_height.max(line.char_height) / block.char_height.min(line.char_height);
+            let gap = line.rect.y0 as f32 - block.rect.y1 as f32;
+            ratio <= 1.8
+                && gap <= 1.2 * block.char_height
+                && block.rect.overlaps_horizontally(&line.rect)
+        });
+
+        match target {
+            Some(block) => {
+                block.rect = block.rect.union(&line.rect);
+                block.char_height = (block.char_height * block.lines as f32 + line.char_height)
+                    / (block.lines + 1) as f32;
+                block.glyphs += line.glyphs;
+                block.lines += 1;
+            }
+            None => blocks.push(line),
+        }
+    }
+
+    blocks
+}
+
+/// Classifica um bloco pela posição na imagem
+fn classify(rect: &Rect, lines: u32, width: u32, height: u32) -> TextRegionKind {
+    let center = (rect.x0 + rect.x1) as f32 / 2.0;
+    let centered = (center - width as f32 / 2.0).abs() <= width as f32 * 0.12;
+    let top = rect.y0 as f32 / height as f32;
+
+    if lines <= 3 && centered && top >= 0.5 {
+        TextRegionKind::Subtitle
+    } else if top >= 0.45 && (lines >= 2 || rect.width() as f32 >= width as f32 * 0.3) {
+        TextRegionKind::Dialogue
+    } else {
+        TextRegionKind::Other
+    }
+}
+
+// ============================================================================
+// CAPTURA PARA DETECÇÃO
+// ============================================================================
+
+/// Caixas achadas numa captura da janela do jogo ou de um monitor
+pub struct AreaDetection {
+    /// A captura analisada (para recortar a caixa escolhida sem capturar de novo)
+    pub image: DynamicImage,
+    /// Canto da captura no desktop virtual
+    pub origin: (i32, i32),
+    /// Área cliente da janela do jogo (None = captura do monitor)
+    pub window: Option<ClientArea>,
+    /// Monitor capturado (quando não há janela presa)
+    pub monitor: Option<u32>,
+    pub regions: Vec<TextRegion>,
+}
+
+impl AreaDetection {
+    /// Canto da caixa no desktop virtual
+    pub fn virtual_origin(&self, region: &TextRegion) -> (i32, i32) {
+        (
+            self.origin.0 + region.x as i32,
+            self.origin.1 + region.y as i32,
+        )
+    }
+
+    /// Caixa como região do config (relativa à janela ou ao monitor)
+    pub fn to_config(&self, region: &TextRegion) -> RegionConfig {
+        match self.window {
+            Some(client) => {
+                let (x, y) = self.virtual_origin(region);
+                RegionConfig {
+                    x: (x - client.x).max(0) as u32,
+                    y: (y - client.y).max(0) as u32,
+                    width: region.width,
+                    height: region.height,
+                    monitor: None,
+                    window_size: Some([client.width, client.height]),
+                }
+            }
+            None => RegionConfig {
+                x: region.x,
+                y: region.y,
+                width: region.width,
+                height: region.height,
+                monitor: self.monitor,
+                window_size: None,
+            },
+        }
+    }
+
+    /// Recorte da captura com a caixa (o que o OCR vai ler)
+    pub fn crop(&self, region: &TextRegion) -> DynamicImage {
+        self.image
+            .crop_imm(region.x, region.y, region.width, region.height)
+    }
+}
+
+/// Captura a janela do jogo inteira (se presa a uma) ou o monitor e
+/// procura caixas de texto nela
+pub fn detect_area(
+    source: &mut dyn CaptureSource,
+    window: &GameWindowConfig,
+    monitor: Option<u32>,
+    config: &TextDetectConfig,
+) -> Result<AreaDetection> {
+    let (image, origin, client, monitor) = match game_window::resolve_window(window)? {
+        Some(target) => {
+            let image = source.capture_region(
+                target.monitor_index(),
+                target.x,
+                target.y,
+                target.width,
+                target.height,
+            )?;
+            (image, target.virtual_origin(), target.bounds, None)
+        }
+        None => {
+            // Sem monitor detectado (ex: replay sem tela) a origem é (0, 0)
+            let info = screenshot::monitor(monitor).ok();
+            let image = source.capture_screen(monitor)?;
+            let origin = info.map(|m| (m.x, m.y)).unwrap_or((0, 0));
+            (image, origin, None, info.map(|m| m.index).or(monitor))
+        }
+    };
+
+    let regions = detect(&image, config);
+    Ok(AreaDetection {
+        image,
+        origin,
+        window: client,
+        monitor,
+        regions,
+    })
+}
+
+// ============================================================================
+// TESTES
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn fill(image: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32) {
+        for py in y..y + h {
+            for px in x..x + w {
+                image.put_pixel(px, py, Rgba([240, 240, 240, 255]));
+            }
+        }
+    }
+
+    /// Uma "linha de texto": `count` letras 12x20 separadas por 6 pixels
+    fn text_line(image: &mut RgbaImage, x: u32, y: u32, count: u32) {
+        for i in 0..count {
+            fill(image, x + i * 18, y, 12, 20);
+        }
+    }
+
+    fn scene() -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(800, 600, Rgba([20, 20, 30, 255]));
+
+        // Moldura grande no topo (alta demais para ser letra)
+        fill(&mut image, 100, 40, 600, 3);
+        fill(&mut image, 100, 240, 600, 3);
+        fill(&mut image, 100, 40, 3, 203);
+        fill(&mut image, 697, 40, 3, 203);
+
+        // Pontinhos espalhados (pequenos demais para serem letras)
+        for i in 0..60u32 {
+            let (x, y) = ((i * 97) % 780 + 5, (i * 53) % 300 + 260);
+            fill(&mut image, x, y, 2, 2);
+        }
+
+        image
+    }
+
+    #[test]
+    fn finds_centered_subtitle_and_ignores_noise() {
+        let mut image = scene();
+        // Legenda de 20 letras centralizada (20·18 - 6 = 354 de largura)
+        text_line(&mut image, 223, 520, 20);
+
+        let regions = detect(
+            &DynamicImage::ImageRgba8(image),
+            &TextDetectConfig::default(),
+        );
+
+        let subtitle = best(&regions, TextRegionKind::Subtitle).expect("legenda não achada");
+        assert_eq!(subtitle.lines, 1);
+        assert!(subtitle.x <= 223 && subtitle.x + subtitle.width >= 223 + 354);
+        assert!(subtitle.y <= 520 && subtitle.y + subtitle.height >= 540);
+        assert!(subtitle.height < 60, "margem grande demais: {:?}", subtitle);
+        assert!(regions
+            .iter()
+            .all(|region| region.y > 250 || region.y + region.height < 40));
+    }
+
+    #[test]
+    fn left_aligned_block_is_dialogue() {
+        let mut image = scene();
+        for line in 0..3 {
+            text_line(&mut image, 60, 430 + line * 30, 25);
+        }
+
+        let regions = detect(
+            &DynamicImage::ImageRgba8(image),
+            &TextDetectConfig::default(),
+        );
+
+        let dialogue = best(&regions, TextRegionKind::Dialogue).expect("diálogo não achado");
+        assert_eq!(dialogue.lines, 3);
+        assert!(dialogue.x <= 60 && dialogue.y <= 430);
+        assert!(best(&regions, TextRegionKind::Subtitle).is_none());
+    }
+}
@@ -0,0 +1,73 @@
+// game-translator/tests/ocr_corpus.rs
+
+// ============================================================================
+// TESTE DE REGRESSÃO DO OCR - Corpus em tests/corpus
+// ============================================================================
+//
+// Roda o modo headless (--ocr-score) com o backend Tesseract, então
+// funciona no Linux/CI:
+//
+//   cargo test --features tesseract
+//
+// Falha se a taxa de erro de caracteres (CER) do pré-processamento de
+// tests/corpus/preprocess.json passar de MAX_CER.
+//
+// O binário roda numa pasta temporária (config.json padrão), então o
+// resultado não depende do config.json local nem do --autotune.
+//
+// De onde vem a fixture (tests/corpus/preprocess.json): é uma cópia do
+// display.preprocess do config.json distribuído, ou seja, o pipeline que
+// o usuário roda sem mexer em nada. Não foi ajustada para este corpus.
+// Com edge_detection ligado, o `threshold` dela não é usado (ver
+// PreprocessConfig::pipeline).
+//
+// MAX_CER é a meta e ainda não foi medida com essa fixture. O job
+// ocr-corpus da CI (.github/workflows/ci.yml) roda com --nocapture, e o
+// relatório com o CER medido fica no log. Para trocar a fixture por uma
+// ajustada ao corpus:
+//
+//   cd $(mktemp -d)
+//   game-translator --autotune <repo>/tests/corpus --ocr-backend tesseract
+//
+// Depois copie o display.preprocess do config.json gerado para
+// preprocess.json e anote aqui o CER do relatório.
+//
+// ============================================================================
+
+#![cfg(feature = "tesseract")]
+
+use std::path::Path;
+use std::process::Command;
+
+/// CER máximo aceito no corpus de teste
+const MAX_CER: &str = "0.05";
+
+#[test]
+fn corpus_cer_within_limit() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let workdir =
+        std::env::temp_dir().join(format!("game-translator-corpus-{}", std::process::id()));
+    std::fs::create_dir_all(&workdir).expect("Falha ao criar pasta temporária");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_game-translator"))
+        .current_dir(&workdir)
+        .arg("--ocr-score")
+        .arg(&corpus)
+        .arg("--score-config")
+        .arg(corpus.join("preprocess.json"))
+        .args(["--ocr-backend", "tesseract", "--max-cer", MAX_CER])
+        .output()
+        .expect("Falha ao executar game-translator");
+    let _ = std::fs::remove_dir_all(&workdir);
+
+    // Visível com --nocapture (a CI guarda o CER medido no log)
+    let report = String::from_utf8_lossy(&output.stdout);
+    println!("{}", report);
+    assert!(
+        output.status.success(),
+        "CER acima de {}:\n{}\n{}",
+        MAX_CER,
+        report,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}